@@ -0,0 +1,78 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tsp::distance::{DistGraph, DistancesIdx};
+use tsp::sparse_graph::SparseGraphIdx;
+
+// counts bytes currently allocated on the heap
+struct CountingAlloc;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        ALLOCATED.fetch_add(new_size, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+const SIZE: u32 = 1000;
+
+// every 20th pair is feasible, 5% density
+fn dense() -> DistancesIdx<'static> {
+    DistancesIdx::from_custom_fn(SIZE, |apt1, apt2| {
+        Some((apt1 + apt2) as f64).filter(|_| (apt1 * 7 + apt2 * 13) % 20 == 0)
+    })
+}
+
+fn sparse() -> DistancesIdx<'static> {
+    DistancesIdx::from_dist_graph(DistGraph::Sparse(SparseGraphIdx::from(
+        dense().graph.to_dense().as_ref(),
+    )))
+}
+
+// heap bytes held by the result of `f`
+fn heap_size<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    let value = f();
+    (value, ALLOCATED.load(Ordering::Relaxed) - before)
+}
+
+fn dist_graph(c: &mut Criterion) {
+    let (dense, dense_bytes) = heap_size(dense);
+    let (sparse, sparse_bytes) = heap_size(sparse);
+    println!(
+        "{SIZE} nodes at {:.01}% density: dense {dense_bytes} bytes, sparse {sparse_bytes} bytes",
+        dense.graph.density() * 100.0
+    );
+
+    let mut group = c.benchmark_group("DistancesIdx::between");
+    for (name, distances) in [("dense", &dense), ("sparse", &sparse)] {
+        group.bench_with_input(BenchmarkId::new(name, SIZE), distances, |b, distances| {
+            b.iter(|| {
+                (0..SIZE)
+                    .filter_map(|apt| distances.between(black_box(apt), SIZE - 1 - apt))
+                    .sum::<f64>()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, dist_graph);
+criterion_main!(benches);
@@ -0,0 +1,74 @@
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use std::hint::black_box;
+use tsp::graph::GraphIdx;
+use tsp::math::great_circle;
+use tsp::model::{Airport, AirportIdx};
+use tsp::types::field::coord::Coord;
+
+fn airports(n: usize) -> Vec<Airport> {
+    (0..n)
+        .map(|i| Airport {
+            icao: format!("A{i:05}"),
+            name: String::new(),
+            coord: Coord {
+                lat: (i as f64 * 0.37).sin(),
+                lon: (i as f64 * 0.73).cos() * 3.0,
+            },
+        })
+        .collect()
+}
+
+fn graph_new(c: &mut Criterion) {
+    let mut group = c.benchmark_group("GraphIdx::new");
+    group.sample_size(10);
+    for n in [500, 2000] {
+        let airports = airports(n);
+        let apt_idx = AirportIdx::new(&airports).unwrap();
+        group.bench_with_input(BenchmarkId::new("sequential", n), &apt_idx, |b, apt_idx| {
+            b.iter(|| {
+                GraphIdx::new_seq(black_box(apt_idx), |apt1, apt2| {
+                    great_circle(apt1.coord, apt2.coord)
+                })
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", n), &apt_idx, |b, apt_idx| {
+            b.iter(|| {
+                GraphIdx::new_par(black_box(apt_idx), |apt1, apt2| {
+                    great_circle(apt1.coord, apt2.coord)
+                })
+            })
+        });
+    }
+    group.finish();
+}
+
+fn transform(c: &mut Criterion) {
+    let mut group = c.benchmark_group("GraphIdx::transform");
+    group.sample_size(10);
+    for n in [100, 1000, 2000] {
+        let airports = airports(n);
+        let apt_idx = AirportIdx::new(&airports).unwrap();
+        let graph = GraphIdx::new(&apt_idx, |apt1, apt2| great_circle(apt1.coord, apt2.coord));
+        group.bench_with_input(BenchmarkId::new("sequential", n), &graph, |b, graph| {
+            b.iter(|| black_box(graph).transform(|dist| (-dist).exp()))
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", n), &graph, |b, graph| {
+            b.iter(|| black_box(graph).transform_parallel(|dist| (-dist).exp()))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("inplace_parallel", n),
+            &graph,
+            |b, graph| {
+                b.iter_batched_ref(
+                    || graph.clone(),
+                    |graph| graph.transform_inplace_par(|dist| *dist = (-*dist).exp()),
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, graph_new, transform);
+criterion_main!(benches);
@@ -0,0 +1,106 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fs::File;
+use std::io::{BufReader, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{fs, io};
+use tsp::parser::file::{parse_airport_primary_records, parse_airport_primary_records_streaming};
+use tsp::types::record::OwnedAirportPrimaryRecord;
+
+// counts bytes currently allocated on the heap and their peak
+struct CountingAlloc;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+fn grow(size: usize) {
+    let allocated = ALLOCATED.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(allocated, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        grow(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        grow(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+const KLAX: &str = "SUSAP KLAXK2ALAX     0     \
+    129YHN33563299W118242898E012000128         1800018000C    \
+    MNAR    LOS ANGELES INTL              310231906";
+
+const LAX: &str = "SUSAD        LAX   K2011360\
+    VTHW N33560033W118255598LAX N33560033W118255598E0150001811     \
+    NARLOS ANGELES                   123451906";
+
+// airport records among nine times as many navaid records, about 10 MB
+const LINES: usize = 80_000;
+
+fn write_input(path: &Path) -> io::Result<()> {
+    let mut file = io::BufWriter::new(File::create(path)?);
+    for i in 0..LINES {
+        writeln!(file, "{}\r", if i % 10 == 0 { KLAX } else { LAX })?;
+    }
+    file.flush()
+}
+
+fn read_to_end(path: &Path) -> Vec<OwnedAirportPrimaryRecord> {
+    let buf = fs::read(path).unwrap();
+    parse_airport_primary_records(&buf)
+        .map(|rec| OwnedAirportPrimaryRecord::from(&rec))
+        .collect()
+}
+
+fn streaming(path: &Path) -> Vec<OwnedAirportPrimaryRecord> {
+    parse_airport_primary_records_streaming(BufReader::new(File::open(path).unwrap()))
+        .collect::<io::Result<_>>()
+        .unwrap()
+}
+
+// peak heap bytes allocated while running `f`
+fn peak_heap<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    PEAK.store(before, Ordering::Relaxed);
+    let value = f();
+    (value, PEAK.load(Ordering::Relaxed) - before)
+}
+
+fn parse_streaming(c: &mut Criterion) {
+    let path = std::env::temp_dir().join(format!("tsp-bench-{}.dat", std::process::id()));
+    write_input(&path).unwrap();
+
+    let (buffered, buffered_bytes) = peak_heap(|| read_to_end(&path));
+    let (streamed, streamed_bytes) = peak_heap(|| streaming(&path));
+    assert_eq!(buffered, streamed);
+    println!(
+        "{} bytes of input, peak heap: read_to_end {buffered_bytes} bytes, \
+        streaming {streamed_bytes} bytes",
+        fs::metadata(&path).unwrap().len()
+    );
+
+    let mut group = c.benchmark_group("parse_airport_primary_records");
+    group.sample_size(10);
+    group.bench_function("read_to_end", |b| b.iter(|| read_to_end(&path)));
+    group.bench_function("streaming", |b| b.iter(|| streaming(&path)));
+    group.finish();
+
+    fs::remove_file(&path).unwrap();
+}
+
+criterion_group!(benches, parse_streaming);
+criterion_main!(benches);
@@ -1,21 +1,83 @@
-use crate::distance::DistancesIdx;
+use crate::distance::{DistGraph, DistancesIdx};
 use crate::graph::GraphIdx;
 use crate::kahan::KahanAdder;
+use crate::par::prelude::*;
 use crate::reusable_weighted_index::CumulativeWeightsWrapper;
+use crate::sparse_graph::SparseGraphIdx;
 use crate::util::cycling;
 use bitvec::bitvec;
 use bitvec::vec::BitVec;
 use lambert_w::lambert_w0;
 use rand::distributions::Distribution;
-use rand::{random, Rng};
+use rand::{random, Rng, SeedableRng};
 use rand_pcg::Pcg64Mcg;
-use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
-use rayon::slice::ParallelSliceMut;
 use std::borrow::Cow;
 use std::f64;
+use std::marker::PhantomData;
 
 const INIT_INTENSITY_MULTIPLIER: f64 = 10.0;
-const MINIMAL_INTENSITY: f64 = 1e-5;
+pub(crate) const MINIMAL_INTENSITY: f64 = 1e-5;
+const SPARSE_DENSITY: f64 = 0.5;
+const MIN_LOCAL_SEARCH_GAIN: f64 = 1e-9;
+const NEAREST_NEIGHBOR_INTENSITY_MULTIPLIER: f64 = 10.0;
+/// Traversals an ant makes per iteration before giving up, the graph may be connected without
+/// any path visiting every node
+const MAX_TRAVERSAL_ATTEMPTS: u32 = 1000;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum AcoVariant {
+    #[default]
+    AntSystem,
+    /// Min-Max Ant System: pheromones start at `tau_max_init` and are kept within
+    /// `[tau_max / (2 * size), tau_max]`, where `tau_max` follows the best-so-far cycle
+    Mmas { tau_max_init: f64 },
+    /// Ant Colony System: pheromones start at `tau0`, every edge traversed by an ant decays
+    /// towards `tau0` with rate `xi`, and only the best-so-far cycle is reinforced
+    Acs { xi: f64, tau0: f64 },
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum AcoInit {
+    #[default]
+    Uniform,
+    /// Edges of the nearest neighbor tour from node 0 get higher initial intensity
+    NearestNeighbor,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum AcoMode {
+    /// Ants return to the node they started from
+    #[default]
+    Closed,
+    /// Ants do not return, so the closing leg is not part of the tour. Tours start at `start` and
+    /// end at `end` if they are set
+    Open {
+        start: Option<u32>,
+        end: Option<u32>,
+    },
+}
+
+/// Receives iteration, tour and its distance
+pub type ImprovementCallback<'a> = dyn FnMut(u32, &[u32], f64) + 'a;
+
+/// Progress of ACO iterations, mirrors the part of `indicatif::ProgressBar` used by [`Aco`], so
+/// any progress bar may be plugged in without depending on a particular crate
+pub trait AcoProgress {
+    /// Advances position by `delta` finished iterations
+    fn inc(&self, delta: u64);
+    /// Replaces message shown along with position
+    fn set_message(&self, msg: String);
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AcoResult {
+    pub tour: Vec<u32>,
+    pub dist: f64,
+    /// Number of iterations run before returning
+    pub converged_at: u32,
+    /// Best-so-far cycle and its length after each iteration, if frames are captured
+    pub frames: Vec<(Vec<u32>, f64)>,
+}
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Aco<'a> {
@@ -24,6 +86,12 @@ pub struct Aco<'a> {
     intensity: f64,
     q: f64,
     opt_dist: Option<f64>,
+    variant: AcoVariant,
+    init: AcoInit,
+    seed: Option<u64>,
+    candidates: Option<(u32, Vec<Vec<u32>>)>,
+    capture_frames: bool,
+    mode: AcoMode,
 }
 
 impl<'a> Aco<'a> {
@@ -33,7 +101,17 @@ impl<'a> Aco<'a> {
         q: Option<f64>,
         opt_dist: Option<f64>,
     ) -> Self {
-        let size = dist_idx.graph.size;
+        Self::new_with_variant(dist_idx, intensity, q, opt_dist, AcoVariant::default())
+    }
+
+    pub fn new_with_variant(
+        dist_idx: &'a DistancesIdx<'a>,
+        intensity: Option<f64>,
+        q: Option<f64>,
+        opt_dist: Option<f64>,
+        variant: AcoVariant,
+    ) -> Self {
+        let size = dist_idx.graph.size();
 
         let dist_idx = match opt_dist {
             Some(opt_dist) => {
@@ -44,19 +122,13 @@ impl<'a> Aco<'a> {
             None => Cow::Borrowed(dist_idx),
         };
 
-        let mean_dist = dist_idx.graph.triangle_sum() / (size * (size - 1) / 2) as f64;
-
-        let q = match q {
-            Some(q) => q,
-            None if size > 1 => mean_dist,
-            None => 1.0,
+        let mean_dist = if size > 1 {
+            Some(dist_idx.graph.triangle_sum() / (size * (size - 1) / 2) as f64)
+        } else {
+            None
         };
 
-        let intensity = match intensity {
-            Some(intensity) => intensity,
-            None if size > 1 => INIT_INTENSITY_MULTIPLIER * mean_dist,
-            None => 0.0,
-        };
+        let (q, intensity) = q_and_intensity(mean_dist, q, intensity);
 
         Self {
             size,
@@ -64,9 +136,90 @@ impl<'a> Aco<'a> {
             intensity,
             q,
             opt_dist,
+            variant,
+            init: AcoInit::default(),
+            seed: None,
+            candidates: None,
+            capture_frames: false,
+            mode: AcoMode::default(),
         }
     }
 
+    pub fn new_with_init(
+        dist_idx: &'a DistancesIdx<'a>,
+        init: AcoInit,
+        intensity: Option<f64>,
+        q: Option<f64>,
+    ) -> Self {
+        Self {
+            init,
+            ..Self::new(dist_idx, intensity, q, None)
+        }
+    }
+
+    /// Makes ants choose their ways deterministically, so runs with the same `seed` and
+    /// parameters give identical results
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Makes ants choose among the `k` nearest not visited nodes while more than `k` nodes are
+    /// left, all not visited nodes are tried only once the nearest ones are exhausted
+    pub fn with_candidates(mut self, k: u32) -> Self {
+        self.candidates = Some((k, self.dist_idx.graph.k_nearest(k)));
+        self
+    }
+
+    pub fn with_init(mut self, init: AcoInit) -> Self {
+        self.init = init;
+        self
+    }
+
+    /// Makes [`AcoResult::frames`] keep the best-so-far cycle of every iteration
+    pub fn with_capture_frames(mut self) -> Self {
+        self.capture_frames = true;
+        self
+    }
+
+    pub fn with_mode(mut self, mode: AcoMode) -> Self {
+        if let AcoMode::Open { start, end } = mode {
+            assert!(
+                start.into_iter().chain(end).all(|node| node < self.size),
+                "Endpoints {start:?}, {end:?} should be less than {}",
+                self.size
+            );
+            assert!(
+                start.is_none() || start != end,
+                "Open tour should start and end at different nodes"
+            );
+        }
+        self.mode = mode;
+        self
+    }
+
+    /// Consecutive nodes of `tour`, including the closing leg in closed mode only
+    fn legs<'t>(&self, tour: &'t [u32]) -> impl Iterator<Item = (&'t u32, &'t u32)> {
+        let closing = match self.mode {
+            AcoMode::Closed => 0,
+            AcoMode::Open { .. } => 1,
+        };
+        cycling(tour).take(tour.len().saturating_sub(closing))
+    }
+
+    fn tour_distance(&self, tour: &[u32]) -> Option<f64> {
+        match self.mode {
+            AcoMode::Closed => self.dist_idx.tour_distance(tour),
+            AcoMode::Open { .. } => self.dist_idx.path_distance(tour),
+        }
+    }
+
+    /// Returns empty cycle with `NaN` length if no feasible cycle exists.
+    /// Best-so-far cycle additionally deposits pheromone of `elite_ants` ants each iteration.
+    /// Stops early once the best cycle has not improved for `stagnation_limit` iterations.
+    /// Each new best cycle is reported to `on_improvement` with its iteration, or printed if `None`.
+    /// `progress` advances by one after each iteration and shows the best-so-far cycle length
+    #[allow(clippy::too_many_arguments)]
     pub fn aco(
         &self,
         iterations: u32,
@@ -74,26 +227,189 @@ impl<'a> Aco<'a> {
         degradation_factor: f64,
         alpha: f64,
         beta: f64,
-    ) -> (Vec<u32>, f64) {
+        elite_ants: u32,
+        stagnation_limit: Option<u32>,
+        on_improvement: Option<&mut ImprovementCallback<'_>>,
+        progress: Option<&dyn AcoProgress>,
+    ) -> AcoResult {
+        self.aco_from(
+            None,
+            iterations,
+            ants,
+            degradation_factor,
+            alpha,
+            beta,
+            elite_ants,
+            stagnation_limit,
+            on_improvement,
+            progress,
+            |_, _| {},
+        )
+        .0
+    }
+
+    /// Same as [`Self::aco`], also returns pheromones after the last iteration. All of them are
+    /// `None` if no iteration was run.
+    #[allow(clippy::too_many_arguments)]
+    pub fn aco_with_intensities(
+        &self,
+        iterations: u32,
+        ants: u32,
+        degradation_factor: f64,
+        alpha: f64,
+        beta: f64,
+        elite_ants: u32,
+        stagnation_limit: Option<u32>,
+        on_improvement: Option<&mut ImprovementCallback<'_>>,
+        progress: Option<&dyn AcoProgress>,
+    ) -> (AcoResult, GraphIdx<'a, Option<f64>>) {
+        let (result, intensities) = self.aco_from(
+            None,
+            iterations,
+            ants,
+            degradation_factor,
+            alpha,
+            beta,
+            elite_ants,
+            stagnation_limit,
+            on_improvement,
+            progress,
+            |_, _| {},
+        );
+        (result, intensities.into_graph())
+    }
+
+    /// Warm-starts from `initial` cycle: it is the best one before the first iteration, and its
+    /// edges get pheromone `q / tour_distance(initial)`
+    #[allow(clippy::too_many_arguments)]
+    pub fn aco_with_initial_cycle(
+        &self,
+        initial: Vec<u32>,
+        iterations: u32,
+        ants: u32,
+        degradation_factor: f64,
+        alpha: f64,
+        beta: f64,
+        elite_ants: u32,
+        stagnation_limit: Option<u32>,
+        on_improvement: Option<&mut ImprovementCallback<'_>>,
+        progress: Option<&dyn AcoProgress>,
+    ) -> AcoResult {
+        self.aco_from(
+            Some(initial),
+            iterations,
+            ants,
+            degradation_factor,
+            alpha,
+            beta,
+            elite_ants,
+            stagnation_limit,
+            on_improvement,
+            progress,
+            |_, _| {},
+        )
+        .0
+    }
+
+    /// `on_update` observes pheromones after each iteration along with MMAS bounds if any
+    #[allow(clippy::too_many_arguments)]
+    fn aco_from(
+        &self,
+        initial: Option<Vec<u32>>,
+        iterations: u32,
+        ants: u32,
+        degradation_factor: f64,
+        alpha: f64,
+        beta: f64,
+        elite_ants: u32,
+        stagnation_limit: Option<u32>,
+        mut on_improvement: Option<&mut ImprovementCallback<'_>>,
+        progress: Option<&dyn AcoProgress>,
+        mut on_update: impl FnMut(&Intensities<'_>, Option<(f64, f64)>),
+    ) -> (AcoResult, Intensities<'a>) {
+        let trivial = |tour, dist| {
+            (
+                AcoResult {
+                    tour,
+                    dist,
+                    converged_at: 0,
+                    frames: vec![],
+                },
+                Intensities::none(&self.dist_idx.graph),
+            )
+        };
         match self.size {
             0 => {
-                return (vec![], 0.0);
+                return trivial(vec![], 0.0);
             }
-            1 => return (vec![0], 0.0),
+            1 => return trivial(vec![0], 0.0),
             _ => {}
         };
 
-        let mut best_cycle_dist: Option<(Vec<_>, f64)> = None;
-        let mut intensities =
-            GraphIdx::transform(&self.dist_idx.graph, |d| d.map(|_| self.intensity));
-        let mut weights = GraphIdx::transform_const(&self.dist_idx.graph, None);
+        let feasible = match self.mode {
+            AcoMode::Closed => self.dist_idx.feasible_cycle_exists(),
+            AcoMode::Open { .. } => self.dist_idx.graph.is_connected(),
+        };
+        if !feasible {
+            eprintln!("Warning: no feasible cycle exists, consider lowering minimal distance");
+            return trivial(vec![], f64::NAN);
+        }
+
+        let mut tau_max = match self.variant {
+            AcoVariant::Mmas { tau_max_init } => Some(tau_max_init),
+            AcoVariant::AntSystem | AcoVariant::Acs { .. } => None,
+        };
+        let initial_intensity = match self.variant {
+            AcoVariant::AntSystem => self.intensity,
+            AcoVariant::Mmas { tau_max_init } => tau_max_init,
+            AcoVariant::Acs { tau0, .. } => tau0,
+        };
+        let mut intensities = Intensities::new(&self.dist_idx.graph, initial_intensity);
+        if self.init == AcoInit::NearestNeighbor {
+            if let Some((tour, _)) = self.dist_idx.nearest_neighbor_tour(0) {
+                for (&node1, &node2) in self.legs(&tour) {
+                    *intensities.between_mut(node1, node2).unwrap_or_else(|| {
+                        unreachable!("No pheromones between {node1} and {node2}")
+                    }) *= NEAREST_NEIGHBOR_INTENSITY_MULTIPLIER;
+                }
+            }
+        }
+        let mut best_cycle_dist: Option<(Vec<_>, f64)> = initial.map(|initial| {
+            let mut visited = bitvec![0; self.size as usize];
+            assert!(
+                initial.len() == self.size as usize
+                    && initial
+                        .iter()
+                        .all(|&node| node < self.size && !visited.replace(node as usize, true)),
+                "Initial cycle should visit every node once"
+            );
+            let distance = self
+                .tour_distance(&initial)
+                .unwrap_or_else(|| panic!("Initial cycle {initial:?} is infeasible"));
+            for (&node1, &node2) in self.legs(&initial) {
+                *intensities.between_mut(node1, node2).unwrap_or_else(|| {
+                    unreachable!("No pheromones between {node1} and {node2}")
+                }) += self.q / distance;
+            }
+            println!("Initial cycle: {initial:?}, len: {distance:.05}");
+            (initial, distance)
+        });
+        let mut weights = Weights::new(&self.dist_idx.graph);
+        let start = match self.mode {
+            AcoMode::Closed => None,
+            AcoMode::Open { start, .. } => start,
+        };
 
+        let mut attempts = Vec::with_capacity(ants as usize);
         let mut cycles = Vec::with_capacity(ants as usize + 1);
+        let mut converged_at = iterations;
+        let mut frames = vec![];
+        let mut stagnated = 0;
 
         for i in 0..iterations {
-            self.dist_idx
-                .graph
-                .merge_parallel_into(&intensities, &mut weights, |dist, intensity| {
+            let prev_best_distance = best_cycle_dist.as_ref().map(|(_, distance)| *distance);
+            intensities
+                .merge_parallel_into(&self.dist_idx.graph, &mut weights, |dist, intensity| {
                     intensity.zip(dist).map(|(intensity, dist)| {
                         intensity.max(MINIMAL_INTENSITY).powf(alpha) / dist.powf(beta)
                     })
@@ -101,7 +417,8 @@ impl<'a> Aco<'a> {
                 .unwrap_or_else(|| {
                     unreachable!(
                         "Mismatched graph sizes: {} vs {}",
-                        self.dist_idx.graph.size, intensities.size
+                        self.dist_idx.graph.size(),
+                        intensities.size()
                     )
                 });
             (0..ants)
@@ -114,72 +431,278 @@ impl<'a> Aco<'a> {
                             CumulativeWeightsWrapper::with_capacity(self.size as usize),
                         )
                     },
-                    |(rng, not_visited, cumulative_weights_wrapper), _| loop {
-                        if let Some((cycle, dist)) = self.traverse_graph(
-                            None,
-                            &weights,
-                            rng,
-                            not_visited,
-                            cumulative_weights_wrapper,
-                        ) {
-                            if cycle.len() == self.size as usize {
-                                break (cycle, dist);
-                            }
+                    |(rng, not_visited, cumulative_weights_wrapper), ant| {
+                        if let Some(seed) = self.seed {
+                            *rng = Pcg64Mcg::seed_from_u64(ant_seed(seed, i, ant));
                         }
+                        (0..MAX_TRAVERSAL_ATTEMPTS).find_map(|_| {
+                            // leftovers of a failed traversal must not affect the next one
+                            not_visited.fill(true);
+                            self.traverse_graph(
+                                start,
+                                &weights,
+                                rng,
+                                not_visited,
+                                cumulative_weights_wrapper,
+                            )
+                            .filter(|(cycle, _)| cycle.len() == self.size as usize)
+                        })
                     },
                 )
-                .collect_into_vec(&mut cycles);
-            if let Some(best_cycle_dist) = &best_cycle_dist {
-                cycles.push(best_cycle_dist.clone());
+                .collect_into_vec(&mut attempts);
+            cycles.extend(attempts.drain(..).flatten());
+            if cycles.is_empty() && best_cycle_dist.is_none() {
+                eprintln!("Warning: no ant completed a tour, consider lowering minimal distance");
+                return trivial(vec![], f64::NAN);
             }
-            cycles.par_sort_unstable_by(|(_, dist1), (_, dist2)| dist1.total_cmp(dist2));
-            cycles.truncate((cycles.len() + 1) / 2);
+            match self.variant {
+                AcoVariant::Acs { xi, tau0 } => {
+                    // ants of one iteration choose edges by the same weights evaluated before the
+                    // construction, so their local updates are applied after it and do not need
+                    // any synchronization between threads
+                    for (cycle, distance) in cycles.drain(..) {
+                        for (&node1, &node2) in self.legs(&cycle) {
+                            let intensity =
+                                intensities.between_mut(node1, node2).unwrap_or_else(|| {
+                                    unreachable!("No pheromones between {node1} and {node2}")
+                                });
+                            *intensity = (1.0 - xi) * *intensity + xi * tau0;
+                        }
+                        update_best_cycle(
+                            &mut best_cycle_dist,
+                            cycle,
+                            distance,
+                            i,
+                            on_improvement.as_deref_mut(),
+                        );
+                    }
 
-            intensities.transform_inplace(|value| {
-                if let Some(value) = value {
-                    *value *= degradation_factor;
+                    if let Some((best_cycle, best_distance)) = &best_cycle_dist {
+                        let delta = self.q / best_distance;
+                        for (&node1, &node2) in self.legs(best_cycle) {
+                            let intensity =
+                                intensities.between_mut(node1, node2).unwrap_or_else(|| {
+                                    unreachable!("No pheromones between {node1} and {node2}")
+                                });
+                            *intensity = degradation_factor * *intensity
+                                + (1.0 - degradation_factor) * delta;
+                        }
+                    }
                 }
-            });
-
-            for (cycle, distance) in cycles.drain(..) {
-                let delta = self.q / distance;
+                AcoVariant::AntSystem | AcoVariant::Mmas { .. } => {
+                    ant_system_update(
+                        &mut cycles,
+                        &mut best_cycle_dist,
+                        self.q,
+                        i,
+                        on_improvement.as_deref_mut(),
+                        &mut intensities,
+                        |intensities| intensities.evaporate(degradation_factor),
+                        |intensities, cycle, delta| {
+                            for (&node1, &node2) in self.legs(cycle) {
+                                *intensities.between_mut(node1, node2).unwrap_or_else(|| {
+                                    unreachable!("No pheromones between {node1} and {node2}")
+                                }) += delta;
+                            }
+                        },
+                    );
 
-                for (&node1, &node2) in cycling(&cycle) {
-                    if let Some(intencity) =
-                        intensities.between_mut(node1, node2).unwrap_or_else(|| {
-                            unreachable!("No pheromones between {node1} and {node2}")
-                        })
+                    if let (Some((best_cycle, best_distance)), 1..) = (&best_cycle_dist, elite_ants)
                     {
-                        *intencity += delta;
+                        let delta = elite_ants as f64 * self.q / best_distance;
+                        for (&node1, &node2) in self.legs(best_cycle) {
+                            *intensities.between_mut(node1, node2).unwrap_or_else(|| {
+                                unreachable!("No pheromones between {node1} and {node2}")
+                            }) += delta;
+                        }
                     }
                 }
+            }
 
-                match best_cycle_dist {
-                    Some((_, best_distance)) if distance < best_distance => {
-                        println!("New cycle: {cycle:?}, len: {distance:.06}, iteration: [{i}]");
-                        best_cycle_dist = Some((cycle, distance));
-                    }
-                    None => {
-                        println!("First cycle: {cycle:?}, len: {distance:.05}");
-                        best_cycle_dist = Some((cycle, distance));
-                    }
-                    _ => {}
+            let bounds = tau_max.as_mut().map(|tau_max| {
+                if let Some((_, best_distance)) = best_cycle_dist {
+                    *tau_max = self.q / ((1.0 - degradation_factor) * best_distance);
                 }
+                let tau_min = *tau_max / (2 * self.size) as f64;
+                intensities.clamp(tau_min, *tau_max);
+                (tau_min, *tau_max)
+            });
+            on_update(&intensities, bounds);
+            if let Some(progress) = progress {
+                progress.inc(1);
+                if let Some((_, best_distance)) = &best_cycle_dist {
+                    progress.set_message(format!("best={best_distance:.2}"));
+                }
+            }
+            if self.capture_frames {
+                frames.extend(best_cycle_dist.clone());
+            }
+
+            if best_cycle_dist.as_ref().map(|(_, distance)| *distance) == prev_best_distance {
+                stagnated += 1;
+            } else {
+                stagnated = 0;
+            }
+            if stagnation_limit.is_some_and(|stagnation_limit| stagnated >= stagnation_limit) {
+                println!("Stagnated for {stagnated} iterations, stopping at [{i}]");
+                converged_at = i + 1;
+                break;
             }
         }
 
         println!("Best cycle: {best_cycle_dist:?}");
 
-        best_cycle_dist.unwrap_or_else(|| {
+        let (tour, dist) = best_cycle_dist.unwrap_or_else(|| {
             #[allow(unreachable_code)]
             !unreachable!("best_cycle is None")
-        })
+        });
+        (
+            AcoResult {
+                tour,
+                dist,
+                converged_at,
+                frames,
+            },
+            intensities,
+        )
+    }
+
+    /// Improves `tour` by the best of all three edge exchanges each pass until none improves it.
+    /// Infeasible legs count as infinitely long.
+    pub fn three_opt(&self, tour: &[u32]) -> (Vec<u32>, f64) {
+        let dist =
+            |apt1: u32, apt2: u32| self.dist_idx.between(apt1, apt2).unwrap_or(f64::INFINITY);
+        let mut tour = tour.to_vec();
+        let n = tour.len();
+        if n < 4 {
+            let length = self.dist_idx.tour_distance(&tour).unwrap_or(f64::INFINITY);
+            return (tour, length);
+        }
+
+        loop {
+            let mut best: Option<(f64, usize, usize, usize, ThreeOptMove)> = None;
+            for i in 0..n - 2 {
+                for j in i + 1..n - 1 {
+                    for k in j + 1..n {
+                        let (a, b) = (tour[i], tour[i + 1]);
+                        let (c, d) = (tour[j], tour[j + 1]);
+                        let (e, f) = (tour[k], tour[(k + 1) % n]);
+                        let removed = dist(a, b) + dist(c, d) + dist(e, f);
+                        for (added, mv) in [
+                            (
+                                dist(a, c) + dist(b, d) + dist(e, f),
+                                ThreeOptMove::ReverseFirst,
+                            ),
+                            (
+                                dist(a, b) + dist(c, e) + dist(d, f),
+                                ThreeOptMove::ReverseSecond,
+                            ),
+                            (
+                                dist(a, c) + dist(b, e) + dist(d, f),
+                                ThreeOptMove::ReverseBoth,
+                            ),
+                            (dist(a, d) + dist(e, b) + dist(c, f), ThreeOptMove::Swap),
+                            (
+                                dist(a, d) + dist(e, c) + dist(b, f),
+                                ThreeOptMove::SwapReverseFirst,
+                            ),
+                            (
+                                dist(a, e) + dist(d, b) + dist(c, f),
+                                ThreeOptMove::SwapReverseSecond,
+                            ),
+                            (
+                                dist(a, e) + dist(d, c) + dist(b, f),
+                                ThreeOptMove::SwapReverseBoth,
+                            ),
+                        ] {
+                            let gain = removed - added;
+                            if gain > MIN_LOCAL_SEARCH_GAIN
+                                && best.is_none_or(|(best_gain, ..)| gain > best_gain)
+                            {
+                                best = Some((gain, i, j, k, mv));
+                            }
+                        }
+                    }
+                }
+            }
+            let Some((_, i, j, k, mv)) = best else {
+                break;
+            };
+            let (first, second) = (tour[i + 1..=j].to_vec(), tour[j + 1..=k].to_vec());
+            let reversed = |segment: &[u32]| segment.iter().rev().copied().collect::<Vec<_>>();
+            let (new_first, new_second) = match mv {
+                ThreeOptMove::ReverseFirst => (reversed(&first), second),
+                ThreeOptMove::ReverseSecond => (first, reversed(&second)),
+                ThreeOptMove::ReverseBoth => (reversed(&first), reversed(&second)),
+                ThreeOptMove::Swap => (second, first),
+                ThreeOptMove::SwapReverseFirst => (second, reversed(&first)),
+                ThreeOptMove::SwapReverseSecond => (reversed(&second), first),
+                ThreeOptMove::SwapReverseBoth => (reversed(&second), reversed(&first)),
+            };
+            tour.splice(i + 1..=k, new_first.into_iter().chain(new_second));
+        }
+
+        let length = self.dist_idx.tour_distance(&tour).unwrap_or(f64::INFINITY);
+        (tour, length)
+    }
+
+    /// Moves chains of `chain_len` consecutive nodes, possibly reversed, to other positions in
+    /// `tour`, applying each improvement at once until a full pass finds none.
+    /// Infeasible legs count as infinitely long.
+    pub fn or_opt(&self, tour: &[u32], chain_len: u32) -> (Vec<u32>, f64) {
+        assert!(chain_len > 0, "Chain should not be empty");
+        let dist =
+            |apt1: u32, apt2: u32| self.dist_idx.between(apt1, apt2).unwrap_or(f64::INFINITY);
+        let mut tour = tour.to_vec();
+        let n = tour.len();
+        let chain_len = chain_len as usize;
+
+        // the rest of the tour should keep at least two gaps besides the one the chain leaves
+        if n < chain_len + 3 {
+            let length = self.dist_idx.tour_distance(&tour).unwrap_or(f64::INFINITY);
+            return (tour, length);
+        }
+
+        'pass: loop {
+            for i in 0..n {
+                let chain: Vec<_> = (i..i + chain_len).map(|k| tour[k % n]).collect();
+                let rest: Vec<_> = (i + chain_len..i + n).map(|k| tour[k % n]).collect();
+                let (first, last) = (chain[0], chain[chain_len - 1]);
+                let (prev, next) = (rest[rest.len() - 1], rest[0]);
+                for j in 0..rest.len() - 1 {
+                    let (a, b) = (rest[j], rest[j + 1]);
+                    let removed = dist(prev, first) + dist(last, next) + dist(a, b);
+                    let forward = dist(prev, next) + dist(a, first) + dist(last, b);
+                    let backward = dist(prev, next) + dist(a, last) + dist(first, b);
+                    let reverse = if removed - forward > MIN_LOCAL_SEARCH_GAIN {
+                        false
+                    } else if removed - backward > MIN_LOCAL_SEARCH_GAIN {
+                        true
+                    } else {
+                        continue;
+                    };
+                    tour.clear();
+                    tour.extend_from_slice(&rest[..=j]);
+                    if reverse {
+                        tour.extend(chain.iter().rev());
+                    } else {
+                        tour.extend_from_slice(&chain);
+                    }
+                    tour.extend_from_slice(&rest[j + 1..]);
+                    continue 'pass;
+                }
+            }
+            break;
+        }
+
+        let length = self.dist_idx.tour_distance(&tour).unwrap_or(f64::INFINITY);
+        (tour, length)
     }
 
     fn traverse_graph(
         &self,
         source_node: Option<u32>,
-        weights: &GraphIdx<Option<f64>>,
+        weights: &Weights,
         rng: &mut impl Rng,
         not_visited: &mut BitVec,
         cumulative_weights_wrapper: &mut CumulativeWeightsWrapper<f64>,
@@ -193,6 +716,14 @@ impl<'a> Aco<'a> {
         let source_node = source_node.unwrap_or_else(|| rng.gen_range(0..self.size));
 
         not_visited.set(source_node as usize, false);
+        // the end is hidden from choice until every other node is visited
+        let mut pending_end = match self.mode {
+            AcoMode::Closed => None,
+            AcoMode::Open { end, .. } => end.filter(|&end| end != source_node),
+        };
+        if let Some(end) = pending_end {
+            not_visited.set(end as usize, false);
+        }
 
         let mut cycle = Vec::with_capacity(self.size as usize);
         cycle.push(source_node);
@@ -202,35 +733,55 @@ impl<'a> Aco<'a> {
 
         loop {
             let chosen = match not_visited.count_ones() {
-                0 => {
-                    not_visited.fill(true);
-                    break self
-                        .dist_idx
-                        .between(current, source_node)
-                        .map(|dist| (cycle, total_dist.push_and_result(dist)));
-                }
+                0 => match pending_end.take() {
+                    Some(end) => end as usize,
+                    None => {
+                        not_visited.fill(true);
+                        break match self.mode {
+                            AcoMode::Closed => self
+                                .dist_idx
+                                .between(current, source_node)
+                                .map(|dist| (cycle, total_dist.push_and_result(dist))),
+                            AcoMode::Open { .. } => Some((cycle, total_dist.result())),
+                        };
+                    }
+                },
                 1 => not_visited
                     .first_one()
                     .unwrap_or_else(|| unreachable!("not_visited should contain one element")),
-                _ => {
-                    let wi = cumulative_weights_wrapper
-                        .fill(not_visited.iter_ones().map(|i| {
-                            let i = i as u32;
-                            // todo: do not account in weight map unacceptable distances
-                            // todo: as it leads to useless idle cycles
-                            weights
-                                .between(None, current, i)
-                                .unwrap_or_else(|| {
-                                    unreachable!("No weights between {current} and {i}")
-                                })
-                                .unwrap_or(0.0)
-                        }))
-                        .ok()?;
-                    let chosen = wi.sample(rng);
-                    not_visited
-                        .iter_ones()
-                        .nth(chosen)
-                        .unwrap_or_else(|| unreachable!("No node in {chosen} position"))
+                left => {
+                    let weight = |i: u32| {
+                        // todo: do not account in weight map unacceptable distances
+                        // todo: as it leads to useless idle cycles
+                        weights.between(current, i).unwrap_or(0.0)
+                    };
+                    let near = match &self.candidates {
+                        Some((k, candidates)) if left > *k as usize => {
+                            Some(&candidates[current as usize])
+                        }
+                        _ => None,
+                    };
+                    let chosen_near = near.and_then(|near| {
+                        let near_not_visited =
+                            || near.iter().copied().filter(|&i| not_visited[i as usize]);
+                        let wi = cumulative_weights_wrapper
+                            .fill(near_not_visited().map(weight))
+                            .ok()?;
+                        near_not_visited().nth(wi.sample(rng))
+                    });
+                    match chosen_near {
+                        Some(chosen) => chosen as usize,
+                        None => {
+                            let wi = cumulative_weights_wrapper
+                                .fill(not_visited.iter_ones().map(|i| weight(i as u32)))
+                                .ok()?;
+                            let chosen = wi.sample(rng);
+                            not_visited
+                                .iter_ones()
+                                .nth(chosen)
+                                .unwrap_or_else(|| unreachable!("No node in {chosen} position"))
+                        }
+                    }
                 }
             };
             not_visited.set(chosen, false);
@@ -242,6 +793,100 @@ impl<'a> Aco<'a> {
     }
 }
 
+fn ant_seed(seed: u64, iteration: u32, ant: u32) -> u64 {
+    seed ^ (iteration as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15) ^ ant as u64
+}
+
+/// `q` and initial intensity defaulting to the mean distance and its multiple
+pub(crate) fn q_and_intensity(
+    mean_dist: Option<f64>,
+    q: Option<f64>,
+    intensity: Option<f64>,
+) -> (f64, f64) {
+    let q = match (q, mean_dist) {
+        (Some(q), _) => q,
+        (None, Some(mean_dist)) => mean_dist,
+        (None, None) => 1.0,
+    };
+
+    let intensity = match (intensity, mean_dist) {
+        (Some(intensity), _) => intensity,
+        (None, Some(mean_dist)) => INIT_INTENSITY_MULTIPLIER * mean_dist,
+        (None, None) => 0.0,
+    };
+
+    (q, intensity)
+}
+
+/// Ant System step: evaporates `pheromones`, then the better half of `cycles` along with the best
+/// cycle so far deposit `q / distance` each. Drains `cycles`
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn ant_system_update<P>(
+    cycles: &mut Vec<(Vec<u32>, f64)>,
+    best_cycle_dist: &mut Option<(Vec<u32>, f64)>,
+    q: f64,
+    iteration: u32,
+    mut on_improvement: Option<&mut ImprovementCallback<'_>>,
+    pheromones: &mut P,
+    evaporate: impl FnOnce(&mut P),
+    mut deposit: impl FnMut(&mut P, &[u32], f64),
+) {
+    if let Some(best_cycle_dist) = best_cycle_dist {
+        cycles.push(best_cycle_dist.clone());
+    }
+    cycles.par_sort_unstable_by(|(_, dist1), (_, dist2)| dist1.total_cmp(dist2));
+    cycles.truncate(cycles.len().div_ceil(2));
+
+    evaporate(pheromones);
+
+    for (cycle, distance) in cycles.drain(..) {
+        deposit(pheromones, &cycle, q / distance);
+        update_best_cycle(
+            best_cycle_dist,
+            cycle,
+            distance,
+            iteration,
+            on_improvement.as_deref_mut(),
+        );
+    }
+}
+
+fn update_best_cycle(
+    best_cycle_dist: &mut Option<(Vec<u32>, f64)>,
+    cycle: Vec<u32>,
+    distance: f64,
+    iteration: u32,
+    on_improvement: Option<&mut ImprovementCallback<'_>>,
+) {
+    match (best_cycle_dist.as_ref(), on_improvement) {
+        (Some((_, best_distance)), _) if distance >= *best_distance => return,
+        (_, Some(on_improvement)) => on_improvement(iteration, &cycle, distance),
+        (Some(_), None) => {
+            println!("New cycle: {cycle:?}, len: {distance:.06}, iteration: [{iteration}]")
+        }
+        (None, None) => println!("First cycle: {cycle:?}, len: {distance:.05}"),
+    }
+    *best_cycle_dist = Some((cycle, distance));
+}
+
+impl Default for Aco<'static> {
+    fn default() -> Self {
+        Self {
+            size: 0,
+            dist_idx: Cow::Owned(DistancesIdx::default()),
+            intensity: 0.0,
+            q: 1.0,
+            opt_dist: None,
+            variant: AcoVariant::default(),
+            init: AcoInit::default(),
+            seed: None,
+            candidates: None,
+            capture_frames: false,
+            mode: AcoMode::default(),
+        }
+    }
+}
+
 fn eval_a(opt_dist: f64) -> f64 {
     (3.0 + lambert_w0(-3.0 / f64::consts::E.powi(3))) / opt_dist
 }
@@ -258,9 +903,754 @@ fn plank_law(x: f64, a: f64, recip_law_ext: f64) -> f64 {
     }
 }
 
+/// Reconnection of the two segments between three removed edges
+#[derive(Copy, Clone, Debug)]
+enum ThreeOptMove {
+    ReverseFirst,
+    ReverseSecond,
+    ReverseBoth,
+    Swap,
+    SwapReverseFirst,
+    SwapReverseSecond,
+    SwapReverseBoth,
+}
+
+/// Pheromones on feasible edges, kept sparse for graphs with few of them
+#[derive(Clone, Debug)]
+enum Intensities<'a> {
+    Dense(GraphIdx<'a, Option<f64>>),
+    Sparse(SparseGraphIdx<'a, f64>),
+}
+
+impl<'a> Intensities<'a> {
+    /// Sparse distances keep intensities sparse, dense ones only if there are few feasible legs
+    fn new(dist_graph: &DistGraph<'a>, intensity: f64) -> Self {
+        match dist_graph {
+            DistGraph::Dense(dist_graph) => {
+                let intensities = dist_graph.transform(|d| d.map(|_| intensity));
+                if dist_graph.density() < SPARSE_DENSITY {
+                    Self::Sparse(SparseGraphIdx::from(&intensities))
+                } else {
+                    Self::Dense(intensities)
+                }
+            }
+            DistGraph::Sparse(dist_graph) => Self::Sparse(dist_graph.transform(|_| intensity)),
+        }
+    }
+
+    /// No pheromones on any edge, in the representation of `dist_graph`
+    fn none(dist_graph: &DistGraph<'a>) -> Self {
+        match dist_graph {
+            DistGraph::Dense(dist_graph) => Self::Dense(dist_graph.transform_const(None)),
+            DistGraph::Sparse(dist_graph) => Self::Sparse(SparseGraphIdx {
+                size: dist_graph.size,
+                adjacency: vec![vec![]; dist_graph.size as usize],
+                _pd: PhantomData,
+            }),
+        }
+    }
+
+    fn size(&self) -> u32 {
+        match self {
+            Self::Dense(graph) => graph.size,
+            Self::Sparse(graph) => graph.size,
+        }
+    }
+
+    fn into_graph(self) -> GraphIdx<'a, Option<f64>> {
+        match self {
+            Self::Dense(graph) => graph,
+            Self::Sparse(graph) => GraphIdx::from(&graph),
+        }
+    }
+
+    fn merge_parallel_into(
+        &self,
+        dist_graph: &DistGraph<'a>,
+        weights: &mut Weights<'a>,
+        f: impl (Fn(Option<f64>, Option<f64>) -> Option<f64>) + Sync,
+    ) -> Option<()> {
+        match (self, dist_graph, weights) {
+            (Self::Dense(graph), DistGraph::Dense(dist_graph), Weights::Dense(weights)) => {
+                dist_graph.merge_parallel_into(graph, weights, f)
+            }
+            (Self::Sparse(graph), DistGraph::Dense(dist_graph), Weights::Dense(weights)) => {
+                graph.merge_parallel_into(dist_graph, weights, |intensity, dist| f(dist, intensity))
+            }
+            (Self::Sparse(graph), DistGraph::Sparse(dist_graph), Weights::Sparse(weights)) => graph
+                .merge_parallel_into_sparse(dist_graph, weights, |intensity, dist| {
+                    f(dist, intensity)
+                }),
+            _ => None,
+        }
+    }
+
+    fn evaporate(&mut self, degradation_factor: f64) {
+        match self {
+            Self::Dense(graph) => graph.transform_inplace_par(|value| {
+                if let Some(value) = value {
+                    *value *= degradation_factor;
+                }
+            }),
+            Self::Sparse(graph) => {
+                graph.transform_inplace_par(|value| *value *= degradation_factor)
+            }
+        }
+    }
+
+    fn clamp(&mut self, min: f64, max: f64) {
+        match self {
+            Self::Dense(graph) => graph.transform_inplace_par(|value| {
+                if let Some(value) = value {
+                    *value = value.clamp(min, max);
+                }
+            }),
+            Self::Sparse(graph) => {
+                graph.transform_inplace_par(|value| *value = value.clamp(min, max))
+            }
+        }
+    }
+
+    fn between_mut(&mut self, apt1: u32, apt2: u32) -> Option<&mut f64> {
+        match self {
+            Self::Dense(graph) => graph.between_mut(apt1, apt2).and_then(Option::as_mut),
+            Self::Sparse(graph) => graph.between_mut(apt1, apt2),
+        }
+    }
+}
+
+/// Edge choice weights of ants, kept in the representation of distances
+#[derive(Clone, Debug)]
+enum Weights<'a> {
+    Dense(GraphIdx<'a, Option<f64>>),
+    Sparse(SparseGraphIdx<'a, f64>),
+}
+
+impl<'a> Weights<'a> {
+    fn new(dist_graph: &DistGraph<'a>) -> Self {
+        match dist_graph {
+            DistGraph::Dense(dist_graph) => Self::Dense(dist_graph.transform_const(None)),
+            DistGraph::Sparse(dist_graph) => Self::Sparse(dist_graph.transform(|dist| dist)),
+        }
+    }
+
+    fn between(&self, apt1: u32, apt2: u32) -> Option<f64> {
+        match self {
+            Self::Dense(graph) => graph.between(None, apt1, apt2).flatten(),
+            Self::Sparse(graph) => graph.between(apt1, apt2),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::{points, random_points};
+    use std::cell::{Cell, RefCell};
+    use std::marker::PhantomData;
+
+    #[test]
+    fn test_default() {
+        let aco = Aco::default();
+        let AcoResult {
+            tour,
+            dist,
+            converged_at,
+            ..
+        } = aco.aco(10, 10, 0.9, 1.0, 1.0, 0, None, None, None);
+        assert_eq!((tour, dist, converged_at), (vec![], 0.0, 0));
+    }
+
+    #[test]
+    fn test_trivial_sizes() {
+        let empty = DistancesIdx::default();
+        let aco = Aco::new(&empty, None, None, None);
+        assert_eq!(aco.size, 0);
+        assert_eq!(aco.q, 1.0);
+        assert_eq!(aco.intensity, 0.0);
+        let AcoResult {
+            tour,
+            dist,
+            converged_at,
+            ..
+        } = aco.aco(10, 10, 0.9, 1.0, 1.0, 0, None, None, None);
+        assert_eq!((tour, dist, converged_at), (vec![], 0.0, 0));
+
+        let single = DistancesIdx {
+            graph: DistGraph::Dense(GraphIdx {
+                size: 1,
+                ..GraphIdx::default()
+            }),
+        };
+        let aco = Aco::new(&single, None, None, None);
+        assert_eq!(aco.q, 1.0);
+        assert_eq!(aco.intensity, 0.0);
+        let AcoResult {
+            tour,
+            dist,
+            converged_at,
+            ..
+        } = aco.aco(10, 10, 0.9, 1.0, 1.0, 0, None, None, None);
+        assert_eq!((tour, dist, converged_at), (vec![0], 0.0, 0));
+    }
+
+    #[test]
+    fn test_infeasible() {
+        let infeasible = DistancesIdx {
+            graph: DistGraph::Dense(GraphIdx {
+                size: 3,
+                edges: vec![Some(1.0), None, Some(1.0)],
+                _pd: PhantomData,
+            }),
+        };
+        let AcoResult {
+            tour: cycle, dist, ..
+        } = Aco::new(&infeasible, None, None, None).aco(10, 10, 0.9, 1.0, 1.0, 0, None, None, None);
+        assert!(cycle.is_empty());
+        assert!(dist.is_nan());
+    }
+
+    #[test]
+    fn test_sparse_intensities() {
+        // ring of six nodes, the only feasible cycle
+        let sparse = DistancesIdx::from_custom_fn(6, |apt1, apt2| {
+            Some(1.0).filter(|_| apt1 - apt2 == 1 || (apt1, apt2) == (5, 0))
+        });
+        let dense = DistancesIdx::from_custom_fn(4, |apt1, apt2| {
+            Some(1.0).filter(|_| (apt1, apt2) != (2, 0))
+        });
+        assert!(matches!(
+            Intensities::new(&sparse.graph, 1.0),
+            Intensities::Sparse(_)
+        ));
+        assert!(matches!(
+            Intensities::new(&dense.graph, 1.0),
+            Intensities::Dense(_)
+        ));
+
+        let AcoResult {
+            tour: cycle, dist, ..
+        } = Aco::new(&sparse, None, None, None).aco(5, 5, 0.9, 1.0, 1.0, 0, None, None, None);
+        assert_eq!(cycle.len(), 6);
+        assert_eq!(dist, 6.0);
+    }
+
+    #[test]
+    fn test_sparse_distances() {
+        let dense = random_points(12, 7);
+        let sparse = DistancesIdx::from_dist_graph(DistGraph::Sparse(SparseGraphIdx::from(
+            dense.graph.to_dense().as_ref(),
+        )));
+        assert!(matches!(
+            Intensities::new(&sparse.graph, 1.0),
+            Intensities::Sparse(_)
+        ));
+        let solve = |distances| {
+            let AcoResult { tour, dist, .. } = Aco::new(distances, None, None, None)
+                .with_seed(3)
+                .aco(20, 10, 0.9, 1.0, 2.0, 0, None, None, None);
+            (tour, dist)
+        };
+        assert_eq!(solve(&sparse), solve(&dense));
+    }
+
+    #[test]
+    fn test_aco_with_initial_cycle() {
+        let distances = points(&[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]);
+        let aco = Aco::new(&distances, None, None, None);
+        let result =
+            aco.aco_with_initial_cycle(vec![3, 2, 1, 0], 0, 4, 0.9, 1.0, 1.0, 0, None, None, None);
+        assert_eq!((result.tour, result.dist), (vec![3, 2, 1, 0], 4.0));
+
+        let result =
+            aco.aco_with_initial_cycle(vec![0, 2, 1, 3], 10, 4, 0.9, 1.0, 1.0, 0, None, None, None);
+        assert_eq!(result.tour.len(), 4);
+        assert_eq!(result.dist, 4.0);
+    }
+
+    #[test]
+    fn test_initial_cycle_intensities() {
+        let distances = random_points(10, 42);
+        let initial: Vec<_> = (0..distances.graph.size()).collect();
+        let aco = Aco::new(&distances, None, None, None).with_seed(42);
+        let mut checked = false;
+        aco.aco_from(
+            Some(initial.clone()),
+            1,
+            2,
+            0.9,
+            1.0,
+            2.0,
+            0,
+            None,
+            None,
+            None,
+            |intensities, _| {
+                let Intensities::Dense(graph) = intensities else {
+                    panic!("Complete graph should have dense intensities");
+                };
+                let values = intensity_values(intensities);
+                let mean = values.iter().sum::<f64>() / values.len() as f64;
+                for (&node1, &node2) in cycling(&initial) {
+                    let intensity = graph.between(None, node1, node2).flatten().unwrap();
+                    assert!(intensity > mean, "{node1}-{node2}: {intensity} <= {mean}");
+                }
+                checked = true;
+            },
+        );
+        assert!(checked);
+    }
+
+    #[test]
+    #[should_panic(expected = "Initial cycle should visit every node once")]
+    fn test_aco_with_invalid_initial_cycle() {
+        let distances = DistancesIdx::from_custom_fn(3, |_, _| Some(1.0));
+        Aco::new(&distances, None, None, None).aco_with_initial_cycle(
+            vec![0, 1, 1],
+            1,
+            1,
+            0.9,
+            1.0,
+            1.0,
+            0,
+            None,
+            None,
+            None,
+        );
+    }
+
+    fn intensity_values(intensities: &Intensities) -> Vec<f64> {
+        match intensities {
+            Intensities::Dense(graph) => graph.edges.iter().flatten().copied().collect(),
+            Intensities::Sparse(graph) => graph
+                .adjacency
+                .iter()
+                .flatten()
+                .map(|&(_, value)| value)
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_mmas_bounds() {
+        let distances = random_points(10, 42);
+        let aco = Aco::new_with_variant(
+            &distances,
+            None,
+            None,
+            None,
+            AcoVariant::Mmas { tau_max_init: 1e3 },
+        );
+        let mut rounds = 0;
+        let (result, _) = aco.aco_from(
+            None,
+            200,
+            10,
+            0.9,
+            1.0,
+            2.0,
+            0,
+            None,
+            None,
+            None,
+            |intensities, bounds| {
+                let (tau_min, tau_max) = bounds.unwrap();
+                assert_eq!(tau_min, tau_max / 20.0);
+                assert!(intensity_values(intensities)
+                    .into_iter()
+                    .all(|value| (tau_min..=tau_max).contains(&value)));
+                rounds += 1;
+            },
+        );
+        assert_eq!(rounds, 200);
+        assert_eq!(result.tour.len(), 10);
+    }
+
+    #[test]
+    fn test_acs_converges_faster() {
+        let distances = random_points(20, 42);
+        let initial: Vec<_> = (0..distances.graph.size()).collect();
+        let tau0 =
+            1.0 / (distances.graph.size() as f64 * distances.tour_distance(&initial).unwrap());
+        let acs = Aco::new_with_variant(
+            &distances,
+            None,
+            Some(1.0),
+            None,
+            AcoVariant::Acs { xi: 0.1, tau0 },
+        );
+        let ant_system = Aco::new(&distances, None, None, None);
+
+        // total over several short seeded runs to smooth out randomness of the ants
+        let total = |aco: &Aco| -> f64 {
+            (0..10)
+                .map(|seed| {
+                    aco.clone()
+                        .with_seed(seed)
+                        .aco(10, 10, 0.9, 1.0, 2.0, 0, None, None, None)
+                        .dist
+                })
+                .sum()
+        };
+        assert!(total(&acs) < total(&ant_system));
+    }
+
+    #[test]
+    fn test_elite_ants() {
+        let distances = random_points(20, 42);
+        let aco = Aco::new(&distances, None, None, None).with_seed(42);
+
+        // few ants keep the regular deposits weak, so the elitist ones stand out
+        let dist = |elite_ants| {
+            aco.aco(50, 2, 0.9, 1.0, 2.0, elite_ants, None, None, None)
+                .dist
+        };
+        assert!(dist(5) < dist(0));
+    }
+
+    #[test]
+    fn test_stagnation_limit() {
+        let distances = DistancesIdx::from_custom_fn(3, |_, _| Some(1.0));
+        let aco = Aco::new(&distances, None, None, None);
+        let result = aco.aco(100, 5, 0.9, 1.0, 1.0, 0, Some(5), None, None);
+        assert_eq!(result.tour.len(), 3);
+        assert_eq!(result.dist, 3.0);
+        assert_eq!(result.converged_at, 6);
+
+        assert_eq!(
+            aco.aco(20, 5, 0.9, 1.0, 1.0, 0, None, None, None)
+                .converged_at,
+            20
+        );
+    }
+
+    #[test]
+    fn test_on_improvement() {
+        let distances = random_points(15, 42);
+        let aco = Aco::new(&distances, None, None, None);
+        let mut reported = vec![];
+        let result = aco.aco(
+            20,
+            10,
+            0.9,
+            1.0,
+            2.0,
+            0,
+            None,
+            Some(&mut |_, tour: &[u32], dist| {
+                assert!((distances.tour_distance(tour).unwrap() - dist).abs() < 1e-12);
+                reported.push(dist);
+            }),
+            None,
+        );
+        assert!(!reported.is_empty());
+        assert!(reported.windows(2).all(|pair| pair[0] > pair[1]));
+        assert_eq!(reported.last(), Some(&result.dist));
+    }
+
+    #[test]
+    fn test_progress() {
+        #[derive(Default)]
+        struct RecordingProgress {
+            position: Cell<u64>,
+            messages: RefCell<Vec<String>>,
+        }
+
+        impl AcoProgress for RecordingProgress {
+            fn inc(&self, delta: u64) {
+                self.position.set(self.position.get() + delta);
+            }
+
+            fn set_message(&self, msg: String) {
+                self.messages.borrow_mut().push(msg);
+            }
+        }
+
+        let distances = DistancesIdx::from_custom_fn(5, |apt1, apt2| Some((apt1 + apt2) as f64));
+        let aco = Aco::new(&distances, None, None, None);
+        let progress = RecordingProgress::default();
+        let result = aco.aco(7, 5, 0.9, 1.0, 1.0, 0, None, None, Some(&progress));
+        assert_eq!(progress.position.get(), 7);
+        let messages = progress.messages.borrow();
+        assert_eq!(messages.len(), 7);
+        assert_eq!(messages.last(), Some(&format!("best={:.2}", result.dist)));
+
+        let progress = RecordingProgress::default();
+        let result = aco.aco(100, 5, 0.9, 1.0, 1.0, 0, Some(3), None, Some(&progress));
+        assert_eq!(progress.position.get(), result.converged_at as u64);
+    }
+
+    #[test]
+    fn test_seeded() {
+        let distances = random_points(15, 42);
+        let run = |seed| {
+            Aco::new(&distances, None, None, None)
+                .with_seed(seed)
+                .aco(20, 10, 0.9, 1.0, 2.0, 0, None, None, None)
+        };
+        let result = run(7);
+        assert_eq!(result.tour, run(7).tour);
+        assert_eq!(result.dist.to_bits(), run(7).dist.to_bits());
+        assert_ne!(ant_seed(7, 0, 1), ant_seed(7, 1, 0));
+    }
+
+    #[test]
+    fn test_nearest_neighbor_init() {
+        let distances = random_points(50, 42);
+        let threshold = 1.2 * distances.nearest_neighbor_tour(0).unwrap().1;
+        let iterations = 50;
+
+        // total over seeded runs of iterations needed to reach the threshold
+        let reached = |init| -> u32 {
+            (0..5)
+                .map(|seed| {
+                    let mut reached = iterations;
+                    Aco::new_with_init(&distances, init, None, None)
+                        .with_seed(seed)
+                        .aco(
+                            iterations,
+                            10,
+                            0.9,
+                            1.0,
+                            2.0,
+                            0,
+                            None,
+                            Some(&mut |i, _: &[u32], dist| {
+                                if dist <= threshold {
+                                    reached = reached.min(i);
+                                }
+                            }),
+                            None,
+                        );
+                    reached
+                })
+                .sum()
+        };
+        assert!(5 * reached(AcoInit::NearestNeighbor) <= 4 * reached(AcoInit::Uniform));
+    }
+
+    #[test]
+    fn test_candidates() {
+        let distances = random_points(30, 42);
+        let aco = Aco::new(&distances, None, None, None).with_candidates(5);
+        let result = aco.aco(10, 10, 0.9, 1.0, 2.0, 0, None, None, None);
+        let mut sorted = result.tour.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..30).collect::<Vec<_>>());
+        assert!((distances.tour_distance(&result.tour).unwrap() - result.dist).abs() < 1e-12);
+
+        // with no candidates ants fall back to all not visited nodes at once
+        let result = Aco::new(&distances, None, None, None)
+            .with_candidates(0)
+            .aco(2, 5, 0.9, 1.0, 2.0, 0, None, None, None);
+        assert_eq!(result.tour.len(), 30);
+    }
+
+    #[test]
+    fn test_aco_with_intensities() {
+        let distances = random_points(10, 42);
+        let aco = Aco::new(&distances, None, None, None).with_seed(42);
+        let (result, intensities) =
+            aco.aco_with_intensities(20, 10, 0.9, 1.0, 2.0, 1, None, None, None);
+        assert_eq!(
+            result,
+            aco.aco(20, 10, 0.9, 1.0, 2.0, 1, None, None, None),
+            "seeded runs should match"
+        );
+        assert_eq!(intensities.size, 10);
+        let values: Vec<_> = intensities
+            .iter_edges()
+            .map(|(_, _, value)| value.unwrap())
+            .collect();
+        assert!(values.iter().all(|&value| value > 0.0));
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        // the best cycle is reinforced by the elitist ant
+        let tour_mean = cycling(&result.tour)
+            .map(|(&apt1, &apt2)| intensities.between(None, apt1, apt2).flatten().unwrap())
+            .sum::<f64>()
+            / result.tour.len() as f64;
+        assert!(tour_mean > mean);
+
+        let single = DistancesIdx::from_custom_fn(1, |_, _| None);
+        let (result, intensities) = Aco::new(&single, None, None, None)
+            .aco_with_intensities(20, 10, 0.9, 1.0, 2.0, 1, None, None, None);
+        assert_eq!(result.tour, vec![0]);
+        assert_eq!(intensities.iter_edges().count(), 0);
+    }
+
+    #[test]
+    fn test_open_mode() {
+        // three nodes on a line, the closed cycle is twice as long as the path
+        let line = DistancesIdx::from_custom_fn(3, |apt1, apt2| Some(apt1.abs_diff(apt2) as f64));
+        let closed = Aco::new(&line, None, None, None)
+            .with_seed(42)
+            .aco(10, 5, 0.9, 1.0, 1.0, 0, None, None, None);
+        assert_eq!(closed.dist, 4.0);
+
+        let open = Aco::new(&line, None, None, None)
+            .with_seed(42)
+            .with_mode(AcoMode::Open {
+                start: None,
+                end: None,
+            })
+            .aco(10, 5, 0.9, 1.0, 1.0, 0, None, None, None);
+        assert_eq!(open.dist, 2.0);
+        assert!(open.tour == [0, 1, 2] || open.tour == [2, 1, 0]);
+
+        let pinned = Aco::new(&line, None, None, None)
+            .with_mode(AcoMode::Open {
+                start: Some(1),
+                end: Some(0),
+            })
+            .aco(10, 5, 0.9, 1.0, 1.0, 0, None, None, None);
+        assert_eq!(pinned.tour, vec![1, 2, 0]);
+        assert_eq!(pinned.dist, 3.0);
+
+        // a path exists although there is no cycle
+        let chain = DistancesIdx::from_custom_fn(4, |apt1, apt2| {
+            Some(1.0).filter(|_| apt1.abs_diff(apt2) == 1)
+        });
+        let open = Aco::new(&chain, None, None, None)
+            .with_mode(AcoMode::Open {
+                start: Some(0),
+                end: None,
+            })
+            .aco(5, 5, 0.9, 1.0, 1.0, 0, None, None, None);
+        assert_eq!((open.tour, open.dist), (vec![0, 1, 2, 3], 3.0));
+    }
+
+    #[test]
+    fn test_open_mode_no_hamiltonian_path() {
+        // connected, but no path visits all three leaves of the star
+        let star = DistancesIdx::from_custom_fn(4, |_, apt2| Some(1.0).filter(|_| apt2 == 0));
+        for (start, end) in [(None, None), (Some(1), None), (Some(1), Some(2))] {
+            let open = Aco::new(&star, None, None, None)
+                .with_seed(42)
+                .with_mode(AcoMode::Open { start, end })
+                .aco(5, 2, 0.9, 1.0, 1.0, 0, None, None, None);
+            assert!(open.tour.is_empty());
+            assert!(open.dist.is_nan());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Open tour should start and end at different nodes")]
+    fn test_open_mode_same_endpoints() {
+        let distances = DistancesIdx::from_custom_fn(3, |_, _| Some(1.0));
+        let _ = Aco::new(&distances, None, None, None).with_mode(AcoMode::Open {
+            start: Some(1),
+            end: Some(1),
+        });
+    }
+
+    #[test]
+    fn test_capture_frames() {
+        let distances = random_points(10, 42);
+        let aco = Aco::new(&distances, None, None, None).with_seed(42);
+        assert!(aco
+            .aco(5, 5, 0.9, 1.0, 2.0, 0, None, None, None)
+            .frames
+            .is_empty());
+
+        let result = aco
+            .with_capture_frames()
+            .aco(20, 5, 0.9, 1.0, 2.0, 0, None, None, None);
+        assert_eq!(result.frames.len(), 20);
+        assert!(result
+            .frames
+            .windows(2)
+            .all(|frames| frames[1].1 <= frames[0].1));
+        assert_eq!(
+            result.frames.last(),
+            Some(&(result.tour.clone(), result.dist))
+        );
+    }
+
+    #[test]
+    fn test_three_opt() {
+        let distances = random_points(15, 42);
+        let aco = Aco::new(&distances, None, None, None);
+        let initial: Vec<_> = (0..distances.graph.size()).collect();
+        let (tour, length) = aco.three_opt(&initial);
+
+        let mut sorted = tour.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, initial);
+        assert!(length < distances.tour_distance(&initial).unwrap());
+        assert!((distances.tour_distance(&tour).unwrap() - length).abs() < 1e-12);
+        assert_eq!(aco.three_opt(&tour).0, tour);
+    }
+
+    #[test]
+    fn test_or_opt() {
+        // vertices of a regular decagon, optimal tour goes around it
+        let distances = DistancesIdx::from_custom_fn(10, |apt1, apt2| {
+            let angle = |apt: u32| apt as f64 * f64::consts::TAU / 10.0;
+            let (angle1, angle2) = (angle(apt1), angle(apt2));
+            Some(f64::hypot(
+                angle1.cos() - angle2.cos(),
+                angle1.sin() - angle2.sin(),
+            ))
+        });
+        let optimum = distances
+            .tour_distance(&(0..10).collect::<Vec<_>>())
+            .unwrap();
+        let aco = Aco::new(&distances, None, None, None);
+
+        let (tour, length) = aco.or_opt(&[0, 1, 5, 2, 3, 4, 6, 7, 8, 9], 1);
+        assert!((length - optimum).abs() < 1e-9);
+        assert!((distances.tour_distance(&tour).unwrap() - length).abs() < 1e-12);
+
+        let (tour, length) = aco.or_opt(&[0, 1, 4, 5, 2, 3, 6, 7, 8, 9], 2);
+        assert!((length - optimum).abs() < 1e-9);
+        let mut sorted = tour.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..10).collect::<Vec<_>>());
+
+        // moving the chain of three leaves a reversed segment, fixed by shorter chains
+        let (tour, _) = aco.or_opt(&[0, 1, 5, 6, 7, 2, 3, 4, 8, 9], 3);
+        let (tour, _) = aco.or_opt(&tour, 2);
+        let (_, length) = aco.or_opt(&tour, 1);
+        assert!((length - optimum).abs() < 1e-9);
+
+        // reversed chain
+        let (_, length) = aco.or_opt(&[0, 1, 7, 6, 5, 4, 3, 2, 8, 9], 3);
+        assert!(
+            length
+                < distances
+                    .tour_distance(&[0, 1, 7, 6, 5, 4, 3, 2, 8, 9])
+                    .unwrap()
+        );
+
+        let (tour, length) = aco.or_opt(&[2, 0, 1], 1);
+        assert_eq!(tour, vec![2, 0, 1]);
+        assert_eq!(length, distances.tour_distance(&[2, 0, 1]).unwrap());
+    }
+
+    #[test]
+    fn test_three_opt_segment_swap() {
+        // points on a line visited out of order, fixed by moving a segment without reversal
+        let xs: [f64; 8] = [0.0, 1.0, 4.0, 5.0, 2.0, 3.0, 6.0, 7.0];
+        let distances = DistancesIdx::from_custom_fn(xs.len() as u32, |apt1, apt2| {
+            Some((xs[apt1 as usize] - xs[apt2 as usize]).abs())
+        });
+        let aco = Aco::new(&distances, None, None, None);
+        let (tour, length) = aco.three_opt(&[0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(length, 14.0);
+        assert_eq!(tour.len(), 8);
+    }
+
+    #[test]
+    fn test_three_opt_infeasible() {
+        // only the ring 0 - 1 - 2 - 3 - 4 - 5 is feasible
+        let distances = DistancesIdx::from_custom_fn(6, |apt1, apt2| {
+            Some(1.0).filter(|_| apt1 - apt2 == 1 || (apt1, apt2) == (5, 0))
+        });
+        let aco = Aco::new(&distances, None, None, None);
+        let (tour, length) = aco.three_opt(&[0, 2, 1, 3, 4, 5]);
+        assert_eq!(length, 6.0);
+        assert_eq!(distances.tour_distance(&tour), Some(6.0));
+    }
 
     #[test]
     fn test_plank_law() {
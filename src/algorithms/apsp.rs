@@ -0,0 +1,89 @@
+use crate::graph::GraphIdx;
+
+/// Lengths of the shortest paths between all pairs of nodes by Floyd-Warshall algorithm over
+/// `Some` edges, `None` for nodes not reachable from each other
+pub fn all_pairs_shortest_paths<'a>(
+    graph: &GraphIdx<'a, Option<f64>>,
+) -> GraphIdx<'a, Option<f64>> {
+    let size = graph.size;
+    let mut paths = graph.clone();
+    for via in 0..size {
+        for apt1 in 0..size {
+            if apt1 == via {
+                continue;
+            }
+            let Some(to_via) = paths.between(None, apt1, via).flatten() else {
+                continue;
+            };
+            for apt2 in 0..apt1 {
+                if apt2 == via {
+                    continue;
+                }
+                let Some(from_via) = paths.between(None, via, apt2).flatten() else {
+                    continue;
+                };
+                let path = paths
+                    .between_mut(apt1, apt2)
+                    .unwrap_or_else(|| unreachable!("Edge {apt1}-{apt2} is in the graph"));
+                if path.is_none_or(|path| to_via + from_via < path) {
+                    *path = Some(to_via + from_via);
+                }
+            }
+        }
+    }
+    paths
+}
+
+/// Whether every node is reachable from any other by `Some` edges. Edges are the same in both
+/// directions, so strong connectivity does not differ from the weak one.
+pub fn is_strongly_connected(graph: &GraphIdx<Option<f64>>) -> bool {
+    graph.is_connected()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distance::DistancesIdx;
+
+    #[test]
+    fn test_all_pairs_shortest_paths() {
+        // chain 0 - 1 - 2 with long direct edge 0 - 2, 3 is isolated
+        let distances = DistancesIdx::from_custom_fn(4, |apt1, apt2| match (apt1, apt2) {
+            (1, 0) => Some(1.0),
+            (2, 1) => Some(2.0),
+            (2, 0) => Some(5.0),
+            _ => None,
+        });
+        let paths = all_pairs_shortest_paths(&distances.graph.to_dense());
+        let path = |apt1, apt2| paths.between(None, apt1, apt2).flatten();
+        assert_eq!(path(0, 1), Some(1.0));
+        assert_eq!(path(1, 2), Some(2.0));
+        assert_eq!(path(2, 0), Some(3.0));
+        assert_eq!((path(0, 3), path(1, 3), path(2, 3)), (None, None, None));
+        assert!(!is_strongly_connected(&distances.graph.to_dense()));
+    }
+
+    #[test]
+    fn test_components() {
+        // {0, 2} and {1, 3}
+        let distances = DistancesIdx::from_custom_fn(4, |apt1, apt2| {
+            Some(1.0).filter(|_| apt1 % 2 == apt2 % 2)
+        });
+        let paths = all_pairs_shortest_paths(&distances.graph.to_dense());
+        let reachable: Vec<_> = paths
+            .iter_edges()
+            .filter(|(_, _, path)| path.is_some())
+            .map(|(apt1, apt2, _)| (apt1, apt2))
+            .collect();
+        assert_eq!(reachable, vec![(2, 0), (3, 1)]);
+        assert!(!is_strongly_connected(&distances.graph.to_dense()));
+
+        let connected = DistancesIdx::from_custom_fn(4, |apt1, apt2| {
+            Some(1.0).filter(|_| apt1.abs_diff(apt2) == 1)
+        });
+        assert!(is_strongly_connected(&connected.graph.to_dense()));
+        assert!(all_pairs_shortest_paths(&connected.graph.to_dense())
+            .iter_edges()
+            .all(|(apt1, apt2, path)| path == Some(apt1.abs_diff(apt2) as f64)));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod apsp;
+pub mod mst;
+pub(crate) mod union_find;
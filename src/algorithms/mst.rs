@@ -0,0 +1,91 @@
+use crate::algorithms::union_find::UnionFind;
+use crate::graph::GraphIdx;
+use crate::kahan::KahanAdder;
+
+/// Minimum spanning tree by Kruskal's algorithm over `Some` edges, marked `true` in the returned
+/// graph, with its total weight. Disconnected graph gets minimum spanning forest.
+///
+/// Removing any leg of a tour leaves a spanning path, so the weight is a lower bound of the
+/// shortest tour length.
+pub fn minimum_spanning_tree<'a>(graph: &GraphIdx<'a, Option<f64>>) -> (GraphIdx<'a, bool>, f64) {
+    let mut edges: Vec<_> = graph
+        .iter_edges()
+        .filter_map(|(apt1, apt2, dist)| dist.map(|dist| (apt1, apt2, dist)))
+        .collect();
+    edges.sort_unstable_by(|(_, _, dist1), (_, _, dist2)| dist1.total_cmp(dist2));
+
+    let mut tree = graph.transform_const(false);
+    let mut weight = KahanAdder::default();
+    let mut components = UnionFind::new(graph.size);
+    let mut left = graph.size.saturating_sub(1);
+    for (apt1, apt2, dist) in edges {
+        if left == 0 {
+            break;
+        }
+        if components.union(apt1, apt2) {
+            tree.set(apt1, apt2, true)
+                .unwrap_or_else(|| unreachable!("Edge {apt1}-{apt2} is in the graph"));
+            weight.push_mut(dist);
+            left -= 1;
+        }
+    }
+    (tree, weight.result())
+}
+
+impl<'a> GraphIdx<'a, Option<f64>> {
+    /// Weight of [`minimum_spanning_tree`]
+    pub fn kruskal_mst_weight(&self) -> f64 {
+        minimum_spanning_tree(self).1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distance::DistancesIdx;
+
+    #[test]
+    fn test_equilateral() {
+        let distances = DistancesIdx::from_custom_fn(3, |_, _| Some(2.0));
+        let (tree, weight) = minimum_spanning_tree(&distances.graph.to_dense());
+        assert_eq!(weight, 2.0 / 3.0 * distances.graph.triangle_sum());
+        assert_eq!(tree.iter_edges().filter(|&(_, _, edge)| edge).count(), 2);
+        assert_eq!(distances.graph.to_dense().kruskal_mst_weight(), weight);
+    }
+
+    #[test]
+    fn test_lower_bound() {
+        // points on a line with gaps 1, 2 and 3
+        let xs = [0.0_f64, 1.0, 3.0, 6.0];
+        let distances = DistancesIdx::from_custom_fn(4, |apt1, apt2| {
+            Some((xs[apt1 as usize] - xs[apt2 as usize]).abs())
+        });
+        let (tree, weight) = minimum_spanning_tree(&distances.graph.to_dense());
+        assert_eq!(weight, 6.0);
+        let tree_edges: Vec<_> = tree
+            .iter_edges()
+            .filter(|&(_, _, edge)| edge)
+            .map(|(apt1, apt2, _)| (apt1, apt2))
+            .collect();
+        assert_eq!(tree_edges, vec![(1, 0), (2, 1), (3, 2)]);
+        assert!(weight <= distances.tour_distance(&[0, 1, 2, 3]).unwrap());
+    }
+
+    #[test]
+    fn test_forest() {
+        // components {0, 2} and {1, 3}
+        let distances = DistancesIdx::from_custom_fn(4, |apt1, apt2| {
+            Some((apt1 + apt2) as f64).filter(|_| apt1 % 2 == apt2 % 2)
+        });
+        let (tree, weight) = minimum_spanning_tree(&distances.graph.to_dense());
+        assert_eq!(weight, 2.0 + 4.0);
+        assert_eq!(tree.iter_edges().filter(|&(_, _, edge)| edge).count(), 2);
+        assert_eq!(
+            DistancesIdx::default()
+                .graph
+                .to_dense()
+                .kruskal_mst_weight(),
+            0.0
+        );
+    }
+}
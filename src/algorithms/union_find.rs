@@ -0,0 +1,58 @@
+/// Disjoint sets of nodes with path halving and union by size
+#[derive(Clone, Debug)]
+pub(crate) struct UnionFind {
+    parents: Vec<u32>,
+    sizes: Vec<u32>,
+}
+
+impl UnionFind {
+    pub(crate) fn new(size: u32) -> Self {
+        Self {
+            parents: (0..size).collect(),
+            sizes: vec![1; size as usize],
+        }
+    }
+
+    pub(crate) fn find(&mut self, mut node: u32) -> u32 {
+        while self.parents[node as usize] != node {
+            let grandparent = self.parents[self.parents[node as usize] as usize];
+            self.parents[node as usize] = grandparent;
+            node = grandparent;
+        }
+        node
+    }
+
+    /// Returns `false` if nodes are already in the same set
+    pub(crate) fn union(&mut self, node1: u32, node2: u32) -> bool {
+        let (root1, root2) = (self.find(node1), self.find(node2));
+        if root1 == root2 {
+            return false;
+        }
+        let (larger, smaller) = if self.sizes[root1 as usize] >= self.sizes[root2 as usize] {
+            (root1, root2)
+        } else {
+            (root2, root1)
+        };
+        self.parents[smaller as usize] = larger;
+        self.sizes[larger as usize] += self.sizes[smaller as usize];
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union_find() {
+        let mut uf = UnionFind::new(5);
+        assert!(uf.union(0, 1));
+        assert!(uf.union(3, 4));
+        assert!(!uf.union(1, 0));
+        assert_eq!(uf.find(0), uf.find(1));
+        assert_ne!(uf.find(1), uf.find(3));
+        assert!(uf.union(1, 4));
+        assert_eq!(uf.find(0), uf.find(3));
+        assert_ne!(uf.find(2), uf.find(0));
+    }
+}
@@ -0,0 +1,222 @@
+use crate::aco::{ant_system_update, q_and_intensity, AcoResult, MINIMAL_INTENSITY};
+use crate::distance::DirectedDistancesIdx;
+use crate::graph::DirectedGraphIdx;
+use crate::kahan::KahanAdder;
+use crate::par::prelude::*;
+use crate::reusable_weighted_index::CumulativeWeightsWrapper;
+use crate::util::cycling;
+use bitvec::bitvec;
+use bitvec::vec::BitVec;
+use rand::distributions::Distribution;
+use rand::{random, Rng};
+use rand_pcg::Pcg64Mcg;
+
+/// Ant System over [`DirectedDistancesIdx`], pheromones are kept per direction as well
+#[derive(Clone, Debug, PartialEq)]
+pub struct AsymmetricAco<'a> {
+    size: u32,
+    dist_idx: &'a DirectedDistancesIdx<'a>,
+    intensity: f64,
+    q: f64,
+}
+
+impl<'a> AsymmetricAco<'a> {
+    pub fn new(
+        dist_idx: &'a DirectedDistancesIdx<'a>,
+        intensity: Option<f64>,
+        q: Option<f64>,
+    ) -> Self {
+        let size = dist_idx.graph.size;
+
+        let (sum, count) = dist_idx
+            .graph
+            .edges
+            .iter()
+            .flatten()
+            .fold((KahanAdder::default(), 0), |(sum, count), &dist| {
+                (sum.push(dist), count + 1)
+            });
+        let mean_dist = (count > 0).then(|| sum.result() / count as f64);
+
+        let (q, intensity) = q_and_intensity(mean_dist, q, intensity);
+
+        Self {
+            size,
+            dist_idx,
+            intensity,
+            q,
+        }
+    }
+
+    /// Runs all `iterations`, returns empty cycle with `NaN` length if ants find no cycle during
+    /// the first iteration
+    pub fn aco(
+        &self,
+        iterations: u32,
+        ants: u32,
+        degradation_factor: f64,
+        alpha: f64,
+        beta: f64,
+    ) -> AcoResult {
+        let trivial = |tour, dist| AcoResult {
+            tour,
+            dist,
+            converged_at: 0,
+            frames: vec![],
+        };
+        match self.size {
+            0 => return trivial(vec![], 0.0),
+            1 => return trivial(vec![0], 0.0),
+            _ => {}
+        }
+
+        let mut intensities = self.dist_idx.graph.transform(|d| d.map(|_| self.intensity));
+        let mut weights = DirectedGraphIdx::default();
+        let mut best_cycle_dist: Option<(Vec<u32>, f64)> = None;
+        let mut cycles = Vec::with_capacity(ants as usize + 1);
+
+        for i in 0..iterations {
+            intensities
+                .merge_parallel_into(&self.dist_idx.graph, &mut weights, |intensity, dist| {
+                    intensity.zip(dist).map(|(intensity, dist)| {
+                        intensity.max(MINIMAL_INTENSITY).powf(alpha) / dist.powf(beta)
+                    })
+                })
+                .unwrap_or_else(|| unreachable!("Mismatched graph sizes"));
+            cycles.clear();
+            cycles.par_extend(
+                (0..ants)
+                    .into_par_iter()
+                    .map_init(
+                        || {
+                            (
+                                Pcg64Mcg::new(random()),
+                                bitvec![1; self.size as usize],
+                                CumulativeWeightsWrapper::with_capacity(self.size as usize),
+                            )
+                        },
+                        |(rng, not_visited, cumulative_weights_wrapper), _| {
+                            not_visited.fill(true);
+                            self.traverse_graph(
+                                &weights,
+                                rng,
+                                not_visited,
+                                cumulative_weights_wrapper,
+                            )
+                        },
+                    )
+                    .flatten(),
+            );
+            if cycles.is_empty() && best_cycle_dist.is_none() {
+                eprintln!("Warning: ants found no cycle");
+                return trivial(vec![], f64::NAN);
+            }
+            ant_system_update(
+                &mut cycles,
+                &mut best_cycle_dist,
+                self.q,
+                i,
+                None,
+                &mut intensities,
+                |intensities| {
+                    intensities.transform_inplace_par(|value| {
+                        if let Some(value) = value {
+                            *value *= degradation_factor;
+                        }
+                    })
+                },
+                |intensities, cycle, delta| {
+                    for (&from, &to) in cycling(cycle) {
+                        if let Some(Some(intensity)) = intensities.between_mut(from, to) {
+                            *intensity += delta;
+                        }
+                    }
+                },
+            );
+        }
+
+        println!("Best cycle: {best_cycle_dist:?}");
+
+        match best_cycle_dist {
+            Some((tour, dist)) => AcoResult {
+                tour,
+                dist,
+                converged_at: iterations,
+                frames: vec![],
+            },
+            None => trivial(vec![], f64::NAN),
+        }
+    }
+
+    // `None` if the ant gets stuck or can not return to the source
+    fn traverse_graph(
+        &self,
+        weights: &DirectedGraphIdx<Option<f64>>,
+        rng: &mut impl Rng,
+        not_visited: &mut BitVec,
+        cumulative_weights_wrapper: &mut CumulativeWeightsWrapper<f64>,
+    ) -> Option<(Vec<u32>, f64)> {
+        let source_node = rng.gen_range(0..self.size);
+        not_visited.set(source_node as usize, false);
+
+        let mut cycle = Vec::with_capacity(self.size as usize);
+        cycle.push(source_node);
+        let mut current = source_node;
+        let mut total_dist = KahanAdder::default();
+
+        while not_visited.any() {
+            let wi = cumulative_weights_wrapper
+                .fill(not_visited.iter_ones().map(|next| {
+                    weights
+                        .between(None, current, next as u32)
+                        .flatten()
+                        .unwrap_or(0.0)
+                }))
+                .ok()?;
+            let chosen = not_visited
+                .iter_ones()
+                .nth(wi.sample(rng))
+                .unwrap_or_else(|| unreachable!("No node in sampled position"));
+            not_visited.set(chosen, false);
+            let chosen = chosen as u32;
+            total_dist.push_mut(self.dist_idx.between(current, chosen)?);
+            cycle.push(chosen);
+            current = chosen;
+        }
+
+        let dist = total_dist.push_and_result(self.dist_idx.between(current, source_node)?);
+        Some((cycle, dist))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directed_cycle() {
+        // going 0 -> 1 -> 2 -> 0 is ten times shorter than the opposite direction
+        let distances = DirectedDistancesIdx::from_custom_fn(3, |from, to| {
+            Some(if to == (from + 1) % 3 { 1.0 } else { 10.0 })
+        });
+        let result = AsymmetricAco::new(&distances, None, None).aco(10, 10, 0.9, 1.0, 2.0);
+        assert_eq!(result.dist, 3.0);
+        assert_eq!(distances.tour_distance(&result.tour), Some(3.0));
+    }
+
+    #[test]
+    fn test_trivial_and_infeasible() {
+        let empty = DirectedDistancesIdx::default();
+        assert!(AsymmetricAco::new(&empty, None, None)
+            .aco(10, 10, 0.9, 1.0, 1.0)
+            .tour
+            .is_empty());
+
+        // no way back to node 0
+        let one_way =
+            DirectedDistancesIdx::from_custom_fn(3, |_, to| Some(1.0).filter(|_| to != 0));
+        let result = AsymmetricAco::new(&one_way, None, None).aco(10, 10, 0.9, 1.0, 1.0);
+        assert!(result.tour.is_empty());
+        assert!(result.dist.is_nan());
+    }
+}
@@ -0,0 +1,203 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::PathBuf;
+
+/// ACO parameters, with the `serde` feature read from TOML file where missing fields take
+/// defaults
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct AcoConfig {
+    pub ants: u32,
+    pub iterations: u32,
+    pub evaporation: f64,
+    pub alpha: f64,
+    pub beta: f64,
+    pub elite_ants: u32,
+    pub stagnation_limit: Option<u32>,
+    pub seed: Option<u64>,
+    pub candidates: Option<u32>,
+}
+
+impl Default for AcoConfig {
+    fn default() -> Self {
+        Self {
+            ants: 50,
+            iterations: 100,
+            evaporation: 0.1,
+            alpha: 0.9,
+            beta: 1.5,
+            elite_ants: 0,
+            stagnation_limit: None,
+            seed: None,
+            candidates: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConfigError {
+    /// Config file could not be read
+    Io {
+        path: PathBuf,
+        kind: io::ErrorKind,
+    },
+    Parse(String),
+    Invalid {
+        field: &'static str,
+        reason: &'static str,
+    },
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io { path, kind } => {
+                write!(f, "cannot read config {}: {kind}", path.display())
+            }
+            ConfigError::Parse(message) => write!(f, "invalid config: {message}"),
+            ConfigError::Invalid { field, reason } => {
+                write!(f, "invalid config: {field} {reason}")
+            }
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+impl AcoConfig {
+    /// Parses and validates config
+    #[cfg(feature = "serde")]
+    pub fn from_toml(s: &str) -> Result<Self, ConfigError> {
+        let config: Self = toml::from_str(s).map_err(|e| ConfigError::Parse(e.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let check = |valid: bool, field, reason| {
+            if valid {
+                Ok(())
+            } else {
+                Err(ConfigError::Invalid { field, reason })
+            }
+        };
+        check(
+            self.evaporation > 0.0 && self.evaporation <= 1.0,
+            "evaporation",
+            "must be in (0, 1]",
+        )?;
+        check(self.alpha > 0.0, "alpha", "must be positive")?;
+        check(self.beta > 0.0, "beta", "must be positive")?;
+        check(self.ants >= 2, "ants", "must be at least 2")?;
+        check(self.iterations >= 1, "iterations", "must be at least 1")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_from_toml() {
+        assert_eq!(AcoConfig::from_toml("").unwrap(), AcoConfig::default());
+        assert_eq!(
+            AcoConfig::from_toml(include_str!("../config.toml")).unwrap(),
+            AcoConfig::default()
+        );
+        assert_eq!(
+            AcoConfig::from_toml("ants = 200\niterations = 1000\nseed = 42\n").unwrap(),
+            AcoConfig {
+                ants: 200,
+                iterations: 1000,
+                seed: Some(42),
+                ..AcoConfig::default()
+            }
+        );
+        assert!(matches!(
+            AcoConfig::from_toml("ant = 200\n"),
+            Err(ConfigError::Parse(_))
+        ));
+        assert!(matches!(
+            AcoConfig::from_toml("ants = \"many\"\n"),
+            Err(ConfigError::Parse(_))
+        ));
+        assert!(matches!(
+            AcoConfig::from_toml("ants = 1"),
+            Err(ConfigError::Invalid { field: "ants", .. })
+        ));
+        assert!(AcoConfig::from_toml("evaporation = 1.0").is_ok());
+    }
+
+    #[test]
+    fn test_validate() {
+        let invalid_field = |config: AcoConfig| match config.validate() {
+            Err(ConfigError::Invalid { field, .. }) => field,
+            other => panic!("Unexpected {other:?}"),
+        };
+        let default = AcoConfig::default;
+        assert_eq!(
+            invalid_field(AcoConfig {
+                evaporation: 0.0,
+                ..default()
+            }),
+            "evaporation"
+        );
+        assert_eq!(
+            invalid_field(AcoConfig {
+                evaporation: 1.5,
+                ..default()
+            }),
+            "evaporation"
+        );
+        assert_eq!(
+            invalid_field(AcoConfig {
+                alpha: 0.0,
+                ..default()
+            }),
+            "alpha"
+        );
+        assert_eq!(
+            invalid_field(AcoConfig {
+                beta: -1.0,
+                ..default()
+            }),
+            "beta"
+        );
+        assert_eq!(
+            invalid_field(AcoConfig {
+                ants: 1,
+                ..default()
+            }),
+            "ants"
+        );
+        assert_eq!(
+            invalid_field(AcoConfig {
+                iterations: 0,
+                ..default()
+            }),
+            "iterations"
+        );
+        assert!(AcoConfig {
+            evaporation: 1.0,
+            ..default()
+        }
+        .validate()
+        .is_ok());
+        assert_eq!(
+            AcoConfig {
+                ants: 1,
+                ..AcoConfig::default()
+            }
+            .validate()
+            .unwrap_err()
+            .to_string(),
+            "invalid config: ants must be at least 2"
+        );
+    }
+}
@@ -1,50 +1,358 @@
-use crate::graph::GraphIdx;
-use crate::model::AirportIdx;
+use crate::graph::{DirectedGraphIdx, GraphIdx};
+use crate::kahan::{kahan_sum, KahanAdder};
+use crate::math::DistanceFormula;
+use crate::model::{Airport, AirportIdx};
+use crate::sparse_graph::SparseGraphIdx;
+use crate::util::cycling;
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
 
-#[derive(Clone, Debug, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DistancesIdx<'a> {
-    pub graph: GraphIdx<'a, Option<f64>>,
+    pub graph: DistGraph<'a>,
+}
+
+/// Distances in either representation, the sparse one is cheaper to build and keep for graphs
+/// with few feasible legs
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DistGraph<'a> {
+    Dense(GraphIdx<'a, Option<f64>>),
+    Sparse(SparseGraphIdx<'a, f64>),
+}
+
+/// Distances which may differ by direction
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct DirectedDistancesIdx<'a> {
+    pub graph: DirectedGraphIdx<'a, Option<f64>>,
+}
+
+impl<'a> DirectedDistancesIdx<'a> {
+    pub fn between(&self, from: u32, to: u32) -> Option<f64> {
+        self.graph.between(None, from, to).flatten()
+    }
+
+    /// Fills the matrix with `f(from, to)` for `from != to`
+    pub fn from_custom_fn(size: u32, f: impl Fn(u32, u32) -> Option<f64>) -> Self {
+        Self {
+            graph: DirectedGraphIdx {
+                size,
+                edges: (0..size)
+                    .flat_map(|from| (0..size).map(move |to| (from, to)))
+                    .map(|(from, to)| if from == to { None } else { f(from, to) })
+                    .collect(),
+                _pd: PhantomData,
+            },
+        }
+    }
+
+    pub fn tour_distance(&self, cycle: &[u32]) -> Option<f64> {
+        if let &[apt] = cycle {
+            return (apt < self.graph.size).then_some(0.0);
+        }
+        cycling(cycle)
+            .try_fold(KahanAdder::default(), |acc, (&from, &to)| {
+                self.between(from, to).map(|dist| acc.push(dist))
+            })
+            .map(KahanAdder::result)
+    }
+}
+
+impl<'a> DistGraph<'a> {
+    pub fn size(&self) -> u32 {
+        match self {
+            Self::Dense(graph) => graph.size,
+            Self::Sparse(graph) => graph.size,
+        }
+    }
+
+    pub fn between(&self, apt1: u32, apt2: u32) -> Option<f64> {
+        match self {
+            Self::Dense(graph) => graph.between(None, apt1, apt2).flatten(),
+            Self::Sparse(graph) => graph.between(apt1, apt2),
+        }
+    }
+
+    /// Feasible legs as `(apt1, apt2, dist)` with `apt1 > apt2`, in the storage order
+    pub fn iter_edges(&self) -> impl Iterator<Item = (u32, u32, f64)> + '_ {
+        let (dense, sparse) = match self {
+            Self::Dense(graph) => (Some(graph), None),
+            Self::Sparse(graph) => (None, Some(graph)),
+        };
+        dense
+            .into_iter()
+            .flat_map(|graph| {
+                graph
+                    .iter_edges()
+                    .filter_map(|(apt1, apt2, dist)| dist.map(|dist| (apt1, apt2, dist)))
+            })
+            .chain(sparse.into_iter().flat_map(SparseGraphIdx::iter_edges))
+    }
+
+    /// Fraction of feasible legs, `1.0` for graphs without legs
+    pub fn density(&self) -> f64 {
+        match self {
+            Self::Dense(graph) => graph.density(),
+            Self::Sparse(graph) => graph.density(),
+        }
+    }
+
+    pub fn triangle_sum(&self) -> f64 {
+        match self {
+            Self::Dense(graph) => graph.triangle_sum(),
+            Self::Sparse(graph) => graph.triangle_sum(),
+        }
+    }
+
+    /// For each node, up to `k` nodes reachable by feasible legs, nearest first
+    pub fn k_nearest(&self, k: u32) -> Vec<Vec<u32>> {
+        match self {
+            Self::Dense(graph) => graph.k_nearest(k),
+            Self::Sparse(graph) => graph.k_nearest(k),
+        }
+    }
+
+    /// Whether every node is reachable from any other by feasible legs
+    pub fn is_connected(&self) -> bool {
+        match self {
+            Self::Dense(graph) => graph.is_connected(),
+            Self::Sparse(graph) => graph.is_connected(),
+        }
+    }
+
+    /// Same representation with `f` applied to every feasible leg
+    pub fn transform(&self, f: impl Fn(f64) -> f64) -> Self {
+        match self {
+            Self::Dense(graph) => Self::Dense(graph.transform(|dist| dist.map(&f))),
+            Self::Sparse(graph) => Self::Sparse(graph.transform(f)),
+        }
+    }
+
+    /// Builds the graph over `nodes` only, in their order. Returns `None` if any node is out of
+    /// range or repeated.
+    pub fn subgraph(&self, nodes: &[u32]) -> Option<Self> {
+        match self {
+            Self::Dense(graph) => graph.subgraph(nodes).map(Self::Dense),
+            Self::Sparse(graph) => graph.subgraph(nodes).map(Self::Sparse),
+        }
+    }
+
+    /// Appends the row of the new last node in place, `distances_to_existing[i]` is the leg to
+    /// node `i`
+    pub fn extend_by_one_node_inplace(&mut self, distances_to_existing: Vec<Option<f64>>) {
+        match self {
+            Self::Dense(graph) => graph.extend_by_one_node_inplace(distances_to_existing),
+            Self::Sparse(graph) => graph.extend_by_one_node_inplace(distances_to_existing),
+        }
+    }
+
+    /// The matrix for algorithms indexing every pair, the sparse graph gets expanded
+    pub fn to_dense(&self) -> Cow<'_, GraphIdx<'a, Option<f64>>> {
+        match self {
+            Self::Dense(graph) => Cow::Borrowed(graph),
+            Self::Sparse(graph) => Cow::Owned(GraphIdx::from(graph)),
+        }
+    }
+}
+
+impl<'a> Default for DistGraph<'a> {
+    fn default() -> Self {
+        Self::Dense(GraphIdx::default())
+    }
 }
 
 impl<'a> DistancesIdx<'a> {
     pub fn between(&self, apt1: u32, apt2: u32) -> Option<f64> {
-        self.graph.between(None, apt1, apt2).flatten()
+        self.graph.between(apt1, apt2)
     }
 
     pub fn from(
         apt_idx: &'a AirportIdx<'a>,
         min_dist: Option<f64>,
         excepts: &HashMap<&str, HashSet<&str>>,
+    ) -> Self {
+        Self::from_with_formula(apt_idx, min_dist, excepts, DistanceFormula::default())
+    }
+
+    pub fn from_with_formula(
+        apt_idx: &'a AirportIdx<'a>,
+        min_dist: Option<f64>,
+        excepts: &HashMap<&str, HashSet<&str>>,
+        formula: DistanceFormula,
     ) -> Self {
         Self {
-            graph: GraphIdx::new(apt_idx, |apt1, apt2| {
-                Some(apt1.distance_to(apt2)).filter(|&dist| {
-                    min_dist.map(|min_dist| dist >= min_dist).unwrap_or(true)
-                        || excepts
-                            .get(apt1.icao.as_str())
-                            .filter(|s| s.contains(apt2.icao.as_str()))
-                            .is_some()
-                        || excepts
-                            .get(apt2.icao.as_str())
-                            .filter(|s| s.contains(&apt1.icao.as_str()))
-                            .is_some()
-                })
+            graph: DistGraph::Dense(GraphIdx::new(apt_idx, |apt1, apt2| {
+                filtered_distance(apt1, apt2, min_dist, excepts, formula)
+            })),
+        }
+    }
+
+    /// Keeps the representation of `graph`, the sparse one is never expanded
+    pub fn from_dist_graph(graph: DistGraph<'a>) -> Self {
+        Self { graph }
+    }
+
+    /// Fills the matrix with `f(apt1, apt2)` for `apt1 > apt2` only, as distances are symmetric
+    pub fn from_custom_fn(size: u32, f: impl Fn(u32, u32) -> Option<f64>) -> Self {
+        Self {
+            graph: DistGraph::Dense(GraphIdx {
+                size,
+                edges: (0..size)
+                    .flat_map(|apt1| (0..apt1).map(move |apt2| (apt1, apt2)))
+                    .map(|(apt1, apt2)| f(apt1, apt2))
+                    .collect(),
+                _pd: PhantomData,
             }),
         }
     }
 
+    /// Appends distances from `new_airport` to `existing_airports`, the airports of existing
+    /// nodes in order, evaluated and filtered the same way as [`DistancesIdx::from_with_formula`].
+    /// Returns the index of the new node, `None` if the number of existing airports differs from
+    /// the number of nodes.
+    pub fn push_airport(
+        &mut self,
+        new_airport: &Airport,
+        existing_airports: &[Airport],
+        min_dist: Option<f64>,
+        excepts: &HashMap<&str, HashSet<&str>>,
+        formula: DistanceFormula,
+    ) -> Option<u32> {
+        if existing_airports.len() != self.graph.size() as usize {
+            return None;
+        }
+        self.graph.extend_by_one_node_inplace(
+            existing_airports
+                .iter()
+                .map(|apt| filtered_distance(new_airport, apt, min_dist, excepts, formula))
+                .collect(),
+        );
+        Some(self.graph.size() - 1)
+    }
+
+    /// Same as [`DistancesIdx::push_airport`] of the airport `new_airport_idx` of `apt_idx`
+    /// instead of rebuilding the whole matrix. `None` unless it is the next one after existing.
+    pub fn add_airport(
+        &self,
+        apt_idx: &'a AirportIdx<'a>,
+        new_airport_idx: u32,
+        min_dist: Option<f64>,
+        excepts: &HashMap<&str, HashSet<&str>>,
+        formula: DistanceFormula,
+    ) -> Option<DistancesIdx<'a>> {
+        let new_airport = apt_idx.aps.get(new_airport_idx as usize)?;
+        let mut distances = self.clone();
+        distances.push_airport(
+            new_airport,
+            &apt_idx.aps[..new_airport_idx as usize],
+            min_dist,
+            excepts,
+            formula,
+        )?;
+        Some(distances)
+    }
+
+    /// Total length of the closed tour, `None` if any leg is infeasible.
+    pub fn tour_distance(&self, cycle: &[u32]) -> Option<f64> {
+        if let &[apt] = cycle {
+            return (apt < self.graph.size()).then_some(0.0);
+        }
+        cycling(cycle)
+            .try_fold(KahanAdder::default(), |acc, (&apt1, &apt2)| {
+                self.between(apt1, apt2).map(|dist| acc.push(dist))
+            })
+            .map(KahanAdder::result)
+    }
+
+    /// Total length of the open path, `None` if any leg is infeasible.
+    pub fn path_distance(&self, path: &[u32]) -> Option<f64> {
+        if let &[apt] = path {
+            return (apt < self.graph.size()).then_some(0.0);
+        }
+        path.windows(2)
+            .try_fold(KahanAdder::default(), |acc, legs| {
+                self.between(legs[0], legs[1]).map(|dist| acc.push(dist))
+            })
+            .map(KahanAdder::result)
+    }
+
+    pub fn nearest_neighbor_distance(&self, apt: u32) -> Option<f64> {
+        (0..self.graph.size())
+            .filter_map(|other| self.between(apt, other))
+            .min_by(f64::total_cmp)
+    }
+
+    /// Ratio of the tour length to the sum of distances from each airport of the tour to its
+    /// nearest neighbor. `NaN` if the tour is infeasible.
+    pub fn detour_factor(&self, cycle: &[u32]) -> f64 {
+        let tour_length = self.tour_distance(cycle);
+        let nearest_sum: Option<Vec<_>> = cycle
+            .iter()
+            .map(|&apt| self.nearest_neighbor_distance(apt))
+            .collect();
+        tour_length
+            .zip(nearest_sum)
+            .map_or(f64::NAN, |(tour_length, nearest_sum)| {
+                tour_length / kahan_sum(nearest_sum.into_iter())
+            })
+    }
+
+    /// Necessary conditions of Hamiltonian cycle existence: the graph is connected and every node
+    /// has at least two feasible edges (one for two nodes graph)
+    pub fn feasible_cycle_exists(&self) -> bool {
+        let size = self.graph.size();
+        let min_degree = size.saturating_sub(1).min(2);
+        let mut degrees = vec![0; size as usize];
+        for (apt1, apt2, _) in self.graph.iter_edges() {
+            degrees[apt1 as usize] += 1;
+            degrees[apt2 as usize] += 1;
+        }
+        degrees.iter().all(|&degree| degree >= min_degree) && self.graph.is_connected()
+    }
+
+    pub fn without_node(&self, node_idx: u32) -> Self {
+        let nodes: Vec<_> = (0..self.graph.size()).filter(|&i| i != node_idx).collect();
+        Self {
+            graph: self
+                .graph
+                .subgraph(&nodes)
+                .unwrap_or_else(|| unreachable!("Nodes are unique and in range")),
+        }
+    }
+
     pub fn transform(&self, f: impl Fn(f64) -> f64) -> Self {
         Self {
-            graph: self.graph.transform(|d| d.map(|v| f(v))),
+            graph: self.graph.transform(f),
         }
     }
 }
 
+/// Distance between airports unless it is below `min_dist` and the pair is not in `excepts`
+pub(crate) fn filtered_distance(
+    apt1: &Airport,
+    apt2: &Airport,
+    min_dist: Option<f64>,
+    excepts: &HashMap<&str, HashSet<&str>>,
+    formula: DistanceFormula,
+) -> Option<f64> {
+    Some(formula.distance(apt1.coord, apt2.coord)).filter(|&dist| {
+        min_dist.map(|min_dist| dist >= min_dist).unwrap_or(true)
+            || excepts
+                .get(apt1.icao.as_str())
+                .filter(|s| s.contains(apt2.icao.as_str()))
+                .is_some()
+            || excepts
+                .get(apt2.icao.as_str())
+                .filter(|s| s.contains(&apt1.icao.as_str()))
+                .is_some()
+    })
+}
+
 #[cfg(test)]
 mod tests {
-    use std::f64::consts::FRAC_PI_2;
-    use std::marker::PhantomData;
+    use std::f64::consts::{FRAC_PI_2, SQRT_2};
 
     use crate::math::great_circle;
     use crate::model::Airport;
@@ -54,6 +362,69 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_directed_distances_idx() {
+        let distances = DirectedDistancesIdx::from_custom_fn(3, |from, to| {
+            Some(if to == (from + 1) % 3 { 1.0 } else { 10.0 })
+        });
+        assert_eq!(distances.between(0, 1), Some(1.0));
+        assert_eq!(distances.between(1, 0), Some(10.0));
+        assert_eq!(distances.between(1, 1), None);
+        assert_eq!(distances.tour_distance(&[0, 1, 2]), Some(3.0));
+        assert_eq!(distances.tour_distance(&[0, 2, 1]), Some(30.0));
+        assert_eq!(distances.tour_distance(&[2]), Some(0.0));
+        assert_eq!(distances.tour_distance(&[3]), None);
+    }
+
+    #[test]
+    fn test_dist_graph() {
+        let dense = DistancesIdx::from_custom_fn(4, |apt1, apt2| {
+            Some((apt1 * 10 + apt2) as f64).filter(|_| apt1 - apt2 == 1)
+        });
+        let sparse = DistancesIdx::from_dist_graph(DistGraph::Sparse(SparseGraphIdx::from(
+            dense.graph.to_dense().as_ref(),
+        )));
+        assert!(matches!(sparse.graph, DistGraph::Sparse(_)));
+        assert_eq!(sparse.graph.size(), 4);
+        assert_eq!(sparse.between(1, 2), Some(21.0));
+        assert_eq!(sparse.between(0, 2), None);
+        assert_eq!(sparse.graph.to_dense(), dense.graph.to_dense());
+        for graph in [&dense.graph, &sparse.graph] {
+            assert_eq!(
+                graph.iter_edges().collect::<Vec<_>>(),
+                vec![(1, 0, 10.0), (2, 1, 21.0), (3, 2, 32.0)]
+            );
+            assert_eq!(graph.density(), 0.5);
+            assert_eq!(graph.triangle_sum(), 63.0);
+            assert_eq!(graph.k_nearest(1), vec![vec![1], vec![0], vec![1], vec![2]]);
+            assert!(graph.is_connected());
+            assert!(!graph.subgraph(&[3, 0, 2]).unwrap().is_connected());
+            assert_eq!(graph.subgraph(&[3, 3]), None);
+            assert_eq!(graph.transform(|dist| dist * 2.0).between(2, 3), Some(64.0));
+            let mut extended = graph.clone();
+            extended.extend_by_one_node_inplace(vec![None, None, None, Some(1.0)]);
+            assert_eq!(extended.size(), 5);
+            assert_eq!(extended.between(4, 3), Some(1.0));
+        }
+        assert!(!sparse.feasible_cycle_exists());
+        assert_eq!(sparse.without_node(0).between(0, 1), Some(21.0));
+        assert_eq!(
+            DistancesIdx::from_dist_graph(DistGraph::Dense(dense.graph.to_dense().into_owned())),
+            dense
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let distances = DistancesIdx::from_custom_fn(4, |apt1, apt2| {
+            Some(apt1 as f64 + apt2 as f64 / 10.0).filter(|_| apt1 != 3)
+        });
+        let json = serde_json::to_string(&distances).unwrap();
+        let restored: DistancesIdx = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, distances);
+    }
+
     fn airports_template() -> [Airport; 3] {
         [
             Airport {
@@ -149,15 +520,183 @@ mod tests {
         assert_eq!(
             distances_idx,
             DistancesIdx {
-                graph: GraphIdx {
+                graph: DistGraph::Dense(GraphIdx {
                     size: 3,
                     edges: vec![Some(quarter); 3],
                     _pd: PhantomData
-                }
+                })
             }
         );
     }
 
+    #[test]
+    fn test_tour_distance() {
+        let airports = airports_template();
+        let apt_idx = AirportIdx::new(&airports).unwrap();
+        let distances_idx = DistancesIdx::from(&apt_idx, None, &HashMap::new());
+        assert_eq!(distances_idx.tour_distance(&[]), Some(0.0));
+        assert_eq!(
+            distances_idx.tour_distance(&[0, 1, 2]),
+            Some(quarter() + quarter() + quarter())
+        );
+        assert_eq!(distances_idx.tour_distance(&[0, 3]), None);
+        assert_eq!(distances_idx.tour_distance(&[1]), Some(0.0));
+        assert_eq!(distances_idx.tour_distance(&[3]), None);
+
+        let distances_idx = DistancesIdx::from(&apt_idx, Some(quarter() * 2.0), &HashMap::new());
+        assert_eq!(distances_idx.tour_distance(&[0, 1, 2]), None);
+    }
+
+    #[test]
+    fn test_path_distance() {
+        let distances_idx = DistancesIdx::from_custom_fn(3, |apt1, apt2| {
+            Some((apt1 as f64 - apt2 as f64).abs()).filter(|&dist| dist < 2.0)
+        });
+        assert_eq!(distances_idx.path_distance(&[]), Some(0.0));
+        assert_eq!(distances_idx.path_distance(&[1]), Some(0.0));
+        assert_eq!(distances_idx.path_distance(&[3]), None);
+        assert_eq!(distances_idx.path_distance(&[0, 1, 2]), Some(2.0));
+        assert_eq!(distances_idx.tour_distance(&[0, 1, 2]), None);
+        assert_eq!(distances_idx.path_distance(&[1, 0, 2]), None);
+    }
+
+    #[test]
+    fn test_detour_factor() {
+        let airports = airports_template();
+        let apt_idx = AirportIdx::new(&airports).unwrap();
+        let distances_idx = DistancesIdx::from(&apt_idx, None, &HashMap::new());
+        assert_eq!(distances_idx.nearest_neighbor_distance(0), Some(quarter()));
+        assert_eq!(distances_idx.detour_factor(&[0, 1, 2]), 1.0);
+
+        let distances_idx = DistancesIdx::from(&apt_idx, Some(quarter() * 2.0), &HashMap::new());
+        assert_eq!(distances_idx.nearest_neighbor_distance(0), None);
+        assert!(distances_idx.detour_factor(&[0, 1, 2]).is_nan());
+    }
+
+    #[test]
+    fn test_from_custom_fn() {
+        // corners of the unit square in order
+        let corners = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+        let distances_idx = DistancesIdx::from_custom_fn(4, |apt1, apt2| {
+            assert!(apt1 > apt2);
+            let ((x1, y1), (x2, y2)) = (corners[apt1 as usize], corners[apt2 as usize]);
+            Some(f64::hypot(x1 - x2, y1 - y2))
+        });
+        assert_eq!(distances_idx.between(0, 1), Some(1.0));
+        assert_eq!(distances_idx.between(3, 2), Some(1.0));
+        assert_eq!(distances_idx.between(0, 2), Some(SQRT_2));
+        assert_eq!(distances_idx.between(1, 1), None);
+        assert_eq!(distances_idx.tour_distance(&[0, 1, 2, 3]), Some(4.0));
+        assert_eq!(
+            distances_idx.tour_distance(&[0, 2, 1, 3]),
+            Some(2.0 + 2.0 * SQRT_2)
+        );
+    }
+
+    #[test]
+    fn test_add_airport() {
+        let airports = airports_template();
+        let apt_idx = AirportIdx::new(&airports).unwrap();
+        let full = DistancesIdx::from(&apt_idx, None, &HashMap::new());
+
+        let partial_idx = AirportIdx::new(&airports[..2]).unwrap();
+        let partial = DistancesIdx::from(&partial_idx, None, &HashMap::new());
+        assert_eq!(
+            partial.add_airport(
+                &apt_idx,
+                2,
+                None,
+                &HashMap::new(),
+                DistanceFormula::default()
+            ),
+            Some(full.clone())
+        );
+        for new_airport_idx in [1, 3] {
+            assert_eq!(
+                partial.add_airport(
+                    &apt_idx,
+                    new_airport_idx,
+                    None,
+                    &HashMap::new(),
+                    DistanceFormula::default()
+                ),
+                None
+            );
+        }
+        let vincenty = |apt_idx| {
+            DistancesIdx::from_with_formula(
+                apt_idx,
+                None,
+                &HashMap::new(),
+                DistanceFormula::Vincenty,
+            )
+        };
+        let added = vincenty(&partial_idx)
+            .add_airport(
+                &apt_idx,
+                2,
+                None,
+                &HashMap::new(),
+                DistanceFormula::Vincenty,
+            )
+            .unwrap();
+        assert_eq!(added, vincenty(&apt_idx));
+        assert_ne!(added.between(2, 0), full.between(2, 0));
+
+        // every leg is shorter than the minimal distance, but C-A is excepted
+        let min_dist = full
+            .graph
+            .iter_edges()
+            .map(|(_, _, dist)| dist)
+            .fold(0.0, f64::max)
+            + 1.0;
+        let excepts = HashMap::from([("C", HashSet::from(["A"]))]);
+        let full = DistancesIdx::from(&apt_idx, Some(min_dist), &excepts);
+        let partial = DistancesIdx::from(&partial_idx, Some(min_dist), &excepts);
+        let added = partial
+            .add_airport(
+                &apt_idx,
+                2,
+                Some(min_dist),
+                &excepts,
+                DistanceFormula::default(),
+            )
+            .unwrap();
+        assert_eq!(added, full);
+        assert!(added.between(2, 0).is_some());
+        assert_eq!(added.between(2, 1), None);
+    }
+
+    #[test]
+    fn test_feasible_cycle_exists() {
+        let airports = airports_template();
+        let apt_idx = AirportIdx::new(&airports).unwrap();
+        let mut distances_idx = DistancesIdx::from(&apt_idx, None, &HashMap::new());
+        assert!(distances_idx.feasible_cycle_exists());
+        assert!(distances_idx.without_node(0).feasible_cycle_exists());
+
+        let DistGraph::Dense(graph) = &mut distances_idx.graph else {
+            unreachable!("Distances from airports are dense")
+        };
+        graph.set(0, 1, None).unwrap();
+        assert!(!distances_idx.feasible_cycle_exists());
+        assert!(!distances_idx.without_node(2).feasible_cycle_exists());
+    }
+
+    #[test]
+    fn test_without_node() {
+        let airports = airports_template();
+        let apt_idx = AirportIdx::new(&airports).unwrap();
+        let distances_idx = DistancesIdx::from(&apt_idx, None, &HashMap::new());
+
+        let mut storage = vec![];
+        let reduced_idx = apt_idx.without("B", &mut storage).unwrap();
+        assert_eq!(
+            distances_idx.without_node(apt_idx.idx_by_icao["B"]),
+            DistancesIdx::from(&reduced_idx, None, &HashMap::new())
+        );
+    }
+
     fn quarter() -> f64 {
         great_circle(
             Coord {
@@ -0,0 +1,228 @@
+use crate::distance::DistancesIdx;
+use crate::model::AirportIdx;
+use crate::types::field::coord::Coord;
+use crate::types::record::AirportPrimaryRecord;
+use crate::util::{cycling, json_escape};
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::fmt::Write;
+
+/// `FeatureCollection` with the tour as `LineString` features, split at the antimeridian, and a
+/// `Point` feature per airport. The tour is closed unless `open`. Elevations are taken from `recs`
+/// with the same indices as airports, if any.
+pub fn export_geojson(
+    apt_idx: &AirportIdx,
+    recs: &[AirportPrimaryRecord],
+    aco: &[u32],
+    open: bool,
+    distances: &DistancesIdx,
+) -> String {
+    let mut features = vec![];
+    for line in split_at_antimeridian(tour_coords(apt_idx, aco, open)) {
+        let coords: Vec<_> = line
+            .iter()
+            .map(|&(lon, lat)| format!("[{lon},{lat}]"))
+            .collect();
+        features.push(format!(
+            r#"{{"type":"Feature","geometry":{{"type":"LineString","coordinates":[{}]}},"properties":{{}}}}"#,
+            coords.join(",")
+        ));
+    }
+
+    let legs = if open {
+        aco.len().saturating_sub(1)
+    } else {
+        aco.len()
+    };
+    let next: HashMap<_, _> = cycling(aco).take(legs).map(|(&i, &j)| (i, j)).collect();
+    for (i, apt) in apt_idx.aps.iter().enumerate() {
+        let (lat, lon) = apt.coord.to_degrees();
+        let elevation = recs
+            .get(i)
+            .map_or("null".to_string(), |rec| rec.airport_elevation.to_string());
+        let distance_to_next = next
+            .get(&(i as u32))
+            .and_then(|&j| distances.between(i as u32, j))
+            .map_or("null".to_string(), |dist| dist.to_string());
+        features.push(format!(
+            r#"{{"type":"Feature","geometry":{{"type":"Point","coordinates":[{lon},{lat}]}},"properties":{{"icao":"{}","name":"{}","elevation":{elevation},"distance_to_next":{distance_to_next}}}}}"#,
+            json_escape(&apt.icao),
+            json_escape(&apt.name),
+        ));
+    }
+
+    let mut geojson = String::from(r#"{"type":"FeatureCollection","features":["#);
+    for (i, feature) in features.iter().enumerate() {
+        if i > 0 {
+            geojson.push(',');
+        }
+        write!(geojson, "\n{feature}").unwrap();
+    }
+    geojson.push_str("\n]}\n");
+    geojson
+}
+
+fn tour_coords(apt_idx: &AirportIdx, aco: &[u32], open: bool) -> Vec<Coord> {
+    aco.iter()
+        .chain(aco.first().filter(|_| !open))
+        .map(|&i| apt_idx.aps[i as usize].coord)
+        .collect()
+}
+
+/// Lines of `[lon, lat]` in degrees, a leg with longitudes more than 180° apart is cut where its
+/// great circle crosses the antimeridian
+fn split_at_antimeridian(coords: Vec<Coord>) -> Vec<Vec<(f64, f64)>> {
+    let mut lines = vec![];
+    let mut line = vec![];
+    for (i, &coord) in coords.iter().enumerate() {
+        if let Some(&prev) = i.checked_sub(1).and_then(|i| coords.get(i)) {
+            if (coord.lon - prev.lon).abs() > PI {
+                let lat = antimeridian_latitude(prev, coord).to_degrees();
+                let lon = if prev.lon > 0.0 { 180.0 } else { -180.0 };
+                line.push((lon, lat));
+                lines.push(line);
+                line = vec![(-lon, lat)];
+            }
+        }
+        let (lat, lon) = coord.to_degrees();
+        line.push((lon, lat));
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Latitude where the great circle through `c1` and `c2` crosses the meridian of ±180°
+fn antimeridian_latitude(c1: Coord, c2: Coord) -> f64 {
+    let Coord {
+        lat: lat1,
+        lon: lon1,
+    } = c1;
+    let Coord {
+        lat: lat2,
+        lon: lon2,
+    } = c2;
+    ((lat1.sin() * lat2.cos() * (PI - lon2).sin() - lat2.sin() * lat1.cos() * (PI - lon1).sin())
+        / (lat1.cos() * lat2.cos() * (lon1 - lon2).sin()))
+    .atan()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Airport;
+    use crate::parser::record::parse_airport_primary_record;
+    use crate::test_util::{KDEN, KLAX, KSEA};
+    use serde_json::Value;
+
+    fn airport(icao: &str, lat: f64, lon: f64) -> Airport {
+        Airport {
+            icao: icao.to_string(),
+            name: format!("{icao} \"INTL\""),
+            coord: Coord::try_from((lat, lon)).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_export_geojson() {
+        let records = [KLAX.as_bytes(), KSEA.as_bytes(), KDEN.as_bytes()];
+        let recs: Vec<_> = records
+            .iter()
+            .map(|&rec| parse_airport_primary_record(rec).unwrap())
+            .collect();
+        let apts: Vec<_> = recs.iter().map(Airport::from).collect();
+        let apt_idx = AirportIdx::new(&apts).unwrap();
+        let distances = DistancesIdx::from(&apt_idx, None, &HashMap::new());
+        let aco = [0, 2, 1];
+
+        let geojson: Value =
+            serde_json::from_str(&export_geojson(&apt_idx, &recs, &aco, false, &distances))
+                .unwrap();
+        assert_eq!(geojson["type"], "FeatureCollection");
+        let features = geojson["features"].as_array().unwrap();
+        assert_eq!(features.len(), 1 + apts.len());
+
+        let line = &features[0]["geometry"];
+        assert_eq!(line["type"], "LineString");
+        let coords = line["coordinates"].as_array().unwrap();
+        assert_eq!(coords.len(), aco.len() + 1);
+        assert_eq!(coords[0], coords[aco.len()]);
+        let (lat, lon) = apts[2].coord.to_degrees();
+        assert_eq!(coords[1], serde_json::json!([lon, lat]));
+
+        let point = &features[1];
+        assert_eq!(point["geometry"]["type"], "Point");
+        assert_eq!(point["properties"]["icao"], "KLAX");
+        assert_eq!(point["properties"]["name"], "LOS ANGELES INTL");
+        assert_eq!(point["properties"]["elevation"], 128);
+        assert_eq!(
+            point["properties"]["distance_to_next"].as_f64(),
+            distances.between(0, 2)
+        );
+        assert_eq!(features[3]["properties"]["elevation"], 5434);
+
+        let geojson: Value =
+            serde_json::from_str(&export_geojson(&apt_idx, &recs, &aco, true, &distances)).unwrap();
+        let features = geojson["features"].as_array().unwrap();
+        let coords = features[0]["geometry"]["coordinates"].as_array().unwrap();
+        assert_eq!(coords.len(), aco.len());
+        assert_ne!(coords[0], coords[aco.len() - 1]);
+        // the last airport of the path, KSEA, has no next one
+        assert_eq!(features[2]["properties"]["distance_to_next"], Value::Null);
+        assert_eq!(
+            features[3]["properties"]["distance_to_next"].as_f64(),
+            distances.between(2, 1)
+        );
+    }
+
+    #[test]
+    fn test_export_geojson_without_records() {
+        let apts = [airport("AAAA", 10.0, 20.0), airport("BBBB", 11.0, 21.0)];
+        let apt_idx = AirportIdx::new(&apts).unwrap();
+        let distances = DistancesIdx::from(&apt_idx, Some(1000.0), &HashMap::new());
+
+        let geojson: Value =
+            serde_json::from_str(&export_geojson(&apt_idx, &[], &[0, 1], false, &distances))
+                .unwrap();
+        let features = geojson["features"].as_array().unwrap();
+        assert_eq!(features[1]["properties"]["name"], "AAAA \"INTL\"");
+        assert_eq!(features[1]["properties"]["elevation"], Value::Null);
+        assert_eq!(features[1]["properties"]["distance_to_next"], Value::Null);
+
+        let geojson: Value =
+            serde_json::from_str(&export_geojson(&apt_idx, &[], &[], false, &distances)).unwrap();
+        assert_eq!(geojson["features"].as_array().unwrap().len(), apts.len());
+    }
+
+    #[test]
+    fn test_split_at_antimeridian() {
+        let apts = [
+            airport("AAAA", 10.0, 170.0),
+            airport("BBBB", 10.0, -170.0),
+            airport("CCCC", -10.0, -170.0),
+        ];
+        let apt_idx = AirportIdx::new(&apts).unwrap();
+        let lines = split_at_antimeridian(tour_coords(&apt_idx, &[0, 1, 2], false));
+        assert_eq!(lines.len(), 3);
+        let (start, crossing) = (lines[0][0], lines[0][1]);
+        assert!((start.0 - 170.0).abs() < 1e-9);
+        // same latitudes meet the antimeridian north of the parallel
+        assert_eq!(crossing.0, 180.0);
+        assert!(crossing.1 > 10.0 && crossing.1 < 10.5);
+        assert_eq!(lines[1][0], (-180.0, crossing.1));
+        assert_eq!(lines[1].len(), 4);
+        // back from CCCC to AAAA crosses the equator on the antimeridian
+        let crossing = lines[1][3];
+        assert_eq!(crossing.0, -180.0);
+        assert!(crossing.1.abs() < 1e-9);
+        assert_eq!(lines[2][0], (180.0, crossing.1));
+        assert_eq!(lines[2].len(), 2);
+
+        let apts = [airport("AAAA", 10.0, 20.0), airport("BBBB", 11.0, 21.0)];
+        let apt_idx = AirportIdx::new(&apts).unwrap();
+        let lines = split_at_antimeridian(tour_coords(&apt_idx, &[0, 1], false));
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].len(), 3);
+    }
+}
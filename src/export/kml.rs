@@ -0,0 +1,149 @@
+use crate::math::METERS_PER_FOOT;
+use crate::model::AirportIdx;
+use crate::types::record::AirportPrimaryRecord;
+use crate::util::xml_escape;
+use std::fmt::Write;
+
+/// KML 2.2 document with a folder of airport placemarks and a placemark of the tour, closed
+/// unless `open`.
+/// Altitudes are airport elevations from `recs` with the same indices as airports, if any,
+/// otherwise zero.
+pub fn export_kml(
+    apt_idx: &AirportIdx,
+    recs: &[AirportPrimaryRecord],
+    aco: &[u32],
+    open: bool,
+) -> String {
+    let coordinates = |i: usize| {
+        let (lat, lon) = apt_idx.aps[i].coord.to_degrees();
+        let alt = recs
+            .get(i)
+            .map_or(0.0, |rec| rec.airport_elevation as f64 * METERS_PER_FOOT);
+        format!("{lon},{lat},{alt}")
+    };
+
+    let mut kml = String::new();
+    writeln!(kml, r#"<?xml version="1.0" encoding="UTF-8"?>"#).unwrap();
+    writeln!(kml, r#"<kml xmlns="http://www.opengis.net/kml/2.2">"#).unwrap();
+    writeln!(kml, "<Document>").unwrap();
+    writeln!(kml, "<name>Tour</name>").unwrap();
+    writeln!(kml, "<Folder>").unwrap();
+    writeln!(kml, "<name>Airports</name>").unwrap();
+    for (i, apt) in apt_idx.aps.iter().enumerate() {
+        writeln!(
+            kml,
+            "<Placemark><name>{}</name><description>{}</description>\
+            <Point><altitudeMode>absolute</altitudeMode><coordinates>{}</coordinates></Point>\
+            </Placemark>",
+            xml_escape(&apt.icao),
+            xml_escape(&apt.name),
+            coordinates(i)
+        )
+        .unwrap();
+    }
+    writeln!(kml, "</Folder>").unwrap();
+    let tour: Vec<_> = aco
+        .iter()
+        .chain(aco.first().filter(|_| !open))
+        .map(|&i| coordinates(i as usize))
+        .collect();
+    writeln!(
+        kml,
+        "<Placemark><name>Tour</name>\
+        <LineString><tessellate>1</tessellate><coordinates>{}</coordinates></LineString>\
+        </Placemark>",
+        tour.join(" ")
+    )
+    .unwrap();
+    writeln!(kml, "</Document>").unwrap();
+    writeln!(kml, "</kml>").unwrap();
+    kml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Airport;
+    use crate::parser::record::parse_airport_primary_record;
+    use crate::test_util::{KDEN, KLAX, KSEA};
+    use crate::types::field::coord::Coord;
+
+    #[test]
+    fn test_export_kml() {
+        let records = [KLAX.as_bytes(), KSEA.as_bytes(), KDEN.as_bytes()];
+        let recs: Vec<_> = records
+            .iter()
+            .map(|&rec| parse_airport_primary_record(rec).unwrap())
+            .collect();
+        let mut apts: Vec<_> = recs.iter().map(Airport::from).collect();
+        apts[0].name = "LOS ANGELES <INTL> & CO".to_string();
+        let apt_idx = AirportIdx::new(&apts).unwrap();
+        let aco = [0, 2, 1];
+
+        let kml = export_kml(&apt_idx, &recs, &aco, false);
+        let doc = roxmltree::Document::parse(&kml).unwrap();
+        let root = doc.root_element();
+        assert_eq!(root.tag_name().name(), "kml");
+        assert_eq!(
+            root.tag_name().namespace(),
+            Some("http://www.opengis.net/kml/2.2")
+        );
+        let placemarks: Vec<_> = root
+            .descendants()
+            .filter(|node| node.has_tag_name("Placemark"))
+            .collect();
+        assert_eq!(placemarks.len(), apts.len() + 1);
+        assert!(root
+            .descendants()
+            .any(|node| node.text() == Some("LOS ANGELES <INTL> & CO")));
+
+        let coordinates = |node: roxmltree::Node| {
+            node.descendants()
+                .find(|node| node.has_tag_name("coordinates"))
+                .and_then(|node| node.text())
+                .unwrap()
+                .to_string()
+        };
+        let den: Vec<f64> = coordinates(placemarks[2])
+            .split(',')
+            .map(|v| v.parse().unwrap())
+            .collect();
+        let (lat, lon) = apts[2].coord.to_degrees();
+        assert_eq!(den[..2], [lon, lat]);
+        assert!((den[2] - 1656.3).abs() < 0.1);
+
+        let line_string = placemarks[3]
+            .descendants()
+            .find(|node| node.has_tag_name("LineString"))
+            .unwrap();
+        let tour = coordinates(line_string);
+        let tour: Vec<_> = tour.split(' ').collect();
+        assert_eq!(tour.len(), aco.len() + 1);
+        assert_eq!(tour.first(), tour.last());
+        assert_eq!(tour[1], coordinates(placemarks[2]));
+
+        let kml = export_kml(&apt_idx, &recs, &aco, true);
+        let doc = roxmltree::Document::parse(&kml).unwrap();
+        let line_string = doc
+            .descendants()
+            .find(|node| node.has_tag_name("LineString"))
+            .unwrap();
+        let path = coordinates(line_string);
+        let path: Vec<_> = path.split(' ').collect();
+        assert_eq!(path.len(), aco.len());
+        assert_ne!(path.first(), path.last());
+    }
+
+    #[test]
+    fn test_export_kml_without_records() {
+        let apts = [Airport {
+            icao: "AAAA".to_string(),
+            name: "A".to_string(),
+            coord: Coord { lat: 0.0, lon: 0.0 },
+        }];
+        let apt_idx = AirportIdx::new(&apts).unwrap();
+        let kml = export_kml(&apt_idx, &[], &[0], false);
+        roxmltree::Document::parse(&kml).unwrap();
+        assert!(kml.contains("<coordinates>0,0,0 0,0,0</coordinates>"));
+    }
+}
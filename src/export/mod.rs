@@ -0,0 +1,2 @@
+pub mod geojson;
+pub mod kml;
@@ -1,12 +1,21 @@
+use crate::algorithms::union_find::UnionFind;
+use crate::distance::{DistGraph, DistancesIdx};
 use crate::kahan::kahan_sum;
+use crate::math::DistanceFormula;
 use crate::model::{Airport, AirportIdx};
-use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
+use crate::par::prelude::*;
+use bitvec::bitvec;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
 use std::marker::PhantomData;
+use std::mem;
 
 #[derive(Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GraphIdx<'a, T: Copy> {
     pub(crate) size: u32,
     pub(crate) edges: Vec<T>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) _pd: PhantomData<AirportIdx<'a>>,
 }
 
@@ -28,7 +37,7 @@ impl<'a, T: Copy> GraphIdx<'a, T> {
         Some(&mut self.edges[Self::pos(apt1, apt2)])
     }
 
-    fn pos(apt1: u32, apt2: u32) -> usize {
+    pub(crate) fn pos(apt1: u32, apt2: u32) -> usize {
         let (apt1, apt2) = if apt1 > apt2 {
             (apt1, apt2)
         } else {
@@ -46,7 +55,20 @@ impl<'a, T: Copy> GraphIdx<'a, T> {
         Some(())
     }
 
-    pub fn new(
+    /// Evaluates `f` on every pair of airports, on all cores with the `parallel` feature
+    pub fn new(apt_idx: &'a AirportIdx, f: impl Fn(&Airport, &Airport) -> T + Sync) -> Self
+    where
+        T: Send,
+    {
+        if cfg!(feature = "parallel") {
+            Self::new_par(apt_idx, f)
+        } else {
+            Self::new_seq(apt_idx, f)
+        }
+    }
+
+    /// Same as [`GraphIdx::new`] on the current thread only
+    pub fn new_seq(
         AirportIdx { aps, .. }: &'a AirportIdx,
         f: impl Fn(&Airport, &Airport) -> T,
     ) -> Self {
@@ -63,6 +85,30 @@ impl<'a, T: Copy> GraphIdx<'a, T> {
         }
     }
 
+    /// Same as [`GraphIdx::new_seq`] on all cores with the `parallel` feature. Rows are collected
+    /// in the storage order, so the result is the same as of the sequential evaluation.
+    pub fn new_par(
+        AirportIdx { aps, .. }: &'a AirportIdx,
+        f: impl Fn(&Airport, &Airport) -> T + Sync,
+    ) -> Self
+    where
+        T: Send,
+    {
+        let size = aps.len() as u32;
+        let edges = (0..aps.len())
+            .into_par_iter()
+            .flat_map_iter(|apt1_i| {
+                let apt1 = &aps[apt1_i];
+                aps[..apt1_i].iter().map(|apt2| f(apt1, apt2))
+            })
+            .collect();
+        Self {
+            size,
+            edges,
+            _pd: PhantomData,
+        }
+    }
+
     pub fn merge<B: Copy, C: Copy>(
         &self,
         other: &GraphIdx<'a, B>,
@@ -112,6 +158,15 @@ impl<'a, T: Copy> GraphIdx<'a, T> {
         }
     }
 
+    /// Same as [`GraphIdx::transform_inplace`] on all cores, the in-place counterpart of
+    /// [`GraphIdx::transform_parallel`]
+    pub fn transform_inplace_par(&mut self, f: impl Fn(&mut T) + Sync + Send)
+    where
+        T: Send,
+    {
+        self.edges.par_iter_mut().for_each(f);
+    }
+
     pub fn transform<B: Copy>(&self, f: impl Fn(T) -> B) -> GraphIdx<'a, B> {
         GraphIdx {
             size: self.size,
@@ -120,6 +175,69 @@ impl<'a, T: Copy> GraphIdx<'a, T> {
         }
     }
 
+    /// Same as [`GraphIdx::transform`] on all cores, pays off on large graphs only
+    pub fn transform_parallel<B: Copy + Send>(
+        &self,
+        f: impl Fn(T) -> B + Sync + Send,
+    ) -> GraphIdx<'a, B>
+    where
+        T: Send + Sync,
+    {
+        GraphIdx {
+            size: self.size,
+            edges: self.edges.par_iter().map(|&a| f(a)).collect(),
+            _pd: PhantomData,
+        }
+    }
+
+    /// Builds the graph over `nodes` only, in their order. Returns `None` if any node is out of
+    /// range or repeated.
+    pub fn subgraph(&self, nodes: &[u32]) -> Option<Self> {
+        let mut seen = bitvec![0; self.size as usize];
+        for &node in nodes {
+            if node >= self.size || seen.replace(node as usize, true) {
+                return None;
+            }
+        }
+        Some(Self {
+            size: nodes.len() as u32,
+            edges: nodes
+                .iter()
+                .enumerate()
+                .flat_map(|(i, &apt1)| {
+                    nodes[..i]
+                        .iter()
+                        .map(move |&apt2| self.edges[Self::pos(apt1, apt2)])
+                })
+                .collect(),
+            _pd: PhantomData,
+        })
+    }
+
+    /// Appends the row of the new last node, `distances_to_existing[i]` is the edge to node `i`
+    pub fn extend_by_one_node(&self, distances_to_existing: Vec<T>) -> GraphIdx<'a, T> {
+        let mut edges = Vec::with_capacity(self.edges.len() + distances_to_existing.len());
+        edges.extend_from_slice(&self.edges);
+        let mut graph = GraphIdx {
+            size: self.size,
+            edges,
+            _pd: PhantomData,
+        };
+        graph.extend_by_one_node_inplace(distances_to_existing);
+        graph
+    }
+
+    /// Same as [`GraphIdx::extend_by_one_node`] without copying existing edges
+    pub fn extend_by_one_node_inplace(&mut self, distances_to_existing: Vec<T>) {
+        assert_eq!(
+            distances_to_existing.len(),
+            self.size as usize,
+            "New node should have edges to all existing nodes"
+        );
+        self.edges.extend(distances_to_existing);
+        self.size += 1;
+    }
+
     pub fn transform_const<B: Copy>(&self, c: B) -> GraphIdx<'a, B> {
         GraphIdx {
             size: self.size,
@@ -127,15 +245,831 @@ impl<'a, T: Copy> GraphIdx<'a, T> {
             _pd: PhantomData,
         }
     }
+
+    /// Yields `(apt1, apt2, value)` for every `apt1 > apt2`, ordered by `apt1` and then by `apt2`,
+    /// which is the storage order
+    pub fn iter_edges(&self) -> impl Iterator<Item = (u32, u32, T)> + '_ {
+        (0..self.size)
+            .flat_map(|apt1| (0..apt1).map(move |apt2| (apt1, apt2)))
+            .zip(&self.edges)
+            .map(|((apt1, apt2), &value)| (apt1, apt2, value))
+    }
+
+    /// Same as [`Self::iter_edges`], but each row ends with `(apt1, apt1, default)`
+    pub fn iter_edges_with_default(&self, default: T) -> impl Iterator<Item = (u32, u32, T)> + '_ {
+        (0..self.size).flat_map(move |apt1| {
+            (0..=apt1).map(move |apt2| {
+                let value = if apt2 == apt1 {
+                    default
+                } else {
+                    self.edges[Self::pos(apt1, apt2)]
+                };
+                (apt1, apt2, value)
+            })
+        })
+    }
+}
+impl<'a, T: Copy> Default for GraphIdx<'a, T> {
+    fn default() -> Self {
+        Self {
+            size: 0,
+            edges: vec![],
+            _pd: PhantomData,
+        }
+    }
 }
+
+impl<'a, T: Copy> GraphIdx<'a, Option<T>> {
+    /// Fraction of `Some` edges, `1.0` for graphs without edges
+    pub fn density(&self) -> f64 {
+        if self.edges.is_empty() {
+            return 1.0;
+        }
+        self.edges.iter().flatten().count() as f64 / self.edges.len() as f64
+    }
+}
+
 impl<'a> GraphIdx<'a, f64> {
     pub fn triangle_sum(&self) -> f64 {
         kahan_sum(self.edges.iter().copied())
     }
+
+    /// Fraction of edges which survive `min_dist = threshold_km` filter, `1.0` for graphs without
+    /// edges
+    pub fn density_at_threshold(&self, threshold_km: f64) -> f64 {
+        if self.edges.is_empty() {
+            return 1.0;
+        }
+        self.edges
+            .iter()
+            .filter(|&&dist| dist >= threshold_km)
+            .count() as f64
+            / self.edges.len() as f64
+    }
+
+    /// `(threshold, density)` pairs for thresholds evenly spaced from zero to the longest edge
+    pub fn density_curve(&self, n_points: usize) -> Vec<(f64, f64)> {
+        let max = self.edges.iter().copied().fold(0.0, f64::max);
+        let step = max / n_points.saturating_sub(1).max(1) as f64;
+        (0..n_points)
+            .map(|i| i as f64 * step)
+            .map(|threshold| (threshold, self.density_at_threshold(threshold)))
+            .collect()
+    }
+
+    /// Edges below `min_val` become `None`
+    pub fn apply_threshold(&self, min_val: f64) -> GraphIdx<'a, Option<f64>> {
+        self.transform(|edge| Some(edge).filter(|&edge| edge >= min_val))
+    }
 }
 
 impl<'a> GraphIdx<'a, Option<f64>> {
     pub fn triangle_sum(&self) -> f64 {
         kahan_sum(self.edges.iter().flatten().copied())
     }
+
+    pub fn fill_none(&self, default: f64) -> GraphIdx<'a, f64> {
+        self.transform(|edge| edge.unwrap_or(default))
+    }
+
+    pub fn min_edge(&self) -> Option<f64> {
+        self.finite_edges().min_by(f64::total_cmp)
+    }
+
+    pub fn max_edge(&self) -> Option<f64> {
+        self.finite_edges().max_by(f64::total_cmp)
+    }
+
+    fn finite_edges(&self) -> impl Iterator<Item = f64> + '_ {
+        self.edges
+            .iter()
+            .flatten()
+            .copied()
+            .filter(|v| v.is_finite())
+    }
+
+    /// Min-max normalization of finite edges into `[0, 1]`, `0.5` if all of them are equal.
+    /// Non-finite edges become `None`.
+    pub fn normalize_weights(&self) -> GraphIdx<'a, Option<f64>> {
+        let (Some(min), Some(max)) = (self.min_edge(), self.max_edge()) else {
+            return self.transform_const(None);
+        };
+        let range = max - min;
+        self.transform(|edge| {
+            edge.filter(|v| v.is_finite())
+                .map(|v| if range > 0.0 { (v - min) / range } else { 0.5 })
+        })
+    }
+
+    /// For each node, up to `k` nodes reachable by `Some` edges, nearest first
+    pub fn k_nearest(&self, k: u32) -> Vec<Vec<u32>> {
+        (0..self.size)
+            .map(|apt1| {
+                let mut nearest: Vec<_> = (0..self.size)
+                    .filter_map(|apt2| {
+                        self.between(None, apt1, apt2)
+                            .flatten()
+                            .filter(|_| apt1 != apt2)
+                            .map(|dist| (apt2, dist))
+                    })
+                    .collect();
+                nearest.sort_unstable_by(|(_, dist1), (_, dist2)| dist1.total_cmp(dist2));
+                nearest.truncate(k as usize);
+                nearest.into_iter().map(|(apt2, _)| apt2).collect()
+            })
+            .collect()
+    }
+
+    /// Whether every node is reachable from any other by `Some` edges
+    pub fn is_connected(&self) -> bool {
+        if self.size == 0 {
+            return true;
+        }
+        let mut visited = bitvec![0; self.size as usize];
+        visited.set(0, true);
+        let mut stack = vec![0];
+        while let Some(node) = stack.pop() {
+            for next in 0..self.size {
+                if !visited[next as usize]
+                    && matches!(self.between(None, node, next), Some(Some(_)))
+                {
+                    visited.set(next as usize, true);
+                    stack.push(next);
+                }
+            }
+        }
+        visited.all()
+    }
+
+    pub fn shortest_path(&self, start: u32, end: u32) -> Option<(Vec<u32>, f64)> {
+        if start >= self.size || end >= self.size {
+            return None;
+        }
+        let size = self.size as usize;
+        let mut dists = vec![f64::INFINITY; size];
+        let mut prevs = vec![None; size];
+        let mut heap = BinaryHeap::new();
+        dists[start as usize] = 0.0;
+        heap.push(MinDistNode {
+            dist: 0.0,
+            node: start,
+        });
+
+        while let Some(MinDistNode { dist, node }) = heap.pop() {
+            if node == end {
+                break;
+            }
+            if dist > dists[node as usize] {
+                continue;
+            }
+            for next in 0..self.size {
+                let Some(Some(edge)) = self.between(None, node, next) else {
+                    continue;
+                };
+                let next_dist = dist + edge;
+                if next_dist < dists[next as usize] {
+                    dists[next as usize] = next_dist;
+                    prevs[next as usize] = Some(node);
+                    heap.push(MinDistNode {
+                        dist: next_dist,
+                        node: next,
+                    });
+                }
+            }
+        }
+
+        let dist = dists[end as usize];
+        if !dist.is_finite() {
+            return None;
+        }
+        let mut path = vec![end];
+        let mut current = end;
+        while let Some(prev) = prevs[current as usize] {
+            path.push(prev);
+            current = prev;
+        }
+        path.reverse();
+        Some((path, dist))
+    }
+}
+
+/// Alternative to [`GraphIdx`] for asymmetric edges, stores the full matrix with row `from`
+/// holding edges to every `to`. The diagonal is kept, but never exposed
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct DirectedGraphIdx<'a, T: Copy> {
+    pub(crate) size: u32,
+    pub(crate) edges: Vec<T>,
+    pub(crate) _pd: PhantomData<AirportIdx<'a>>,
+}
+
+impl<'a, T: Copy> DirectedGraphIdx<'a, T> {
+    pub fn between(&self, default: T, from: u32, to: u32) -> Option<T> {
+        if from >= self.size || to >= self.size {
+            return None;
+        }
+        if from == to {
+            return Some(default);
+        }
+        Some(self.edges[self.pos(from, to)])
+    }
+
+    pub fn between_mut(&mut self, from: u32, to: u32) -> Option<&mut T> {
+        if from >= self.size || to >= self.size || from == to {
+            return None;
+        }
+        let pos = self.pos(from, to);
+        Some(&mut self.edges[pos])
+    }
+
+    fn pos(&self, from: u32, to: u32) -> usize {
+        from as usize * self.size as usize + to as usize
+    }
+
+    pub fn set(&mut self, from: u32, to: u32, val: T) -> Option<()> {
+        *self.between_mut(from, to)? = val;
+        Some(())
+    }
+
+    pub fn new(
+        AirportIdx { aps, .. }: &'a AirportIdx,
+        f: impl Fn(&Airport, &Airport) -> T,
+    ) -> Self {
+        Self {
+            size: aps.len() as u32,
+            edges: aps
+                .iter()
+                .flat_map(|from| aps.iter().map(|to| f(from, to)))
+                .collect(),
+            _pd: PhantomData,
+        }
+    }
+
+    pub fn merge<B: Copy, C: Copy>(
+        &self,
+        other: &DirectedGraphIdx<'a, B>,
+        f: impl Fn(T, B) -> C,
+    ) -> Option<DirectedGraphIdx<'a, C>> {
+        if self.size != other.size {
+            return None;
+        }
+        Some(DirectedGraphIdx {
+            size: self.size,
+            edges: self
+                .edges
+                .iter()
+                .zip(other.edges.iter())
+                .map(|(&a, &b)| f(a, b))
+                .collect(),
+            _pd: PhantomData,
+        })
+    }
+
+    pub fn merge_parallel_into<B, C>(
+        &self,
+        other: &DirectedGraphIdx<'a, B>,
+        target: &mut DirectedGraphIdx<'a, C>,
+        f: impl (Fn(T, B) -> C) + Sync,
+    ) -> Option<()>
+    where
+        T: Send + Sync,
+        B: Send + Sync + Copy,
+        C: Send + Sync + Copy,
+    {
+        if self.size != other.size {
+            return None;
+        }
+        target.size = self.size;
+        self.edges
+            .par_iter()
+            .zip(&other.edges)
+            .map(|(&a, &b)| f(a, b))
+            .collect_into_vec(&mut target.edges);
+        Some(())
+    }
+
+    pub fn transform_inplace(&mut self, f: impl Fn(&mut T)) {
+        for edge in &mut self.edges {
+            f(edge);
+        }
+    }
+
+    pub fn transform_inplace_par(&mut self, f: impl Fn(&mut T) + Sync + Send)
+    where
+        T: Send,
+    {
+        self.edges.par_iter_mut().for_each(f);
+    }
+
+    pub fn transform<B: Copy>(&self, f: impl Fn(T) -> B) -> DirectedGraphIdx<'a, B> {
+        DirectedGraphIdx {
+            size: self.size,
+            edges: self.edges.iter().map(|&a| f(a)).collect(),
+            _pd: PhantomData,
+        }
+    }
+
+    pub fn subgraph(&self, nodes: &[u32]) -> Option<Self> {
+        let mut seen = bitvec![0; self.size as usize];
+        for &node in nodes {
+            if node >= self.size || seen.replace(node as usize, true) {
+                return None;
+            }
+        }
+        Some(Self {
+            size: nodes.len() as u32,
+            edges: nodes
+                .iter()
+                .flat_map(|&from| nodes.iter().map(move |&to| self.edges[self.pos(from, to)]))
+                .collect(),
+            _pd: PhantomData,
+        })
+    }
+
+    pub fn transform_const<B: Copy>(&self, c: B) -> DirectedGraphIdx<'a, B> {
+        DirectedGraphIdx {
+            size: self.size,
+            edges: vec![c; self.edges.len()],
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: Copy> Default for DirectedGraphIdx<'a, T> {
+    fn default() -> Self {
+        Self {
+            size: 0,
+            edges: vec![],
+            _pd: PhantomData,
+        }
+    }
+}
+
+/// Appends `new_airport` to `graph` over `existing_airports` in place, leaving existing edges
+/// intact, and returns its index. Edges are evaluated and filtered as in
+/// [`DistancesIdx::push_airport`] without excepts, `None` if `existing_airports` do not match
+/// nodes of `graph`.
+pub fn add_airport(
+    graph: &mut GraphIdx<Option<f64>>,
+    new_airport: &Airport,
+    existing_airports: &[Airport],
+    min_dist: Option<f64>,
+    formula: DistanceFormula,
+) -> Option<u32> {
+    let mut distances = DistancesIdx {
+        graph: DistGraph::Dense(mem::take(graph)),
+    };
+    let new_airport_idx = distances.push_airport(
+        new_airport,
+        existing_airports,
+        min_dist,
+        &HashMap::new(),
+        formula,
+    );
+    let DistGraph::Dense(extended) = distances.graph else {
+        unreachable!("Pushing an airport keeps the representation")
+    };
+    *graph = extended;
+    new_airport_idx
+}
+
+/// Component of every node, components are numbered from 0 in order of their first nodes
+pub fn connected_components(graph: &GraphIdx<Option<f64>>) -> Vec<u32> {
+    let mut components = UnionFind::new(graph.size);
+    for (apt1, apt2, _) in graph.iter_edges().filter(|(_, _, edge)| edge.is_some()) {
+        components.union(apt1, apt2);
+    }
+    let mut labels = vec![None; graph.size as usize];
+    let mut count = 0;
+    (0..graph.size)
+        .map(|apt| {
+            *labels[components.find(apt) as usize].get_or_insert_with(|| {
+                count += 1;
+                count - 1
+            })
+        })
+        .collect()
+}
+
+/// Nodes of the largest connected component in ascending order, the first one of equal ones
+pub fn largest_connected_component_indices(graph: &GraphIdx<Option<f64>>) -> Vec<u32> {
+    let components = connected_components(graph);
+    let mut sizes = vec![0; graph.size as usize];
+    for &component in &components {
+        sizes[component as usize] += 1;
+    }
+    let Some(largest) = (0..sizes.len())
+        .rev()
+        .max_by_key(|&component| sizes[component])
+    else {
+        return vec![];
+    };
+    (0..graph.size)
+        .filter(|&apt| components[apt as usize] == largest as u32)
+        .collect()
+}
+
+#[derive(Copy, Clone, Debug)]
+struct MinDistNode {
+    dist: f64,
+    node: u32,
+}
+
+impl PartialEq for MinDistNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for MinDistNode {}
+
+impl PartialOrd for MinDistNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MinDistNode {
+    // reversed to turn max-heap into min-heap
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .dist
+            .total_cmp(&self.dist)
+            .then_with(|| other.node.cmp(&self.node))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distance::DistancesIdx;
+    use crate::math::great_circle;
+    use crate::types::field::coord::Coord;
+
+    #[test]
+    fn test_connected_components() {
+        // {0, 3}, {1} and {2, 4, 5}
+        let edges = [(3, 0), (4, 2), (5, 4)];
+        let distances = DistancesIdx::from_custom_fn(6, |apt1, apt2| {
+            Some(1.0).filter(|_| edges.contains(&(apt1, apt2)))
+        });
+        assert_eq!(
+            connected_components(&distances.graph.to_dense()),
+            vec![0, 1, 2, 0, 2, 2]
+        );
+        assert_eq!(
+            largest_connected_component_indices(&distances.graph.to_dense()),
+            vec![2, 4, 5]
+        );
+
+        let pairs = DistancesIdx::from_custom_fn(4, |apt1, apt2| {
+            Some(1.0).filter(|_| apt1 / 2 == apt2 / 2)
+        });
+        assert_eq!(
+            connected_components(&pairs.graph.to_dense()),
+            vec![0, 0, 1, 1]
+        );
+        assert_eq!(
+            largest_connected_component_indices(&pairs.graph.to_dense()),
+            vec![0, 1]
+        );
+
+        let empty = GraphIdx::<Option<f64>>::default();
+        assert!(connected_components(&empty).is_empty());
+        assert!(largest_connected_component_indices(&empty).is_empty());
+    }
+
+    #[test]
+    fn test_new() {
+        let airports: Vec<_> = (0..50)
+            .map(|i| Airport {
+                icao: format!("A{i:03}"),
+                name: String::new(),
+                coord: Coord {
+                    lat: (i as f64 * 0.37).sin(),
+                    lon: (i as f64 * 0.73).cos() * 3.0,
+                },
+            })
+            .collect();
+        let apt_idx = AirportIdx::new(&airports).unwrap();
+        let graph = GraphIdx::new_seq(&apt_idx, |apt1, apt2| great_circle(apt1.coord, apt2.coord));
+        assert_eq!(graph.size, 50);
+        let airports = &airports[..];
+        let sequential: Vec<_> = (0..airports.len())
+            .flat_map(|apt1| {
+                (0..apt1).map(move |apt2| great_circle(airports[apt1].coord, airports[apt2].coord))
+            })
+            .collect();
+        assert_eq!(graph.edges, sequential);
+        let parallel =
+            GraphIdx::new_par(&apt_idx, |apt1, apt2| great_circle(apt1.coord, apt2.coord));
+        assert_eq!(parallel, graph);
+        assert_eq!(
+            graph.between(0.0, 7, 3),
+            Some(great_circle(airports[7].coord, airports[3].coord))
+        );
+    }
+
+    #[test]
+    fn test_transform_parallel() {
+        let distances =
+            DistancesIdx::from_custom_fn(40, |apt1, apt2| Some(apt1 as f64 * 0.5 + apt2 as f64));
+        let graph = distances.graph.to_dense();
+        let f = |edge: Option<f64>| edge.map(|dist| dist.sqrt());
+        assert_eq!(graph.transform_parallel(f), graph.transform(f));
+
+        let (mut sequential, mut parallel) = (graph.clone().into_owned(), graph.into_owned());
+        sequential.transform_inplace(|edge| *edge = f(*edge));
+        parallel.transform_inplace_par(|edge| *edge = f(*edge));
+        assert_eq!(parallel, sequential);
+        assert_eq!(
+            GraphIdx::<u32>::default().transform_parallel(|edge| edge + 1),
+            GraphIdx::default()
+        );
+    }
+
+    #[test]
+    fn test_directed_graph_idx() {
+        let mut graph = DirectedGraphIdx {
+            size: 3,
+            edges: (0..9).collect(),
+            _pd: PhantomData,
+        };
+        assert_eq!(graph.between(100, 0, 1), Some(1));
+        assert_eq!(graph.between(100, 1, 0), Some(3));
+        assert_eq!(graph.between(100, 2, 2), Some(100));
+        assert_eq!(graph.between(100, 3, 0), None);
+        assert_eq!(graph.between_mut(1, 1), None);
+        graph.set(2, 1, 70).unwrap();
+        *graph.between_mut(1, 2).unwrap() += 10;
+        assert_eq!(graph.set(0, 3, 1), None);
+
+        let sub = graph.subgraph(&[2, 1]).unwrap();
+        assert_eq!(sub.between(0, 0, 1), Some(70));
+        assert_eq!(sub.between(0, 1, 0), Some(15));
+        assert_eq!(graph.subgraph(&[1, 1]), None);
+
+        let doubled = graph.transform(|edge| edge * 2);
+        let diff = doubled.merge(&graph, |a, b| a - b).unwrap();
+        assert_eq!(diff, graph);
+        let mut target = DirectedGraphIdx::default();
+        doubled
+            .merge_parallel_into(&graph, &mut target, |a, b| a - b)
+            .unwrap();
+        assert_eq!(target, graph);
+        assert_eq!(doubled.merge(&sub, |a, _| a), None);
+
+        graph.transform_inplace_par(|edge| *edge += 1);
+        graph.transform_inplace(|edge| *edge -= 1);
+        assert_eq!(graph, diff);
+        assert_eq!(graph.transform_const(()).edges.len(), 9);
+    }
+
+    #[test]
+    fn test_k_nearest() {
+        let graph = chain_graph();
+        assert_eq!(
+            graph.k_nearest(2),
+            vec![
+                vec![1, 2],
+                vec![0, 2],
+                vec![1, 3],
+                vec![2],
+                Vec::<u32>::new()
+            ]
+        );
+        assert_eq!(graph.k_nearest(0), vec![Vec::<u32>::new(); 5]);
+    }
+
+    #[test]
+    fn test_iter_edges() {
+        let graph = GraphIdx {
+            size: 3,
+            edges: vec![1, 2, 3],
+            _pd: PhantomData,
+        };
+        assert_eq!(
+            graph.iter_edges().collect::<Vec<_>>(),
+            vec![(1, 0, 1), (2, 0, 2), (2, 1, 3)]
+        );
+        assert_eq!(
+            graph.iter_edges_with_default(0).collect::<Vec<_>>(),
+            vec![
+                (0, 0, 0),
+                (1, 0, 1),
+                (1, 1, 0),
+                (2, 0, 2),
+                (2, 1, 3),
+                (2, 2, 0)
+            ]
+        );
+        assert!(graph
+            .iter_edges()
+            .all(|(apt1, apt2, value)| graph.between(0, apt1, apt2).unwrap() == value));
+        assert_eq!(GraphIdx::<u32>::default().iter_edges().count(), 0);
+    }
+
+    // 0 - 1 - 2 - 3 chain with a long shortcut between 0 and 2, node 4 is isolated
+    fn chain_graph() -> GraphIdx<'static, Option<f64>> {
+        let mut graph = GraphIdx {
+            size: 5,
+            edges: vec![None; 10],
+            _pd: PhantomData,
+        };
+        graph.set(0, 1, Some(1.0)).unwrap();
+        graph.set(1, 2, Some(1.0)).unwrap();
+        graph.set(2, 3, Some(1.0)).unwrap();
+        graph.set(0, 2, Some(5.0)).unwrap();
+        graph
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        let graph = chain_graph();
+        assert_eq!(graph.shortest_path(0, 3), Some((vec![0, 1, 2, 3], 3.0)));
+        assert_eq!(graph.shortest_path(3, 0), Some((vec![3, 2, 1, 0], 3.0)));
+        assert_eq!(graph.shortest_path(2, 2), Some((vec![2], 0.0)));
+        assert_eq!(graph.shortest_path(0, 4), None);
+        assert_eq!(graph.shortest_path(0, 5), None);
+    }
+
+    #[test]
+    fn test_is_connected() {
+        let mut graph = chain_graph();
+        assert!(!graph.is_connected());
+        graph.set(1, 4, Some(1.0)).unwrap();
+        assert!(graph.is_connected());
+        assert!(GraphIdx::<Option<f64>>::default().is_connected());
+    }
+
+    #[test]
+    fn test_normalize_weights() {
+        let mut graph = chain_graph();
+        assert_eq!(graph.min_edge(), Some(1.0));
+        assert_eq!(graph.max_edge(), Some(5.0));
+        graph.set(3, 4, Some(3.0)).unwrap();
+        graph.set(0, 4, Some(f64::INFINITY)).unwrap();
+
+        let normalized = graph.normalize_weights();
+        assert_eq!(normalized.between(None, 0, 1), Some(Some(0.0)));
+        assert_eq!(normalized.between(None, 0, 2), Some(Some(1.0)));
+        assert_eq!(normalized.between(None, 3, 4), Some(Some(0.5)));
+        assert_eq!(normalized.between(None, 0, 4), Some(None));
+        assert_eq!(normalized.between(None, 0, 3), Some(None));
+
+        let flat = graph
+            .transform(|edge| edge.map(|_| 2.0))
+            .normalize_weights();
+        assert_eq!(flat.edges.iter().flatten().count(), 6);
+        assert!(flat.edges.iter().flatten().all(|&edge| edge == 0.5));
+
+        let empty = graph.transform_const(None).normalize_weights();
+        assert!(empty.edges.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_density_curve() {
+        let base_distances = GraphIdx {
+            size: 3,
+            edges: vec![1.0, 2.0, 4.0],
+            _pd: PhantomData,
+        };
+        assert_eq!(base_distances.density_at_threshold(2.0), 2.0 / 3.0);
+        assert_eq!(
+            base_distances.density_curve(5),
+            vec![
+                (0.0, 1.0),
+                (1.0, 1.0),
+                (2.0, 2.0 / 3.0),
+                (3.0, 1.0 / 3.0),
+                (4.0, 1.0 / 3.0)
+            ]
+        );
+        assert_eq!(base_distances.density_curve(0), vec![]);
+        assert_eq!(
+            GraphIdx::<f64>::default().density_curve(2),
+            vec![(0.0, 1.0), (0.0, 1.0)]
+        );
+    }
+
+    #[test]
+    fn test_extend_by_one_node() {
+        let graph = chain_graph();
+        let extended = graph.extend_by_one_node(vec![None, None, None, None, Some(2.0)]);
+        assert_eq!(extended.size, 6);
+        assert_eq!(extended.between(None, 5, 4), Some(Some(2.0)));
+        assert_eq!(extended.between(None, 0, 5), Some(None));
+        assert_eq!(extended.subgraph(&[0, 1, 2, 3, 4]).unwrap(), graph);
+        let mut inplace = graph.clone();
+        inplace.extend_by_one_node_inplace(vec![None, None, None, None, Some(2.0)]);
+        assert_eq!(inplace, extended);
+    }
+
+    #[test]
+    fn test_add_airport() {
+        let apt = |icao: &str, lat: f64, lon: f64| Airport {
+            icao: icao.to_string(),
+            name: icao.to_string(),
+            coord: Coord::try_from((lat, lon)).unwrap(),
+        };
+        let airports = [
+            apt("KLAX", 33.94, -118.41),
+            apt("KSEA", 47.45, -122.31),
+            apt("KDEN", 39.86, -104.67),
+            apt("KSFO", 37.62, -122.38),
+        ];
+        let distance = |apt1: &Airport, apt2: &Airport| {
+            Some(apt1.distance_to(apt2)).filter(|&dist| dist >= 600.0)
+        };
+        let apt_idx = AirportIdx::new(&airports[..3]).unwrap();
+        let mut graph = GraphIdx::new(&apt_idx, distance);
+        let existing = graph.edges.clone();
+
+        assert_eq!(
+            add_airport(
+                &mut graph,
+                &airports[3],
+                &airports[..3],
+                Some(600.0),
+                DistanceFormula::default()
+            ),
+            Some(3)
+        );
+        assert_eq!(graph.size, 4);
+        assert_eq!(graph.edges[..existing.len()], existing);
+        for apt1 in 0..4 {
+            for apt2 in 0..4 {
+                if apt1 != apt2 {
+                    assert_eq!(
+                        graph.between(None, apt1, apt2),
+                        Some(distance(&airports[apt1 as usize], &airports[apt2 as usize]))
+                    );
+                }
+            }
+        }
+        // KSFO is too close to KLAX
+        assert_eq!(graph.between(None, 3, 0), Some(None));
+        let full_idx = AirportIdx::new(&airports).unwrap();
+        assert_eq!(graph, GraphIdx::new(&full_idx, distance));
+
+        let vincenty = |apt1: &Airport, apt2: &Airport| {
+            Some(DistanceFormula::Vincenty.distance(apt1.coord, apt2.coord))
+        };
+        let mut graph = GraphIdx::new(&apt_idx, vincenty);
+        add_airport(
+            &mut graph,
+            &airports[3],
+            &airports[..3],
+            None,
+            DistanceFormula::Vincenty,
+        )
+        .unwrap();
+        assert_eq!(graph, GraphIdx::new(&full_idx, vincenty));
+    }
+
+    #[test]
+    fn test_add_airport_mismatch() {
+        let airport = Airport {
+            icao: "KLAX".to_string(),
+            name: String::new(),
+            coord: Coord { lat: 0.0, lon: 0.0 },
+        };
+        let mut graph = chain_graph();
+        assert_eq!(
+            add_airport(&mut graph, &airport, &[], None, DistanceFormula::default()),
+            None
+        );
+        assert_eq!(graph, chain_graph());
+    }
+
+    #[test]
+    #[should_panic(expected = "New node should have edges to all existing nodes")]
+    fn test_extend_by_one_node_mismatch() {
+        chain_graph().extend_by_one_node(vec![None]);
+    }
+
+    #[test]
+    fn test_apply_threshold() {
+        let graph = chain_graph();
+        let filled = graph.fill_none(0.0);
+        assert_eq!(filled.between(0.0, 0, 2), Some(5.0));
+        assert_eq!(filled.between(0.0, 0, 4), Some(0.0));
+
+        let thresholded = filled.apply_threshold(1.0);
+        assert_eq!(thresholded, graph);
+        let thresholded = filled.apply_threshold(2.0);
+        assert_eq!(thresholded.between(None, 0, 1), Some(None));
+        assert_eq!(thresholded.between(None, 0, 2), Some(Some(5.0)));
+    }
+
+    #[test]
+    fn test_subgraph() {
+        let graph = chain_graph();
+        let subgraph = graph.subgraph(&[3, 2, 0]).unwrap();
+        assert_eq!(subgraph.size, 3);
+        assert_eq!(subgraph.between(None, 0, 1), Some(Some(1.0)));
+        assert_eq!(subgraph.between(None, 1, 2), Some(Some(5.0)));
+        assert_eq!(subgraph.between(None, 0, 2), Some(None));
+        assert_eq!(graph.subgraph(&[0, 0]), None);
+        assert_eq!(graph.subgraph(&[5]), None);
+    }
 }
@@ -0,0 +1,321 @@
+use crate::algorithms::union_find::UnionFind;
+use crate::distance::DistancesIdx;
+use crate::util::cycling;
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64Mcg;
+
+/// Rotations of the Hamiltonian cycle search per node before it gives up
+const ROTATIONS_PER_NODE: usize = 20;
+
+impl<'a> DistancesIdx<'a> {
+    /// Tour with short longest leg, returns it with its longest leg or `None` if not found.
+    ///
+    /// The longest edge of the minimum spanning tree bounds the search for the least leg limit
+    /// keeping the graph of legs within it biconnected, which every Hamiltonian cycle is, so it
+    /// is a lower bound of the optimal longest leg. Then binary search on the maximum leg from
+    /// that bound finds the least limit for which the rotation-extension search finds a
+    /// Hamiltonian cycle of legs within it. For metric distances between all pairs the square of
+    /// the biconnected graph is Hamiltonian, so limits from twice the lower bound hold a tour,
+    /// which makes the result a 2-approximation whenever the search finds one.
+    pub fn bottleneck_tour_approx(&self) -> Option<(Vec<u32>, f64)> {
+        match self.graph.size() {
+            0 => return Some((vec![], 0.0)),
+            1 => return Some((vec![0], 0.0)),
+            2 => return self.between(0, 1).map(|leg| (vec![0, 1], leg)),
+            _ => {}
+        }
+
+        let neighbors = self.nearest_neighbors();
+        let mut limits: Vec<_> = self.graph.iter_edges().map(|(_, _, dist)| dist).collect();
+        limits.sort_unstable_by(f64::total_cmp);
+        limits.dedup();
+        let bottleneck = self.mst_bottleneck()?;
+        let spanning = limits.partition_point(|&limit| limit < bottleneck);
+        let mut lower =
+            spanning + limits[spanning..].partition_point(|&limit| !biconnected(&neighbors, limit));
+        let mut upper = limits.len();
+        let mut found = None;
+        while lower < upper {
+            let mid = (lower + upper) / 2;
+            match hamiltonian_cycle(&neighbors, limits[mid]) {
+                Some(tour) => {
+                    found = Some(tour);
+                    upper = mid;
+                }
+                None => lower = mid + 1,
+            }
+        }
+
+        let tour = found?;
+        let longest_leg = cycling(&tour)
+            .map(|(&apt1, &apt2)| self.between(apt1, apt2))
+            .try_fold(0.0, |acc: f64, leg| leg.map(|leg| acc.max(leg)))?;
+        Some((tour, longest_leg))
+    }
+
+    /// Tour greedily going from `start` to the nearest not visited node, returns it with its
+    /// length or `None` if it gets stuck or `start` is out of range
+    pub fn nearest_neighbor_tour(&self, start: u32) -> Option<(Vec<u32>, f64)> {
+        let size = self.graph.size();
+        if start >= size {
+            return None;
+        }
+        let mut visited = vec![false; size as usize];
+        let mut tour = Vec::with_capacity(size as usize);
+        let mut current = start;
+        loop {
+            visited[current as usize] = true;
+            tour.push(current);
+            if tour.len() == size as usize {
+                break;
+            }
+            current = (0..size)
+                .filter(|&next| !visited[next as usize])
+                .filter_map(|next| self.between(current, next).map(|dist| (next, dist)))
+                .min_by(|(_, dist1), (_, dist2)| dist1.total_cmp(dist2))?
+                .0;
+        }
+        let length = self.tour_distance(&tour)?;
+        Some((tour, length))
+    }
+
+    // longest edge of the minimum spanning tree, `None` if the graph is disconnected
+    fn mst_bottleneck(&self) -> Option<f64> {
+        let size = self.graph.size();
+        let mut edges: Vec<_> = self.graph.iter_edges().collect();
+        edges.sort_unstable_by(|(_, _, dist1), (_, _, dist2)| dist1.total_cmp(dist2));
+        let mut components = UnionFind::new(size);
+        let mut left = size.saturating_sub(1);
+        if left == 0 {
+            return Some(0.0);
+        }
+        edges
+            .into_iter()
+            .filter(|&(apt1, apt2, _)| components.union(apt1, apt2))
+            .find(|_| {
+                left -= 1;
+                left == 0
+            })
+            .map(|(_, _, dist)| dist)
+    }
+
+    // feasible legs of every node, nearest first
+    fn nearest_neighbors(&self) -> Vec<Vec<(u32, f64)>> {
+        let mut neighbors = vec![vec![]; self.graph.size() as usize];
+        for (apt1, apt2, dist) in self.graph.iter_edges() {
+            neighbors[apt1 as usize].push((apt2, dist));
+            neighbors[apt2 as usize].push((apt1, dist));
+        }
+        for row in &mut neighbors {
+            row.sort_unstable_by(|(_, dist1), (_, dist2)| dist1.total_cmp(dist2));
+        }
+        neighbors
+    }
+}
+
+// neighbors with legs within `limit`
+fn within(neighbors: &[(u32, f64)], limit: f64) -> &[(u32, f64)] {
+    &neighbors[..neighbors.partition_point(|&(_, dist)| dist <= limit)]
+}
+
+// whether legs within `limit` connect all nodes with no articulation point, by depth first
+// search with low links
+fn biconnected(neighbors: &[Vec<(u32, f64)>], limit: f64) -> bool {
+    let size = neighbors.len();
+    let mut order = vec![None; size];
+    let mut low = vec![0; size];
+    order[0] = Some(0);
+    let mut visited = 1;
+    let mut root_children = 0;
+    // node with its parent and the index of its next neighbor
+    let mut stack = vec![(0, None, 0)];
+    while let Some((node, parent, next)) = stack.last_mut() {
+        let (node, parent) = (*node, *parent);
+        if let Some(&(neighbor, _)) = within(&neighbors[node], limit).get(*next) {
+            *next += 1;
+            let neighbor = neighbor as usize;
+            match order[neighbor] {
+                None => {
+                    order[neighbor] = Some(visited);
+                    low[neighbor] = visited;
+                    visited += 1;
+                    stack.push((neighbor, Some(node), 0));
+                }
+                Some(neighbor_order) if Some(neighbor) != parent => {
+                    low[node] = low[node].min(neighbor_order);
+                }
+                Some(_) => {}
+            }
+        } else {
+            stack.pop();
+            let Some(parent) = parent else {
+                continue;
+            };
+            low[parent] = low[parent].min(low[node]);
+            if parent == 0 {
+                root_children += 1;
+            } else if order[parent].is_some_and(|parent_order| low[node] >= parent_order) {
+                return false;
+            }
+        }
+    }
+    visited == size && root_children == 1
+}
+
+// rotation-extension search of a Hamiltonian cycle of legs within `limit`: the path grows by the
+// nearest not visited neighbor of its end, otherwise the end is changed by reversing the part of
+// the path after a random neighbor of it. Gives up after a number of rotations proportional to
+// the number of nodes
+fn hamiltonian_cycle(neighbors: &[Vec<(u32, f64)>], limit: f64) -> Option<Vec<u32>> {
+    let size = neighbors.len();
+    let mut rng = Pcg64Mcg::seed_from_u64(size as u64);
+    let mut path = Vec::with_capacity(size);
+    path.push(0);
+    let mut positions = vec![None; size];
+    positions[0] = Some(0);
+    let mut rotations = 0;
+    loop {
+        let end = *path.last()?;
+        let legs = within(&neighbors[end as usize], limit);
+        if let Some(&(next, _)) = legs
+            .iter()
+            .find(|&&(next, _)| positions[next as usize].is_none())
+        {
+            positions[next as usize] = Some(path.len());
+            path.push(next);
+            continue;
+        }
+        if path.len() == size && legs.iter().any(|&(next, _)| next == path[0]) {
+            return Some(path);
+        }
+        if rotations == ROTATIONS_PER_NODE * size {
+            return None;
+        }
+        rotations += 1;
+        // the neighbor before the end gives no new end
+        let pivots = || {
+            legs.iter()
+                .filter_map(|&(next, _)| positions[next as usize])
+                .filter(|&pos| pos + 2 < path.len())
+        };
+        let count = pivots().count();
+        if count == 0 {
+            return None;
+        }
+        let pivot = pivots().nth(rng.gen_range(0..count))?;
+        path[pivot + 1..].reverse();
+        for (pos, &node) in path.iter().enumerate().skip(pivot + 1) {
+            positions[node as usize] = Some(pos);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distance::DistGraph;
+    use crate::sparse_graph::SparseGraphIdx;
+    use crate::test_util::{points, random_points};
+
+    fn assert_tour(distances: &DistancesIdx, tour: &[u32], longest_leg: f64) {
+        let mut sorted = tour.to_vec();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..distances.graph.size()).collect::<Vec<_>>());
+        let max = cycling(tour)
+            .map(|(&apt1, &apt2)| distances.between(apt1, apt2).unwrap())
+            .fold(0.0, f64::max);
+        assert_eq!(longest_leg, max);
+    }
+
+    #[test]
+    fn test_bottleneck_tour_square() {
+        let distances = points(&[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]);
+        let (tour, longest_leg) = distances.bottleneck_tour_approx().unwrap();
+        assert_tour(&distances, &tour, longest_leg);
+        assert_eq!(longest_leg, 1.0);
+    }
+
+    #[test]
+    fn test_bottleneck_tour_line() {
+        let distances = points(&[
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (2.0, 0.0),
+            (3.0, 0.0),
+            (4.0, 0.0),
+            (5.0, 0.0),
+            (6.0, 0.0),
+        ]);
+        let (tour, longest_leg) = distances.bottleneck_tour_approx().unwrap();
+        assert_tour(&distances, &tour, longest_leg);
+        // optimal tour zigzags with legs of two
+        assert!(longest_leg <= 2.0 * 2.0);
+    }
+
+    #[test]
+    fn test_bottleneck_tour_sparse_ring() {
+        // only the legs of the ring and a long chord are feasible
+        let dense = DistancesIdx::from_custom_fn(8, |apt1, apt2| match apt1 - apt2 {
+            1 | 7 => Some(1.0),
+            4 => Some(10.0),
+            _ => None,
+        });
+        let sparse = DistancesIdx::from_dist_graph(DistGraph::Sparse(SparseGraphIdx::from(
+            dense.graph.to_dense().as_ref(),
+        )));
+        for distances in [dense, sparse] {
+            let (tour, longest_leg) = distances.bottleneck_tour_approx().unwrap();
+            assert_tour(&distances, &tour, longest_leg);
+            assert_eq!(longest_leg, 1.0);
+        }
+    }
+
+    #[test]
+    fn test_bottleneck_tour_random() {
+        for seed in 0..5 {
+            let distances = random_points(40, seed);
+            let neighbors = distances.nearest_neighbors();
+            let mut limits: Vec<_> = distances
+                .graph
+                .iter_edges()
+                .map(|(_, _, dist)| dist)
+                .collect();
+            limits.sort_unstable_by(f64::total_cmp);
+            let lower_bound = limits
+                .into_iter()
+                .find(|&limit| biconnected(&neighbors, limit))
+                .unwrap();
+            let (tour, longest_leg) = distances.bottleneck_tour_approx().unwrap();
+            assert_tour(&distances, &tour, longest_leg);
+            assert!(longest_leg >= lower_bound);
+            assert!(longest_leg <= 2.0 * lower_bound);
+        }
+    }
+
+    #[test]
+    fn test_nearest_neighbor_tour() {
+        let distances = points(&[(0.0, 0.0), (2.0, 0.0), (1.0, 0.0), (3.0, 0.0)]);
+        let (tour, length) = distances.nearest_neighbor_tour(0).unwrap();
+        assert_eq!(tour, vec![0, 2, 1, 3]);
+        assert_eq!(length, 6.0);
+
+        let stuck = DistancesIdx::from_custom_fn(3, |apt1, _| Some(1.0).filter(|_| apt1 == 1));
+        assert_eq!(stuck.nearest_neighbor_tour(0), None);
+
+        assert_eq!(distances.nearest_neighbor_tour(4), None);
+        assert_eq!(DistancesIdx::default().nearest_neighbor_tour(0), None);
+    }
+
+    #[test]
+    fn test_bottleneck_tour_trivial() {
+        assert_eq!(
+            DistancesIdx::default().bottleneck_tour_approx(),
+            Some((vec![], 0.0))
+        );
+        let two = points(&[(0.0, 0.0), (3.0, 4.0)]);
+        assert_eq!(two.bottleneck_tour_approx(), Some((vec![0, 1], 5.0)));
+        let disconnected =
+            DistancesIdx::from_custom_fn(3, |apt1, _| Some(1.0).filter(|_| apt1 == 1));
+        assert_eq!(disconnected.bottleneck_tour_approx(), None);
+    }
+}
@@ -1,3 +1,10 @@
+use crate::par;
+use crate::par::prelude::*;
+use std::ops::{Sub, SubAssign};
+
+const PAR_CHUNK_SIZE: usize = 1024;
+const BLOCK_SIZE: usize = 256;
+
 #[derive(Default, Debug, Clone, Copy, PartialEq, PartialOrd)]
 pub struct KahanAdder {
     sum: f64,
@@ -42,6 +49,108 @@ impl KahanAdder {
     }
 }
 
+impl Sub<f64> for KahanAdder {
+    type Output = Self;
+
+    fn sub(self, x: f64) -> Self {
+        self.push(-x)
+    }
+}
+
+impl SubAssign<f64> for KahanAdder {
+    fn sub_assign(&mut self, x: f64) {
+        self.push_mut(-x);
+    }
+}
+
 pub fn kahan_sum(it: impl Iterator<Item = f64>) -> f64 {
     it.fold(KahanAdder::default(), KahanAdder::push).result()
 }
+
+pub fn kahan_sum_par(arr: &[f64]) -> f64 {
+    let partial_sums: Vec<_> = arr
+        .par_chunks(PAR_CHUNK_SIZE)
+        .map(|chunk| kahan_sum(chunk.iter().copied()))
+        .collect();
+    kahan_sum(partial_sums.into_iter())
+}
+
+/// Sums blocks of `arr` separately, then sums the block sums
+pub fn block_kahan_sum(arr: &[f64]) -> f64 {
+    kahan_sum(
+        arr.chunks(BLOCK_SIZE)
+            .map(|block| kahan_sum(block.iter().copied())),
+    )
+}
+
+/// Splits `arr` into a chunk per thread, whole blocks each, and sums them with
+/// [`block_kahan_sum`] in parallel
+pub fn block_kahan_sum_parallel(arr: &[f64]) -> f64 {
+    let blocks = arr.len().div_ceil(BLOCK_SIZE);
+    let chunk_size = blocks.div_ceil(par::current_num_threads()).max(1) * BLOCK_SIZE;
+    let partial_sums: Vec<_> = arr.par_chunks(chunk_size).map(block_kahan_sum).collect();
+    kahan_sum(partial_sums.into_iter())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kahan_sum_par() {
+        const N: usize = 1_000_000;
+        let mut arr = vec![1e-16; N + 1];
+        arr[0] = 1.0;
+        let expected = 1.0 + N as f64 * 1e-16;
+
+        let naive: f64 = arr.iter().sum();
+        let parallel = kahan_sum_par(&arr);
+
+        assert_eq!(naive, 1.0);
+        assert!(
+            (parallel - expected).abs() < 1e-15,
+            "{parallel} is not close to {expected}"
+        );
+    }
+
+    #[test]
+    fn test_block_kahan_sum_parallel() {
+        const N: usize = 1_000_000;
+        // small terms vanish when naively added to the large one
+        let mut arr = vec![1e-16; N + 1];
+        arr[0] = 1.0;
+        let expected = 1.0 + N as f64 * 1e-16;
+
+        let sequential = block_kahan_sum(&arr);
+        let parallel = block_kahan_sum_parallel(&arr);
+        assert!(
+            (sequential - expected).abs() < 1e-15,
+            "{sequential} is not close to {expected}"
+        );
+        assert!(
+            (parallel - sequential).abs() < 1e-15,
+            "{parallel} is not close to {sequential}"
+        );
+        assert_eq!(block_kahan_sum_parallel(&[]), 0.0);
+        assert_eq!(block_kahan_sum_parallel(&[1.5]), 1.5);
+    }
+
+    #[test]
+    fn test_sub() {
+        const N: usize = 1_000_000;
+        let mut adder = KahanAdder::new(1.0);
+        for _ in 0..N {
+            adder -= 1e-16;
+        }
+        let expected = 1.0 - N as f64 * 1e-16;
+        assert!((adder.result() - expected).abs() < 1e-15);
+
+        let adder = KahanAdder::new(1.0).push(0.5) - 0.25;
+        assert_eq!(adder.result(), 1.25);
+    }
+
+    #[test]
+    fn test_kahan_sum_par_empty() {
+        assert_eq!(kahan_sum_par(&[]), 0.0);
+    }
+}
@@ -1,11 +1,24 @@
 pub mod aco;
+pub mod algorithms;
+pub mod asymmetric_aco;
+pub mod config;
 pub mod distance;
+pub mod export;
 pub mod graph;
+pub mod heuristic;
 pub mod kahan;
+pub mod local_search;
 pub mod math;
 pub mod model;
+mod par;
 pub mod parser;
 pub mod reusable_weighted_index;
 pub mod scaler;
+pub mod serializer;
+pub mod solver;
+pub mod sparse_graph;
+#[cfg(test)]
+mod test_util;
 pub mod types;
 pub mod util;
+pub mod validator;
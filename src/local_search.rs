@@ -0,0 +1,175 @@
+use crate::distance::DistancesIdx;
+
+// how many candidates are tried on each level of the search, its length limits the depth
+const LKH_BREADTH: [usize; 5] = [5, 3, 1, 1, 1];
+const MIN_GAIN: f64 = 1e-9;
+
+/// For each node, the `k` nearest reachable nodes with distances to them, sorted by distance
+pub fn candidate_lists(distances: &DistancesIdx, k: usize) -> Vec<Vec<(u32, f64)>> {
+    let size = distances.graph.size();
+    (0..size)
+        .map(|apt1| {
+            let mut candidates: Vec<_> = (0..size)
+                .filter_map(|apt2| distances.between(apt1, apt2).map(|dist| (apt2, dist)))
+                .collect();
+            candidates.sort_unstable_by(|(_, dist1), (_, dist2)| dist1.total_cmp(dist2));
+            candidates.truncate(k);
+            candidates
+        })
+        .collect()
+}
+
+/// Applies one improving Lin-Kernighan style sequential exchange.
+///
+/// Every tour edge `(t1, t2)` is removed in turn, turning the tour into a Hamiltonian path
+/// `t1 .. t2`. Then edges from the free end of the path to its candidates are added, each of
+/// them followed by removal of the edge which keeps the path Hamiltonian. The search goes
+/// deeper while the cumulative gain stays positive and stops as soon as closing the path
+/// gives a shorter tour. Returns `None` if no improving exchange is found.
+pub fn lkh_step(
+    cycle: &[u32],
+    distances: &DistancesIdx,
+    candidates: &[Vec<(u32, f64)>],
+) -> Option<Vec<u32>> {
+    let n = cycle.len();
+    if n < 4 {
+        return None;
+    }
+    for i in 0..n {
+        let forward: Vec<_> = (0..n).map(|k| cycle[(i + 1 + k) % n]).collect();
+        let backward: Vec<_> = (0..n).map(|k| cycle[(i + n - k) % n]).collect();
+        for path in [forward, backward] {
+            let Some(removed) = distances.between(path[0], path[n - 1]) else {
+                continue;
+            };
+            if let Some(improved) = lkh_search(&path, removed, 0, distances, candidates) {
+                return Some(improved);
+            }
+        }
+    }
+    None
+}
+
+fn lkh_search(
+    path: &[u32],
+    gain: f64,
+    depth: usize,
+    distances: &DistancesIdx,
+    candidates: &[Vec<(u32, f64)>],
+) -> Option<Vec<u32>> {
+    let n = path.len();
+    let last = path[n - 1];
+    let breadth = *LKH_BREADTH.get(depth)?;
+    let mut tried = 0;
+    for &(candidate, added) in &candidates[last as usize] {
+        if tried >= breadth {
+            break;
+        }
+        let partial_gain = gain - added;
+        // candidates are sorted, so the gain will only decrease further
+        if partial_gain <= MIN_GAIN {
+            break;
+        }
+        let Some(j) = path.iter().position(|&node| node == candidate) else {
+            continue;
+        };
+        // the candidate is already adjacent to the free end
+        if j + 2 >= n {
+            continue;
+        }
+        let Some(removed) = distances.between(candidate, path[j + 1]) else {
+            continue;
+        };
+        tried += 1;
+
+        let mut next_path = path.to_vec();
+        next_path[j + 1..].reverse();
+        let next_gain = partial_gain + removed;
+        if let Some(closing) = distances.between(next_path[n - 1], next_path[0]) {
+            if next_gain - closing > MIN_GAIN {
+                return Some(next_path);
+            }
+        }
+        if let Some(improved) = lkh_search(&next_path, next_gain, depth + 1, distances, candidates)
+        {
+            return Some(improved);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Airport, AirportIdx};
+    use crate::types::field::coord::Coord;
+    use rand::Rng;
+    use rand_pcg::Pcg64Mcg;
+    use std::collections::HashMap;
+
+    fn square() -> [Airport; 4] {
+        [(0.0, 0.0), (0.0, 0.1), (0.1, 0.1), (0.1, 0.0)].map(|(lat, lon)| Airport {
+            icao: format!("{lat}:{lon}"),
+            name: String::new(),
+            coord: Coord { lat, lon },
+        })
+    }
+
+    #[test]
+    fn test_candidate_lists() {
+        let airports = square();
+        let apt_idx = AirportIdx::new(&airports).unwrap();
+        let distances = DistancesIdx::from(&apt_idx, None, &HashMap::new());
+        let candidates = candidate_lists(&distances, 2);
+        assert_eq!(candidates.len(), 4);
+        let mut nearest: Vec<_> = candidates[0].iter().map(|&(apt, _)| apt).collect();
+        nearest.sort_unstable();
+        assert_eq!(nearest, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_lkh_step() {
+        let airports = square();
+        let apt_idx = AirportIdx::new(&airports).unwrap();
+        let distances = DistancesIdx::from(&apt_idx, None, &HashMap::new());
+        let candidates = candidate_lists(&distances, 3);
+
+        let crossed = [0, 2, 1, 3];
+        let improved = lkh_step(&crossed, &distances, &candidates).unwrap();
+        assert!(
+            distances.tour_distance(&improved).unwrap()
+                < distances.tour_distance(&crossed).unwrap()
+        );
+        assert_eq!(lkh_step(&improved, &distances, &candidates), None);
+        assert_eq!(lkh_step(&[0, 1, 2, 3], &distances, &candidates), None);
+    }
+
+    #[test]
+    fn test_lkh_step_converges() {
+        let mut rng = Pcg64Mcg::new(42);
+        let airports: Vec<_> = (0..30)
+            .map(|i| Airport {
+                icao: i.to_string(),
+                name: String::new(),
+                coord: Coord {
+                    lat: rng.gen_range(-0.5..0.5),
+                    lon: rng.gen_range(-0.5..0.5),
+                },
+            })
+            .collect();
+        let apt_idx = AirportIdx::new(&airports).unwrap();
+        let distances = DistancesIdx::from(&apt_idx, None, &HashMap::new());
+        let candidates = candidate_lists(&distances, 5);
+
+        let mut cycle: Vec<_> = (0..30).collect();
+        let mut dist = distances.tour_distance(&cycle).unwrap();
+        while let Some(improved) = lkh_step(&cycle, &distances, &candidates) {
+            let mut sorted = improved.clone();
+            sorted.sort_unstable();
+            assert_eq!(sorted, (0..30).collect::<Vec<_>>());
+            let improved_dist = distances.tour_distance(&improved).unwrap();
+            assert!(improved_dist < dist);
+            (cycle, dist) = (improved, improved_dist);
+        }
+    }
+}
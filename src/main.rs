@@ -1,5 +1,5 @@
 use ab_glyph::{FontRef, PxScale};
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use clap_stdin::FileOrStdin;
 use image::buffer::ConvertBuffer;
 use image::{RgbImage, Rgba, RgbaImage};
@@ -7,18 +7,100 @@ use imageproc::drawing::{
     draw_antialiased_line_segment_mut, draw_hollow_circle_mut, draw_text_mut,
 };
 use imageproc::pixelops::interpolate;
+use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, HashSet};
-use std::io::{BufReader, BufWriter, Read, Write};
-use std::path::PathBuf;
-use std::{fs, io};
-use tsp::aco::Aco;
-use tsp::distance::DistancesIdx;
-use tsp::model::{Airport, AirportIdx};
-use tsp::parser::file::parse_airport_primary_records;
-use tsp::scaler::Scaler;
-use tsp::types::field::coord::{Coord, LatitudeHemisphere, LongitudeHemisphere};
+use std::fmt::Write as _;
+use std::io::{BufReader, BufWriter, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use std::{fs, io, process};
+use tsp::aco::{AcoMode, AcoProgress, AcoResult};
+use tsp::config::{AcoConfig, ConfigError};
+use tsp::distance::{DistGraph, DistancesIdx};
+use tsp::export::geojson::export_geojson;
+use tsp::export::kml::export_kml;
+use tsp::graph::{connected_components, largest_connected_component_indices, GraphIdx};
+use tsp::math::{great_circle_arc, DistanceFormula};
+use tsp::model::{flight_time, total_flight_time, Airport, AirportIdx};
+use tsp::parser::csv::parse_airports_csv;
+use tsp::parser::file::{parse_airport_primary_records_checked, parse_vhf_navaid_records};
+use tsp::parser::openflights::parse_openflights_csv;
+use tsp::parser::tsplib::from_tsplib_matrix;
+use tsp::scaler::{Scaler, ScalerProjection};
+use tsp::serializer::file::tour_to_arinc424;
+use tsp::serializer::openflights::write_openflights_csv;
+use tsp::solver::{validate_path, validate_tour, AcoSolver, NearestNeighborSolver, TspSolver};
+use tsp::types::field::coord::Coord;
+use tsp::types::field::icao_region::IcaoRegion;
+use tsp::types::record::navaid::VhfNavaidRecord;
 use tsp::types::record::AirportPrimaryRecord;
-use tsp::util::{cycling, trim_0d};
+use tsp::util::{csv_quote, cycling, json_escape, trim_0d, xml_escape};
+use tsp::validator::{validate_recommended_navaids, ValidationWarning};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum InputFormat {
+    /// ARINC 424 airport primary records
+    Arinc424,
+    /// OpenFlights airports.dat CSV
+    OpenFlights,
+    /// CSV with icao,name,lat,lon header, coordinates in decimal degrees
+    Csv,
+    /// TSPLib instance with EUC_2D or EXPLICIT edge weights, solved over its nodes
+    #[value(name = "tsplib")]
+    TspLib,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// Human readable lines
+    Text,
+    /// JSON object with total distance and airports
+    Json,
+    /// CSV rows of airports
+    Csv,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum DistanceFormulaArg {
+    /// Great circle on the sphere
+    Haversine,
+    /// Geodesic on the WGS84 ellipsoid
+    Vincenty,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum SolverArg {
+    /// Ant colony optimization
+    Aco,
+    /// Greedy nearest neighbor tour, fast baseline
+    NearestNeighbor,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum ProjectionArg {
+    /// Latitude and longitude are linear
+    Equirectangular,
+    /// Web Mercator
+    Mercator,
+}
+
+impl From<ProjectionArg> for ScalerProjection {
+    fn from(value: ProjectionArg) -> Self {
+        match value {
+            ProjectionArg::Equirectangular => Self::Equirectangular,
+            ProjectionArg::Mercator => Self::Mercator,
+        }
+    }
+}
+
+impl From<DistanceFormulaArg> for DistanceFormula {
+    fn from(value: DistanceFormulaArg) -> Self {
+        match value {
+            DistanceFormulaArg::Haversine => Self::Haversine,
+            DistanceFormulaArg::Vincenty => Self::Vincenty,
+        }
+    }
+}
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
@@ -26,49 +108,161 @@ struct Args {
     /// The input file. With no input file, or when input file is -, read standard input
     #[clap(default_value = "-")]
     input: FileOrStdin,
-    /// Output file. If omitted, write to standard output
-    #[clap(short, long)]
+    /// Input file format
+    #[clap(long, value_enum, default_value_t = InputFormat::Arinc424)]
+    input_format: InputFormat,
+    /// Formula of distances between airports
+    #[clap(long, value_enum, default_value_t = DistanceFormulaArg::Haversine)]
+    distance_formula: DistanceFormulaArg,
+    /// Output file. If omitted, write to standard output, which is allowed for the text format
+    /// only as it is shared with progress messages
+    #[clap(
+        short,
+        long,
+        required_if_eq_any([("output_format", "json"), ("output_format", "csv")])
+    )]
     output: Option<PathBuf>,
     /// Output airport primary records
     #[clap(short, long)]
     print_aps: bool,
+    /// Format of output airport primary records
+    #[clap(long, value_enum, default_value_t = OutputFormat::Text)]
+    output_format: OutputFormat,
     /// Filter file
     #[clap(short, long)]
     filter: Option<PathBuf>,
-    /// Number of ants
-    #[clap(default_value = "50", short, long)]
-    ants: u32,
-    /// Number of iterations
-    #[clap(default_value = "100", short, long)]
-    iterations: u32,
-    /// Evaporation rate (from 0 to 1)
-    #[clap(default_value = "0.1", short, long)]
-    evaporation: f64,
-    /// Alpha
-    #[clap(default_value = "0.9", long)]
-    alpha: f64,
-    /// Beta
-    #[clap(default_value = "1.5", long)]
-    beta: f64,
+    /// TOML file with ACO parameters, command line flags override it
+    #[cfg(feature = "serde")]
+    #[clap(long)]
+    config: Option<PathBuf>,
+    /// Algorithm of the tour
+    #[clap(long, value_enum, default_value_t = SolverArg::Aco)]
+    solver: SolverArg,
+    /// Number of ants [default: 50]
+    #[clap(short, long)]
+    ants: Option<u32>,
+    /// Number of iterations [default: 100]
+    #[clap(short, long)]
+    iterations: Option<u32>,
+    /// Evaporation rate (from 0 to 1) [default: 0.1]
+    #[clap(short, long)]
+    evaporation: Option<f64>,
+    /// Alpha [default: 0.9]
+    #[clap(long)]
+    alpha: Option<f64>,
+    /// Beta [default: 1.5]
+    #[clap(long)]
+    beta: Option<f64>,
+    /// Number of elitist ants reinforcing the best cycle [default: 0]
+    #[clap(long)]
+    elite_ants: Option<u32>,
+    /// Stop after this many iterations without improvement
+    #[clap(long)]
+    stagnation_limit: Option<u32>,
+    /// Seed for reproducible runs
+    #[clap(long)]
+    seed: Option<u64>,
+    /// Number of nearest neighbors ants choose from while they are not visited
+    #[clap(long)]
+    candidates: Option<u32>,
     /// Show unfiltered
     #[clap(short, long)]
     unfiltered: bool,
     /// Output images directory
     #[clap(long)]
     images: Option<PathBuf>,
+    /// Also write the image in SVG format to the images directory
+    #[clap(long, requires = "images")]
+    svg: bool,
+    /// Draw pheromone trails of the last iteration under the tour on the image
+    #[clap(long, requires = "images", conflicts_with = "bottleneck")]
+    pheromone_overlay: bool,
+    /// Write the best cycle of every iteration as frames of animated GIF to file
+    #[clap(long, conflicts_with = "bottleneck")]
+    animated_gif: Option<PathBuf>,
+    /// Frame rate of animated GIF
+    #[clap(long, default_value = "10", value_parser = clap::value_parser!(u32).range(1..=100))]
+    gif_fps: u32,
+    /// Map projection of images
+    #[clap(long, value_enum, default_value_t = ProjectionArg::Equirectangular)]
+    projection: ProjectionArg,
     /// Minimal allowable distance
     #[clap(short, long)]
     min_dist: Option<f64>,
     /// Allow distances between ICAO codes below min_dist, in format <ICAO Code>-<ICAO Code>,...
     #[clap(long, num_args = 1.., value_delimiter = ',')]
     except: Vec<String>,
+    /// Allow distances between ICAO codes below min_dist, read from file with one <ICAO Code>-<ICAO Code> pair per line
+    #[clap(long)]
+    except_file: Option<PathBuf>,
     /// Optimal distance
     #[clap(long)]
     opt: Option<f64>,
+    /// Keep only airports from ICAO regions with these prefixes, in format <Prefix>,...
+    #[clap(long, num_args = 1.., value_delimiter = ',')]
+    filter_region: Vec<char>,
+    /// Keep only airports with these customer area codes, in format <Area code>,...
+    #[clap(long, num_args = 1.., value_delimiter = ',')]
+    area_codes: Vec<String>,
+    /// Keep only airports within bounding box, in format <Lat max>,<Lon min>,<Lat min>,<Lon max> in
+    /// decimal degrees
+    #[clap(long, value_delimiter = ',', allow_negative_numbers = true)]
+    bbox: Vec<f64>,
+    /// Write selected airports in OpenFlights CSV format to file
+    #[clap(long)]
+    export_airports_csv: Option<PathBuf>,
+    /// Write airport primary records of the selected cycle in ARINC 424 format to file
+    #[clap(long)]
+    arinc_output: Option<PathBuf>,
+    /// Write the selected cycle and airports in GeoJSON format to file
+    #[clap(long)]
+    geojson: Option<PathBuf>,
+    /// Write the selected cycle and airports in KML format to file
+    #[clap(long)]
+    kml: Option<PathBuf>,
+    /// Check records against cross-field rules and VHF navaids of the input to stderr and exclude
+    /// failed ones
+    #[clap(long)]
+    validate: bool,
+    /// Abort on the first record failed validation
+    #[clap(long)]
+    strict_validation: bool,
+    /// Print how graph density depends on minimal distance
+    #[clap(long)]
+    analyze_density: bool,
+    /// Keep only the largest connected component of airports if they are disconnected
+    #[clap(long)]
+    auto_reduce: bool,
+    /// Print weight of the minimum spanning tree, a lower bound of the tour length
+    #[clap(long)]
+    mst_bound: bool,
+    /// Minimize the longest leg instead of the total length, approximately
+    #[clap(long)]
+    bottleneck: bool,
+    /// Find path not returning to the first airport
+    #[clap(long, conflicts_with = "bottleneck")]
+    open: bool,
+    /// ICAO code of the first airport of the path, implies --open
+    #[clap(long, conflicts_with = "bottleneck")]
+    start_icao: Option<String>,
+    /// ICAO code of the last airport of the path, implies --open
+    #[clap(long, conflicts_with = "bottleneck")]
+    end_icao: Option<String>,
+    /// Check that the selected cycle visits every airport once by feasible legs, always done in
+    /// debug builds
+    #[clap(long)]
+    validate_tour: bool,
+    /// Cruise speed in knots to report total flight time of the selected cycle
+    #[clap(long, value_parser = parse_cruise_speed)]
+    cruise_speed: Option<f64>,
 }
 
 fn main() {
     let args = Args::parse();
+    let config = aco_config(&args).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1)
+    });
     let buf = {
         let reader = args.input.into_reader().unwrap();
         let mut readable = BufReader::new(reader);
@@ -78,6 +272,31 @@ fn main() {
     };
     let buf = &buf[..];
 
+    if args.input_format == InputFormat::TspLib {
+        assert!(
+            args.arinc_output.is_none()
+                && !args.validate
+                && !args.strict_validation
+                && args.area_codes.is_empty()
+                && args.start_icao.is_none()
+                && args.end_icao.is_none(),
+            "ARINC 424 output, validation, area codes filter and endpoint airports require \
+            airports input"
+        );
+        let distances = DistancesIdx {
+            graph: DistGraph::Dense(from_tsplib_matrix(buf).unwrap_or_else(|e| {
+                eprintln!("Invalid TSPLib instance: {e}");
+                process::exit(1)
+            })),
+        };
+        let (tour, dist) = solve_tsplib(&distances, &config, args.solver, args.opt, args.open);
+        // TSPLib numbers nodes from 1
+        let nodes: Vec<_> = tour.iter().map(|&node| node + 1).collect();
+        println!("Selected cycle {nodes:?}");
+        println!("Total lengths: {dist:.05}");
+        return;
+    }
+
     let hs = if let Some(filter) = args.filter {
         let mut items = vec![];
         BufReader::new(fs::File::open(filter).unwrap())
@@ -95,43 +314,512 @@ fn main() {
         None
     };
 
-    let recs: Vec<_> = parse_airport_primary_records(buf)
-        .filter(|rec| {
-            hs.as_ref()
-                .map_or(true, |hs| hs.contains(rec.icao_identifier))
+    let regions: HashSet<_> = args
+        .filter_region
+        .iter()
+        .map(|&c| {
+            IcaoRegion::from_prefix(c).unwrap_or_else(|| panic!("Unknown ICAO region prefix {c}"))
         })
         .collect();
 
-    let airports: Vec<_> = recs.iter().map(Airport::from).collect();
-    let apt_idx = AirportIdx::new(&airports).unwrap();
-    let excepts = parse_excepts(&args.except);
-    let distances = DistancesIdx::from(&apt_idx, args.min_dist, &excepts);
-
-    let aco = Aco::new(&distances, None, None, args.opt);
-    let (aco, dist) = aco.aco(
-        args.iterations,
-        args.ants,
-        1.0 - args.evaporation,
-        args.alpha,
-        args.beta,
+    let bbox = match args.bbox[..] {
+        [] => None,
+        [lat_max, lon_min, lat_min, lon_max] => Some(
+            Coord::try_from((lat_max, lon_min))
+                .and_then(|top_left| {
+                    Coord::try_from((lat_min, lon_max)).map(|bottom_right| (top_left, bottom_right))
+                })
+                .unwrap_or_else(|_| panic!("Bounding box {:?} is out of range", args.bbox)),
+        ),
+        _ => panic!("Bounding box {:?} should have 4 values", args.bbox),
+    };
+
+    let accepts = |icao: &str, region: Option<IcaoRegion>, coord: Coord| {
+        hs.as_ref().is_none_or(|hs| hs.contains(icao))
+            && (regions.is_empty() || region.is_some_and(|region| regions.contains(&region)))
+            && bbox.is_none_or(|(top_left, bottom_right)| {
+                coord.in_bounding_box(top_left, bottom_right)
+            })
+    };
+
+    let areas: HashSet<_> = args.area_codes.iter().map(String::as_str).collect();
+
+    let accepts_rec = |rec: &AirportPrimaryRecord| {
+        accepts(
+            rec.icao_identifier,
+            rec.country_code(),
+            (
+                &rec.airport_reference_point_latitude,
+                &rec.airport_reference_point_longitude,
+            )
+                .into(),
+        )
+    };
+
+    let recs: Vec<_> = match args.input_format {
+        InputFormat::Arinc424 => parse_airport_primary_records_checked(buf)
+            .filter_map(|rec| {
+                rec.inspect_err(|e| eprintln!("Skipping airport primary record: {e}"))
+                    .ok()
+            })
+            .filter(|rec| areas.is_empty() || areas.contains(rec.customer_area_code))
+            .filter(accepts_rec)
+            .collect(),
+        InputFormat::OpenFlights | InputFormat::Csv => {
+            assert!(
+                args.arinc_output.is_none()
+                    && !args.validate
+                    && !args.strict_validation
+                    && areas.is_empty(),
+                "ARINC 424 output, validation and area codes filter require ARINC 424 input"
+            );
+            vec![]
+        }
+        InputFormat::TspLib => unreachable!("TSPLib instances are solved without airports"),
+    };
+
+    let recs = if args.validate || args.strict_validation {
+        let navaids: Vec<_> = parse_vhf_navaid_records(buf).collect();
+        validate_recs(recs, &navaids, args.strict_validation)
+    } else {
+        recs
+    };
+
+    let mut airports = vec![];
+    let apt_idx = match args.input_format {
+        InputFormat::Arinc424 => {
+            AirportIdx::from_iter(recs.iter().map(Airport::from), &mut airports)
+        }
+        InputFormat::OpenFlights => AirportIdx::from_iter(
+            parse_openflights_csv(buf)
+                .map(|apt| {
+                    apt.unwrap_or_else(|e| {
+                        eprintln!("Invalid OpenFlights record: {e}");
+                        process::exit(1)
+                    })
+                })
+                .filter(|apt| {
+                    accepts(
+                        &apt.icao,
+                        apt.icao.chars().next().and_then(IcaoRegion::from_prefix),
+                        apt.coord,
+                    )
+                }),
+            &mut airports,
+        ),
+        InputFormat::Csv => AirportIdx::from_iter(
+            parse_airports_csv(buf)
+                .unwrap_or_else(|e| {
+                    eprintln!("Invalid airports CSV: {e}");
+                    process::exit(1)
+                })
+                .into_iter()
+                .filter(|apt| {
+                    accepts(
+                        &apt.icao,
+                        apt.icao.chars().next().and_then(IcaoRegion::from_prefix),
+                        apt.coord,
+                    )
+                }),
+            &mut airports,
+        ),
+        InputFormat::TspLib => unreachable!("TSPLib instances are solved without airports"),
+    }
+    .unwrap();
+    if let Some(export_airports_csv) = args.export_airports_csv {
+        let mut writable = BufWriter::new(fs::File::create(export_airports_csv).unwrap());
+        write_openflights_csv(&mut writable, apt_idx.aps).unwrap();
+    }
+    let except_file = args
+        .except_file
+        .map(|except_file| fs::read_to_string(except_file).unwrap())
+        .unwrap_or_default();
+    let excepts = parse_excepts(
+        args.except.iter().map(String::as_str).chain(
+            except_file
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty()),
+        ),
+    )
+    .unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1)
+    });
+    if args.analyze_density {
+        let formula = DistanceFormula::from(args.distance_formula);
+        let base_distances = GraphIdx::new(&apt_idx, |apt1, apt2| {
+            formula.distance(apt1.coord, apt2.coord)
+        });
+        println!("Threshold, km\tDensity");
+        for (threshold, density) in base_distances.density_curve(DENSITY_CURVE_POINTS) {
+            println!("{threshold:.01}\t{density:.05}");
+        }
+    }
+    let distances = DistancesIdx::from_with_formula(
+        &apt_idx,
+        args.min_dist,
+        &excepts,
+        args.distance_formula.into(),
     );
+
+    let components = connected_components(&distances.graph.to_dense());
+    let components_count = components.iter().max().map_or(0, |&max| max + 1);
+    if components_count > 1 {
+        eprintln!(
+            "Warning: airports form {components_count} isolated components, consider lowering \
+            minimal distance or --auto-reduce:"
+        );
+        for component in 0..components_count {
+            let icaos: Vec<_> = (0..apt_idx.aps.len())
+                .filter(|&apt| components[apt] == component)
+                .map(|apt| apt_idx.aps[apt].icao.as_str())
+                .collect();
+            eprintln!("  {}", icaos.join(", "));
+        }
+    }
+
+    let mut reduced_airports = vec![];
+    let reduced_idx;
+    let (recs, apt_idx, distances) = if args.auto_reduce && components_count > 1 {
+        let nodes = largest_connected_component_indices(&distances.graph.to_dense());
+        eprintln!(
+            "Reducing to the largest component of {} airports",
+            nodes.len()
+        );
+        let recs = if recs.is_empty() {
+            recs
+        } else {
+            nodes.iter().map(|&apt| recs[apt as usize]).collect()
+        };
+        reduced_idx = AirportIdx::from_iter(
+            nodes.iter().map(|&apt| apt_idx.aps[apt as usize].clone()),
+            &mut reduced_airports,
+        )
+        .unwrap();
+        let distances = DistancesIdx {
+            graph: distances
+                .graph
+                .subgraph(&nodes)
+                .unwrap_or_else(|| unreachable!("Nodes are unique and in range")),
+        };
+        (recs, &reduced_idx, distances)
+    } else {
+        (recs, &apt_idx, distances)
+    };
+
+    if args.mst_bound {
+        println!(
+            "MST lower bound: {:.02}",
+            distances.graph.to_dense().kruskal_mst_weight()
+        );
+    }
+
+    let (start, end) = path_endpoints(
+        apt_idx,
+        args.start_icao.as_deref(),
+        args.end_icao.as_deref(),
+    )
+    .unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1)
+    });
+    let open = args.open || start.is_some() || end.is_some();
+
+    let (aco, dist, intensities) = if args.bottleneck {
+        let (cycle, longest_leg) = distances.bottleneck_tour_approx().unwrap_or_else(|| {
+            eprintln!("No feasible bottleneck tour found");
+            process::exit(1)
+        });
+        println!("Longest leg: {longest_leg:.05}");
+        let dist = distances.tour_distance(&cycle).unwrap_or(f64::NAN);
+        (cycle, dist, None)
+    } else if args.solver == SolverArg::NearestNeighbor {
+        if end.is_some() {
+            eprintln!("Nearest neighbor solver does not support the end airport");
+            process::exit(1)
+        }
+        let (cycle, dist) = nearest_neighbor(&distances, start.unwrap_or_default(), open);
+        (cycle, dist, None)
+    } else {
+        let solver = AcoSolver {
+            capture_frames: args.animated_gif.is_some(),
+            ..aco_solver(&config, args.opt, open, start, end)
+        };
+        let progress = io::stderr()
+            .is_terminal()
+            .then(|| ProgressBar::new(config.iterations as u64));
+        let (
+            AcoResult {
+                tour,
+                dist,
+                converged_at,
+                frames,
+            },
+            intensities,
+        ) = solver.solve_with_intensities(
+            &distances,
+            progress
+                .as_ref()
+                .map(|progress| progress as &dyn AcoProgress),
+        );
+        if let Some(progress) = progress {
+            progress.finish();
+        }
+        println!("Converged at iteration: {converged_at}");
+        if let Some(animated_gif) = &args.animated_gif {
+            draw_animated_gif(
+                animated_gif,
+                &frames,
+                apt_idx,
+                GIF_WIDTH,
+                GIF_HEIGHT,
+                args.gif_fps,
+                args.projection.into(),
+                open,
+            );
+        }
+        (tour, dist, args.pheromone_overlay.then_some(intensities))
+    };
+    if cfg!(debug_assertions) || args.validate_tour {
+        let validated = if open {
+            validate_path(&aco, &distances)
+        } else {
+            validate_tour(&aco, &distances)
+        };
+        if let Err(e) = validated {
+            eprintln!("Invalid selected cycle: {e}");
+            if args.validate_tour {
+                process::exit(1);
+            }
+        }
+    }
     println!("Selected cycle {aco:?}");
     println!("Total nodes: {}", aco.len());
+    if let Some(cruise_speed) = args.cruise_speed {
+        let flight_time = if open {
+            flight_time(dist, cruise_speed)
+        } else {
+            total_flight_time(&aco, &distances, cruise_speed)
+        };
+        let minutes = flight_time.as_secs() / 60;
+        println!("Total flight time: {}h {:02}m", minutes / 60, minutes % 60);
+    }
 
     if args.print_aps {
-        print_aps(&recs, &distances, &aco, dist, args.output);
+        print_aps(
+            apt_idx.aps,
+            &recs,
+            &distances,
+            &aco,
+            dist,
+            open,
+            args.output,
+            args.output_format,
+        );
+    }
+
+    if let Some(arinc_output) = args.arinc_output {
+        fs::write(arinc_output, tour_to_arinc424(&recs, &aco)).unwrap();
+    }
+
+    if let Some(geojson) = args.geojson {
+        fs::write(
+            geojson,
+            export_geojson(apt_idx, &recs, &aco, open, &distances),
+        )
+        .unwrap();
+    }
+
+    if let Some(kml) = args.kml {
+        fs::write(kml, export_kml(apt_idx, &recs, &aco, open)).unwrap();
     }
 
     if let Some(images_dir) = args.images {
-        draw_images(images_dir, &airports, &apt_idx, &aco, args.unfiltered);
+        if args.svg {
+            check_images_dir(&images_dir);
+            fs::write(
+                images_dir.join("aco.svg"),
+                draw_svg(
+                    apt_idx.aps,
+                    apt_idx,
+                    &aco,
+                    open,
+                    args.unfiltered,
+                    args.projection.into(),
+                ),
+            )
+            .unwrap();
+        }
+        draw_images(
+            images_dir,
+            apt_idx.aps,
+            apt_idx,
+            &aco,
+            open,
+            args.unfiltered,
+            args.projection.into(),
+            intensities.as_ref(),
+        );
+    }
+}
+
+const PROGRESS_SPINNER: [char; 8] = ['⠁', '⠂', '⠄', '⡀', '⢀', '⠠', '⠐', '⠈'];
+
+/// Redraws `{spinner} [{elapsed}] {pos}/{len} {msg}` line on standard error
+struct ProgressBar {
+    len: u64,
+    pos: Cell<u64>,
+    msg: RefCell<String>,
+    started: Instant,
+}
+
+impl ProgressBar {
+    fn new(len: u64) -> Self {
+        Self {
+            len,
+            pos: Cell::new(0),
+            msg: RefCell::new(String::new()),
+            started: Instant::now(),
+        }
+    }
+
+    fn draw(&self) {
+        let pos = self.pos.get();
+        let elapsed = self.started.elapsed().as_secs();
+        eprint!(
+            "\r{} [{:02}:{:02}:{:02}] {pos}/{} {}\x1b[K",
+            PROGRESS_SPINNER[pos as usize % PROGRESS_SPINNER.len()],
+            elapsed / 3600,
+            elapsed / 60 % 60,
+            elapsed % 60,
+            self.len,
+            self.msg.borrow()
+        );
+    }
+
+    fn finish(&self) {
+        self.draw();
+        eprintln!();
+    }
+}
+
+impl AcoProgress for ProgressBar {
+    fn inc(&self, delta: u64) {
+        self.pos.set(self.pos.get() + delta);
+        self.draw();
+    }
+
+    fn set_message(&self, msg: String) {
+        *self.msg.borrow_mut() = msg;
+        self.draw();
+    }
+}
+
+/// Tour over nodes of TSPLib instance, airport specific options do not apply
+fn solve_tsplib(
+    distances: &DistancesIdx,
+    config: &AcoConfig,
+    solver: SolverArg,
+    opt: Option<f64>,
+    open: bool,
+) -> (Vec<u32>, f64) {
+    match solver {
+        SolverArg::NearestNeighbor => nearest_neighbor(distances, 0, open),
+        SolverArg::Aco => aco_solver(config, opt, open, None, None)
+            .solve(distances)
+            .unwrap_or_else(|e| panic!("{e}")),
+    }
+}
+
+/// Nearest neighbor tour from `start`, its length lacks the closing leg if `open`
+fn nearest_neighbor(distances: &DistancesIdx, start: u32, open: bool) -> (Vec<u32>, f64) {
+    let (tour, dist) = NearestNeighborSolver { start }
+        .solve(distances)
+        .unwrap_or_else(|e| panic!("{e}"));
+    let dist = if open {
+        distances.path_distance(&tour).unwrap_or(f64::NAN)
+    } else {
+        dist
+    };
+    (tour, dist)
+}
+
+/// Colony of every input format, open tours go from `start` to `end` if they are set
+fn aco_solver(
+    config: &AcoConfig,
+    opt: Option<f64>,
+    open: bool,
+    start: Option<u32>,
+    end: Option<u32>,
+) -> AcoSolver {
+    AcoSolver {
+        config: config.clone(),
+        opt_dist: opt,
+        mode: if open {
+            AcoMode::Open { start, end }
+        } else {
+            AcoMode::Closed
+        },
+        ..AcoSolver::default()
     }
 }
 
-fn parse_excepts(arg: &[String]) -> HashMap<&str, HashSet<&str>> {
+// Recommended navaids are checked only if the input has VHF navaid records
+fn validate_recs<'a>(
+    recs: Vec<AirportPrimaryRecord<'a>>,
+    navaids: &[VhfNavaidRecord],
+    strict_validation: bool,
+) -> Vec<AirportPrimaryRecord<'a>> {
+    let mut navaid_warnings: HashMap<_, Vec<_>> = HashMap::new();
+    if !navaids.is_empty() {
+        for warning in validate_recommended_navaids(&recs, navaids) {
+            if let ValidationWarning::UnknownRecommendedNavaid { airport } = warning {
+                navaid_warnings.entry(airport).or_default().push(warning);
+            }
+        }
+    }
+    let mut failed = 0;
+    let recs: Vec<_> = recs
+        .into_iter()
+        .enumerate()
+        .filter(|(airport, rec)| {
+            let mut warnings = rec.validate();
+            warnings.extend(navaid_warnings.remove(airport).unwrap_or_default());
+            for warning in &warnings {
+                eprintln!("Validation warning for {}: {warning}", rec.icao_identifier);
+            }
+            if warnings.is_empty() {
+                true
+            } else if strict_validation {
+                panic!("Record {} failed validation", rec.icao_identifier);
+            } else {
+                failed += 1;
+                false
+            }
+        })
+        .map(|(_, rec)| rec)
+        .collect();
+    eprintln!("Records failed validation: {failed}");
+    recs
+}
+
+fn parse_cruise_speed(s: &str) -> Result<f64, String> {
+    let speed = s.parse::<f64>().map_err(|e| e.to_string())?;
+    if speed > 0.0 {
+        Ok(speed)
+    } else {
+        Err(format!("cruise speed must be positive, got {s}"))
+    }
+}
+
+fn parse_excepts<'a>(
+    pairs: impl IntoIterator<Item = &'a str>,
+) -> Result<HashMap<&'a str, HashSet<&'a str>>, String> {
     let mut ret: HashMap<_, HashSet<_>> = HashMap::new();
 
-    for pair in arg {
-        let apt_pair = AptPair::from_str(pair).unwrap();
+    for pair in pairs {
+        let apt_pair = AptPair::from_str(pair)?;
         ret.entry(apt_pair.0)
             .and_modify(|s| {
                 s.insert(apt_pair.1);
@@ -139,31 +827,62 @@ fn parse_excepts(arg: &[String]) -> HashMap<&str, HashSet<&str>> {
             .or_insert_with(|| HashSet::from([apt_pair.1]));
     }
 
-    ret
+    Ok(ret)
 }
 
+#[derive(Debug, PartialEq)]
 struct AptPair<'a>(&'a str, &'a str);
 
 impl<'a> AptPair<'a> {
     fn from_str(s: &'a str) -> Result<AptPair<'a>, String> {
-        let (a, b) = s
-            .trim()
-            .split_once('-')
-            .ok_or("Invalid format in except, expected ICAO-ICAO")?;
-        Ok(AptPair(a, b))
+        let mut icaos = s.trim().split('-');
+        match (icaos.next(), icaos.next(), icaos.next()) {
+            (Some(a), Some(b), None) if !a.is_empty() && !b.is_empty() => Ok(AptPair(a, b)),
+            _ => Err(format!(
+                "Invalid except entry {s:?}: expected ICAO-ICAO format"
+            )),
+        }
     }
 }
 
+/// Indices of the first and the last airports of the path
+fn path_endpoints(
+    apt_idx: &AirportIdx,
+    start_icao: Option<&str>,
+    end_icao: Option<&str>,
+) -> Result<(Option<u32>, Option<u32>), String> {
+    let idx = |icao: Option<&str>| {
+        icao.map(|icao| {
+            apt_idx
+                .idx_by_icao
+                .get(icao)
+                .copied()
+                .ok_or_else(|| format!("Airport {icao} is not among selected airports"))
+        })
+        .transpose()
+    };
+    let (start, end) = (idx(start_icao)?, idx(end_icao)?);
+    if start.is_some() && start == end {
+        return Err(format!(
+            "Path should start and end at different airports, got {} for both",
+            start_icao.unwrap_or_default()
+        ));
+    }
+    Ok((start, end))
+}
+
+const DENSITY_CURVE_POINTS: usize = 21;
+
 const IMG_WIDTH: u32 = 1920 * 2;
 const IMG_HEIGHT: u32 = 1080 * 2;
+const IMG_MARGIN_FRAC: f64 = 0.05;
+const IMG_ARC_SEGMENTS: usize = 16;
+const PHEROMONE_OVERLAY_DECADES: f64 = 3.0;
+const GIF_WIDTH: u32 = 960;
+const GIF_HEIGHT: u32 = 540;
+const GIF_QUANTIZATION_SPEED: i32 = 10;
 
-fn draw_images(
-    mut images_dir: PathBuf,
-    apts: &[Airport],
-    apt_idx: &AirportIdx,
-    aco: &[u32],
-    draw_unfiltered: bool,
-) {
+fn check_images_dir(images_dir: &Path) {
     match images_dir.try_exists() {
         Ok(true) if images_dir.is_dir() => {}
         Ok(true) => {
@@ -176,91 +895,254 @@ fn draw_images(
             panic!("Images directory {images_dir:?} does not exist: {e:?}");
         }
     }
+}
 
-    let mut img_buf = RgbaImage::from_pixel(IMG_WIDTH, IMG_HEIGHT, Rgba([0xFF, 0xFF, 0xFF, 0xFF]));
+fn image_scaler(
+    apt_idx: &AirportIdx,
+    width: u32,
+    height: u32,
+    projection: ScalerProjection,
+) -> Scaler {
     let (top_left, bottom_right) = apt_idx
-        .aps
-        .iter()
-        .map(|apt| (apt.coord, apt.coord))
-        .reduce(|(acc_tl, acc_br), (apt_tl, apt_br)| {
-            (
-                Coord {
-                    lat: acc_tl.lat.max(apt_tl.lat),
-                    lon: acc_tl.lon.min(apt_tl.lon),
-                },
-                Coord {
-                    lat: acc_br.lat.min(apt_br.lat),
-                    lon: acc_br.lon.max(apt_br.lon),
-                },
-            )
-        })
+        .bounding_box_with_margin_frac(IMG_MARGIN_FRAC)
         .unwrap();
-    let margin = Coord {
-        lon: (bottom_right.lon - top_left.lon).abs() * 0.05,
-        lat: (bottom_right.lat - top_left.lat).abs() * 0.05,
-    };
-    let (top_left, bottom_right) = (
-        Coord {
-            lat: top_left.lat + margin.lat,
-            lon: top_left.lon - margin.lon,
-        },
-        Coord {
-            lat: bottom_right.lat - margin.lat,
-            lon: bottom_right.lon + margin.lon,
-        },
-    );
-    let scaler = Scaler::new(top_left, bottom_right, IMG_WIDTH, IMG_HEIGHT);
-    images_dir.push("aco.png");
+    Scaler::new_with_projection(top_left, bottom_right, width, height, projection)
+}
 
-    for apt in if draw_unfiltered { apts } else { apt_idx.aps } {
-        draw_hollow_circle_mut(
-            &mut img_buf,
-            scaler.map(apt.coord),
-            5,
-            Rgba([0xFF, 0, 0, 0xFF]),
-        );
-    }
-    for (&aco1, &aco2) in cycling(aco) {
-        draw_antialiased_line_segment_mut(
-            &mut img_buf,
-            scaler.map(apt_idx.aps[aco1 as usize].coord),
-            scaler.map(apt_idx.aps[aco2 as usize].coord),
-            Rgba([0, 0, 0xFF, 0xFF]),
-            interpolate,
-        );
-    }
-    let font = FontRef::try_from_slice(include_bytes!(concat!(
+fn load_font() -> FontRef<'static> {
+    FontRef::try_from_slice(include_bytes!(concat!(
         env!("CARGO_MANIFEST_DIR"),
         "/fonts/DejaVuSans.ttf"
     )))
-    .unwrap();
-    let font_height = 10.0;
-    let scale = PxScale {
-        x: font_height,
-        y: font_height,
-    };
-    for apt in apt_idx.aps {
+    .unwrap()
+}
+
+const FONT_SCALE: PxScale = PxScale { x: 10.0, y: 10.0 };
+
+fn draw_airports(img: &mut RgbaImage, scaler: &Scaler, apts: &[Airport]) {
+    for apt in apts {
+        draw_hollow_circle_mut(img, scaler.map(apt.coord), 5, Rgba([0xFF, 0, 0, 0xFF]));
+    }
+}
+
+fn draw_tour(img: &mut RgbaImage, scaler: &Scaler, apt_idx: &AirportIdx, aco: &[u32], open: bool) {
+    for (aco1, aco2) in legs(aco, open) {
+        let arc = great_circle_arc(
+            apt_idx.aps[aco1 as usize].coord,
+            apt_idx.aps[aco2 as usize].coord,
+            IMG_ARC_SEGMENTS,
+        );
+        for (&coord1, &coord2) in arc.iter().zip(&arc[1..]) {
+            draw_antialiased_line_segment_mut(
+                img,
+                scaler.map(coord1),
+                scaler.map(coord2),
+                Rgba([0, 0, 0xFF, 0xFF]),
+                interpolate,
+            );
+        }
+    }
+}
+
+fn draw_labels(img: &mut RgbaImage, scaler: &Scaler, apts: &[Airport], font: &FontRef) {
+    for apt in apts {
         let (x, y) = scaler.map(apt.coord);
         draw_text_mut(
-            &mut img_buf,
+            img,
             Rgba([0, 0, 0, 0xFF]),
             x + 5,
             y - 10 - 5,
-            scale,
-            &font,
+            FONT_SCALE,
+            font,
             &apt.icao,
         );
     }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn draw_images(
+    mut images_dir: PathBuf,
+    apts: &[Airport],
+    apt_idx: &AirportIdx,
+    aco: &[u32],
+    open: bool,
+    draw_unfiltered: bool,
+    projection: ScalerProjection,
+    intensities: Option<&GraphIdx<Option<f64>>>,
+) {
+    check_images_dir(&images_dir);
+
+    let mut img_buf = RgbaImage::from_pixel(IMG_WIDTH, IMG_HEIGHT, Rgba([0xFF, 0xFF, 0xFF, 0xFF]));
+    let scaler = image_scaler(apt_idx, IMG_WIDTH, IMG_HEIGHT, projection);
+    images_dir.push("aco.png");
+
+    if let Some(intensities) = intensities {
+        draw_pheromone_overlay(&mut img_buf, intensities, apt_idx, &scaler);
+    }
+
+    draw_airports(
+        &mut img_buf,
+        &scaler,
+        if draw_unfiltered { apts } else { apt_idx.aps },
+    );
+    draw_tour(&mut img_buf, &scaler, apt_idx, aco, open);
+    draw_labels(&mut img_buf, &scaler, apt_idx.aps, &load_font());
     let img_buf: RgbImage = img_buf.convert();
     img_buf.save(images_dir).unwrap();
 }
 
-fn print_aps<'a: 'b, 'b>(
-    recs: &'b [AirportPrimaryRecord<'a>],
+/// One frame per element of `frames` with airports, the cycle, its iteration and length
+#[allow(clippy::too_many_arguments)]
+fn draw_animated_gif(
+    path: &Path,
+    frames: &[(Vec<u32>, f64)],
+    apt_idx: &AirportIdx,
+    width: u32,
+    height: u32,
+    fps: u32,
+    projection: ScalerProjection,
+    open: bool,
+) {
+    let scaler = image_scaler(apt_idx, width, height, projection);
+    let font = load_font();
+    let mut encoder = gif::Encoder::new(
+        BufWriter::new(fs::File::create(path).unwrap()),
+        width as u16,
+        height as u16,
+        &[],
+    )
+    .unwrap();
+    encoder.set_repeat(gif::Repeat::Infinite).unwrap();
+    for (i, (aco, dist)) in frames.iter().enumerate() {
+        let mut img_buf = RgbaImage::from_pixel(width, height, Rgba([0xFF, 0xFF, 0xFF, 0xFF]));
+        draw_airports(&mut img_buf, &scaler, apt_idx.aps);
+        draw_tour(&mut img_buf, &scaler, apt_idx, aco, open);
+        draw_labels(&mut img_buf, &scaler, apt_idx.aps, &font);
+        draw_text_mut(
+            &mut img_buf,
+            Rgba([0, 0, 0, 0xFF]),
+            5,
+            5,
+            FONT_SCALE,
+            &font,
+            &format!("Iteration: {}, length: {dist:.01}", i + 1),
+        );
+        let mut frame = gif::Frame::from_rgba_speed(
+            width as u16,
+            height as u16,
+            &mut img_buf.into_raw(),
+            GIF_QUANTIZATION_SPEED,
+        );
+        // in hundredths of a second
+        frame.delay = (100 / fps) as u16;
+        encoder.write_frame(&frame).unwrap();
+    }
+}
+
+/// Edges are drawn with opacity linear in the logarithm of pheromone relative to the maximal one,
+/// pheromones below `PHEROMONE_OVERLAY_DECADES` orders of magnitude of it are not drawn
+fn draw_pheromone_overlay(
+    img: &mut RgbaImage,
+    intensities: &GraphIdx<Option<f64>>,
+    apt_idx: &AirportIdx,
+    scaler: &Scaler,
+) {
+    let Some(max) = intensities
+        .iter_edges()
+        .filter_map(|(_, _, intensity)| intensity)
+        .filter(|&intensity| intensity > 0.0)
+        .reduce(f64::max)
+    else {
+        return;
+    };
+    let (width, height) = img.dimensions();
+    let visible =
+        |(x, y): (i32, i32)| (0..width as i32).contains(&x) && (0..height as i32).contains(&y);
+    for (apt1, apt2, intensity) in intensities.iter_edges() {
+        let Some(intensity) = intensity.filter(|&intensity| intensity > 0.0) else {
+            continue;
+        };
+        let opacity = 1.0 + (intensity / max).log10() / PHEROMONE_OVERLAY_DECADES;
+        if opacity <= 0.0 {
+            continue;
+        }
+        let (start, end) = (
+            scaler.map(apt_idx.aps[apt1 as usize].coord),
+            scaler.map(apt_idx.aps[apt2 as usize].coord),
+        );
+        if !visible(start) || !visible(end) {
+            continue;
+        }
+        draw_antialiased_line_segment_mut(
+            img,
+            start,
+            end,
+            Rgba([0, 0xC0, 0, 0xFF]),
+            |line, background, weight| interpolate(line, background, weight * opacity as f32),
+        );
+    }
+}
+
+fn draw_svg(
+    apts: &[Airport],
+    apt_idx: &AirportIdx,
+    aco: &[u32],
+    open: bool,
+    draw_unfiltered: bool,
+    projection: ScalerProjection,
+) -> String {
+    let scaler = image_scaler(apt_idx, IMG_WIDTH, IMG_HEIGHT, projection);
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{IMG_WIDTH}" height="{IMG_HEIGHT}" viewBox="0 0 {IMG_WIDTH} {IMG_HEIGHT}">"#
+    )
+    .unwrap();
+    writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#).unwrap();
+    for (aco1, aco2) in legs(aco, open) {
+        let (x1, y1) = scaler.map_f32(apt_idx.aps[aco1 as usize].coord);
+        let (x2, y2) = scaler.map_f32(apt_idx.aps[aco2 as usize].coord);
+        writeln!(
+            svg,
+            r#"<line x1="{x1:.1}" y1="{y1:.1}" x2="{x2:.1}" y2="{y2:.1}" stroke="blue"/>"#
+        )
+        .unwrap();
+    }
+    for apt in if draw_unfiltered { apts } else { apt_idx.aps } {
+        let (x, y) = scaler.map_f32(apt.coord);
+        writeln!(
+            svg,
+            r#"<circle cx="{x:.1}" cy="{y:.1}" r="5" fill="none" stroke="red"><title>{} ({})</title></circle>"#,
+            xml_escape(&apt.icao),
+            xml_escape(&apt.name)
+        )
+        .unwrap();
+    }
+    for apt in apt_idx.aps {
+        let (x, y) = scaler.map_f32(apt.coord);
+        writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{:.1}" font-family="DejaVu Sans" font-size="10">{}</text>"#,
+            x + 5.0,
+            y - 5.0,
+            xml_escape(&apt.icao)
+        )
+        .unwrap();
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[allow(clippy::too_many_arguments)]
+fn print_aps(
+    apts: &[Airport],
+    recs: &[AirportPrimaryRecord],
     distances_idx: &DistancesIdx,
     aco: &[u32],
     selected_dist: f64,
+    open: bool,
     out: Option<PathBuf>,
+    format: OutputFormat,
 ) {
     let (mut stdout_write, mut file_write);
     let writable: &mut dyn Write = if let Some(path) = out {
@@ -272,36 +1154,523 @@ fn print_aps<'a: 'b, 'b>(
     };
     let mut writable = BufWriter::new(writable);
 
-    for (i, j, rec, rec_next) in
-        cycling(aco).map(|(&i, &j)| (i, j, recs[i as usize], recs[j as usize]))
-    {
-        let lat = &rec.airport_reference_point_latitude;
-        let lon = &rec.airport_reference_point_longitude;
-        writeln!(
+    match format {
+        OutputFormat::Text => write_aps_text(
             &mut writable,
-            "{} ({}): {}°{}′{}.{:02}″{} {}°{}′{}.{:02}″{}. Distance to next {}: {:.01}",
-            rec.icao_identifier,
-            rec.airport_name,
-            lat.degrees,
-            lat.minutes,
-            lat.seconds,
-            lat.fractional_seconds,
-            match lat.hemisphere {
-                LatitudeHemisphere::North => 'N',
-                LatitudeHemisphere::South => 'S',
-            },
-            lon.degrees,
-            lon.minutes,
-            lon.seconds,
-            lon.fractional_seconds,
-            match lon.hemisphere {
-                LongitudeHemisphere::East => 'E',
-                LongitudeHemisphere::West => 'W',
-            },
-            rec_next.icao_identifier,
-            distances_idx.between(i, j).unwrap_or(f64::NAN)
-        )
-        .unwrap();
+            apts,
+            recs,
+            distances_idx,
+            aco,
+            selected_dist,
+            open,
+        ),
+        OutputFormat::Json => {
+            write_aps_json(&mut writable, apts, distances_idx, aco, selected_dist, open)
+        }
+        OutputFormat::Csv => write_aps_csv(&mut writable, apts, distances_idx, aco, open),
+    }
+    .unwrap();
+}
+
+fn write_aps_text(
+    writable: &mut dyn Write,
+    apts: &[Airport],
+    recs: &[AirportPrimaryRecord],
+    distances_idx: &DistancesIdx,
+    aco: &[u32],
+    selected_dist: f64,
+    open: bool,
+) -> io::Result<()> {
+    for (i, j) in with_next(aco, open) {
+        let apt = &apts[i as usize];
+        let description = recs
+            .get(i as usize)
+            .map(AirportPrimaryRecord::to_string)
+            .unwrap_or_else(|| format!("{} ({})", apt.icao, apt.name));
+        match j {
+            Some(j) => writeln!(
+                writable,
+                "{description}. Distance to next {}: {:.01}",
+                apts[j as usize].icao,
+                distances_idx.between(i, j).unwrap_or(f64::NAN)
+            )?,
+            None => writeln!(writable, "{description}.")?,
+        }
+    }
+    writeln!(writable, "Total lengths: {selected_dist:.05}")
+}
+
+fn write_aps_json(
+    writable: &mut dyn Write,
+    apts: &[Airport],
+    distances_idx: &DistancesIdx,
+    aco: &[u32],
+    selected_dist: f64,
+    open: bool,
+) -> io::Result<()> {
+    let total_distance = if selected_dist.is_finite() {
+        selected_dist.to_string()
+    } else {
+        "null".to_string()
+    };
+    write!(
+        writable,
+        r#"{{"total_distance":{total_distance},"airports":["#
+    )?;
+    for (k, (i, j)) in with_next(aco, open).enumerate() {
+        let apt = &apts[i as usize];
+        let (lat, lon) = apt.coord.to_degrees();
+        let distance_to_next = j
+            .and_then(|j| distances_idx.between(i, j))
+            .map_or("null".to_string(), |dist| dist.to_string());
+        write!(
+            writable,
+            r#"{}{{"icao":"{}","name":"{}","lat":{lat},"lon":{lon},"distance_to_next":{distance_to_next}}}"#,
+            if k > 0 { "," } else { "" },
+            json_escape(&apt.icao),
+            json_escape(&apt.name),
+        )?;
+    }
+    writeln!(writable, "]}}")
+}
+
+fn write_aps_csv(
+    writable: &mut dyn Write,
+    apts: &[Airport],
+    distances_idx: &DistancesIdx,
+    aco: &[u32],
+    open: bool,
+) -> io::Result<()> {
+    writeln!(writable, "icao,name,lat_deg,lon_deg,distance_to_next")?;
+    for (i, j) in with_next(aco, open) {
+        let apt = &apts[i as usize];
+        let (lat, lon) = apt.coord.to_degrees();
+        let distance_to_next = j
+            .and_then(|j| distances_idx.between(i, j))
+            .map(|dist| dist.to_string())
+            .unwrap_or_default();
+        writeln!(
+            writable,
+            "{},{},{lat},{lon},{distance_to_next}",
+            csv_quote(&apt.icao, false),
+            csv_quote(&apt.name, false),
+        )?;
+    }
+    Ok(())
+}
+
+/// Consecutive airports of `tour`, including the closing leg unless `open`
+fn legs(tour: &[u32], open: bool) -> impl Iterator<Item = (u32, u32)> + '_ {
+    with_next(tour, open).filter_map(|(i, j)| j.map(|j| (i, j)))
+}
+
+/// Each airport of `tour` with the next one, the last airport of open tour has none
+fn with_next(tour: &[u32], open: bool) -> impl Iterator<Item = (u32, Option<u32>)> + '_ {
+    cycling(tour)
+        .enumerate()
+        .map(move |(k, (&i, &j))| (i, (!open || k + 1 < tour.len()).then_some(j)))
+}
+
+fn aco_config(args: &Args) -> Result<AcoConfig, ConfigError> {
+    #[cfg(feature = "serde")]
+    let config = match &args.config {
+        Some(path) => {
+            AcoConfig::from_toml(&fs::read_to_string(path).map_err(|e| ConfigError::Io {
+                path: path.clone(),
+                kind: e.kind(),
+            })?)?
+        }
+        None => AcoConfig::default(),
+    };
+    #[cfg(not(feature = "serde"))]
+    let config = AcoConfig::default();
+    let config = AcoConfig {
+        ants: args.ants.unwrap_or(config.ants),
+        iterations: args.iterations.unwrap_or(config.iterations),
+        evaporation: args.evaporation.unwrap_or(config.evaporation),
+        alpha: args.alpha.unwrap_or(config.alpha),
+        beta: args.beta.unwrap_or(config.beta),
+        elite_ants: args.elite_ants.unwrap_or(config.elite_ants),
+        stagnation_limit: args.stagnation_limit.or(config.stagnation_limit),
+        seed: args.seed.or(config.seed),
+        candidates: args.candidates.or(config.candidates),
+    };
+    config.validate()?;
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tsp::aco::Aco;
+
+    #[test]
+    fn test_apt_pair_from_str() {
+        assert_eq!(
+            AptPair::from_str(" KLAX-KSEA "),
+            Ok(AptPair("KLAX", "KSEA"))
+        );
+        assert_eq!(
+            AptPair::from_str("KL-AX-KSEA"),
+            Err(r#"Invalid except entry "KL-AX-KSEA": expected ICAO-ICAO format"#.to_string())
+        );
+        assert!(AptPair::from_str("KLAX").is_err());
+        assert!(AptPair::from_str("KLAX-").is_err());
+    }
+
+    #[test]
+    fn test_draw_svg() {
+        let apt = |icao: &str, name: &str, lat, lon| Airport {
+            icao: icao.to_string(),
+            name: name.to_string(),
+            coord: Coord { lat, lon },
+        };
+        let airports = [
+            apt("KLAX", "LOS ANGELES INTL", 0.59, -2.06),
+            apt("KSEA", "SEATTLE-TACOMA INTL", 0.83, -2.13),
+            apt("KDEN", "DENVER INTL", 0.70, -1.83),
+            apt("KJFK", "JOHN F KENNEDY <INTL> & CO", 0.70, -1.29),
+        ];
+        let apt_idx = AirportIdx::new(&airports[..3]).unwrap();
+        let aco = [0, 2, 1];
+
+        let svg = draw_svg(
+            &airports,
+            &apt_idx,
+            &aco,
+            false,
+            false,
+            ScalerProjection::Equirectangular,
+        );
+        let doc = roxmltree::Document::parse(&svg).unwrap();
+        let root = doc.root_element();
+        assert_eq!(root.tag_name().name(), "svg");
+        let count = |tag| {
+            root.descendants()
+                .filter(|node| node.has_tag_name(tag))
+                .count()
+        };
+        assert_eq!(count("circle"), 3);
+        assert_eq!(count("line"), aco.len());
+        let titles: Vec<_> = root
+            .descendants()
+            .filter(|node| node.has_tag_name("title"))
+            .map(|node| node.text().unwrap())
+            .collect();
+        assert_eq!(
+            titles,
+            vec![
+                "KLAX (LOS ANGELES INTL)",
+                "KSEA (SEATTLE-TACOMA INTL)",
+                "KDEN (DENVER INTL)"
+            ]
+        );
+
+        let svg = draw_svg(
+            &airports,
+            &apt_idx,
+            &aco,
+            true,
+            true,
+            ScalerProjection::Mercator,
+        );
+        let doc = roxmltree::Document::parse(&svg).unwrap();
+        assert!(doc
+            .descendants()
+            .any(|node| node.text() == Some("KJFK (JOHN F KENNEDY <INTL> & CO)")));
+    }
+
+    #[test]
+    fn test_draw_pheromone_overlay() {
+        let apt = |icao: &str, lat, lon| Airport {
+            icao: icao.to_string(),
+            name: icao.to_string(),
+            coord: Coord { lat, lon },
+        };
+        // A-B is horizontal, A-C is vertical and B-C is diagonal
+        let airports = [apt("A", 1.0, 0.0), apt("B", 1.0, 1.0), apt("C", 0.0, 0.0)];
+        let apt_idx = AirportIdx::new(&airports).unwrap();
+        let intensities = GraphIdx::new(&apt_idx, |apt1, apt2| {
+            match (apt1.icao.as_str(), apt2.icao.as_str()) {
+                ("B", "A") => Some(10.0),
+                ("C", "A") => Some(1e-3),
+                ("C", "B") => Some(0.1),
+                _ => None,
+            }
+        });
+        let scaler = Scaler::new(airports[0].coord, Coord { lat: 0.0, lon: 1.0 }, 101, 101);
+        let mut img = RgbaImage::from_pixel(101, 101, Rgba([0xFF, 0xFF, 0xFF, 0xFF]));
+        draw_pheromone_overlay(&mut img, &intensities, &apt_idx, &scaler);
+
+        let white = Rgba([0xFF, 0xFF, 0xFF, 0xFF]);
+        // the strongest edge is drawn opaque
+        assert_eq!(*img.get_pixel(50, 0), Rgba([0, 0xC0, 0, 0xFF]));
+        // too weak edge is not drawn
+        assert_eq!(*img.get_pixel(0, 50), white);
+        // two orders of magnitude weaker edge is drawn at a third of opacity
+        let Rgba([r, g, b, _]) = *img.get_pixel(50, 50);
+        assert_eq!(r, b);
+        assert!((0xA0..0xB0).contains(&r));
+        assert!(g > 0xC0 && g < 0xFF);
+    }
+
+    #[test]
+    fn test_draw_animated_gif() {
+        let apt = |icao: &str, lat, lon| Airport {
+            icao: icao.to_string(),
+            name: icao.to_string(),
+            coord: Coord { lat, lon },
+        };
+        let airports = [apt("A", 1.0, 0.0), apt("B", 1.0, 1.0), apt("C", 0.0, 0.0)];
+        let apt_idx = AirportIdx::new(&airports).unwrap();
+        let frames = [(vec![0, 1, 2], 3.5), (vec![0, 2, 1], 3.4)];
+        let path = std::env::temp_dir().join(format!("tsp-test-{}.gif", process::id()));
+
+        draw_animated_gif(
+            &path,
+            &frames,
+            &apt_idx,
+            64,
+            48,
+            10,
+            ScalerProjection::Equirectangular,
+            false,
+        );
+
+        let mut decoder = gif::DecodeOptions::new()
+            .read_info(fs::File::open(&path).unwrap())
+            .unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (64, 48));
+        let mut decoded = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 10);
+            decoded += 1;
+        }
+        assert_eq!(decoded, frames.len());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_write_aps() {
+        let apt = |icao: &str, name: &str, lat: f64, lon: f64| Airport {
+            icao: icao.to_string(),
+            name: name.to_string(),
+            coord: Coord::try_from((lat, lon)).unwrap(),
+        };
+        let airports = [
+            apt("KLAX", "LOS ANGELES INTL", 33.9425, -118.408),
+            apt("KSEA", "SEATTLE-TACOMA INTL", 47.45, -122.31),
+            apt("KDEN", "DENVER, \"INTL\"", 39.86, -104.67),
+        ];
+        let apt_idx = AirportIdx::new(&airports).unwrap();
+        let distances = DistancesIdx::from(&apt_idx, None, &HashMap::new());
+        let aco = [2, 0, 1];
+        let dist = distances.tour_distance(&aco).unwrap();
+        let expected_icaos = ["KDEN", "KLAX", "KSEA"];
+
+        let mut text = vec![];
+        write_aps_text(&mut text, &airports, &[], &distances, &aco, dist, false).unwrap();
+        let text = String::from_utf8(text).unwrap();
+        let lines: Vec<_> = text.lines().collect();
+        assert_eq!(lines.len(), aco.len() + 1);
+        for (line, icao) in lines.iter().zip(expected_icaos) {
+            assert!(line.starts_with(icao), "{line}");
+        }
+        assert_eq!(lines[3], format!("Total lengths: {dist:.05}"));
+
+        let mut json = vec![];
+        write_aps_json(&mut json, &airports, &distances, &aco, dist, false).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(json["total_distance"].as_f64(), Some(dist));
+        let json_airports = json["airports"].as_array().unwrap();
+        assert_eq!(
+            json_airports
+                .iter()
+                .map(|apt| apt["icao"].as_str().unwrap())
+                .collect::<Vec<_>>(),
+            expected_icaos
+        );
+        assert_eq!(json_airports[0]["name"], "DENVER, \"INTL\"");
+        assert!((json_airports[1]["lat"].as_f64().unwrap() - 33.9425).abs() < 1e-9);
+        assert!((json_airports[1]["lon"].as_f64().unwrap() + 118.408).abs() < 1e-9);
+        assert_eq!(
+            json_airports[2]["distance_to_next"].as_f64(),
+            distances.between(1, 2)
+        );
+
+        let mut csv = vec![];
+        write_aps_csv(&mut csv, &airports, &distances, &aco, false).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines[0], "icao,name,lat_deg,lon_deg,distance_to_next");
+        assert_eq!(lines.len(), aco.len() + 1);
+        for (line, icao) in lines[1..].iter().zip(expected_icaos) {
+            assert!(line.starts_with(&format!("{icao},")), "{line}");
+        }
+        assert!(lines[1].starts_with(r#"KDEN,"DENVER, ""INTL""","#));
+        let distance_to_next: f64 = lines[3].rsplit(',').next().unwrap().parse().unwrap();
+        assert_eq!(Some(distance_to_next), distances.between(1, 2));
+
+        let path_dist = distances.path_distance(&aco).unwrap();
+        let mut text = vec![];
+        write_aps_text(&mut text, &airports, &[], &distances, &aco, path_dist, true).unwrap();
+        let text = String::from_utf8(text).unwrap();
+        let lines: Vec<_> = text.lines().collect();
+        assert!(lines[1].contains("Distance to next KSEA"), "{}", lines[1]);
+        assert_eq!(lines[2], "KSEA (SEATTLE-TACOMA INTL).");
+        assert_eq!(lines[3], format!("Total lengths: {path_dist:.05}"));
+
+        let mut json = vec![];
+        write_aps_json(&mut json, &airports, &distances, &aco, path_dist, true).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert!(json["airports"][2]["distance_to_next"].is_null());
+
+        let mut csv = vec![];
+        write_aps_csv(&mut csv, &airports, &distances, &aco, true).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.lines().last().unwrap().ends_with(','));
+    }
+
+    #[test]
+    fn test_machine_output_format_requires_output() {
+        for format in ["json", "csv"] {
+            assert!(Args::try_parse_from(["tsp", "--output-format", format]).is_err());
+            assert!(Args::try_parse_from(["tsp", "--output-format", format, "-o", "out"]).is_ok());
+        }
+        assert!(Args::try_parse_from(["tsp", "--output-format", "text"]).is_ok());
+    }
+
+    #[test]
+    fn test_cruise_speed() {
+        let cruise_speed = |speed| {
+            Args::try_parse_from(["tsp", "--cruise-speed", speed]).map(|args| args.cruise_speed)
+        };
+        assert_eq!(cruise_speed("450").unwrap(), Some(450.0));
+        for speed in ["0", "-1", "NaN", "fast"] {
+            assert!(cruise_speed(speed).is_err(), "{speed}");
+        }
+    }
+
+    #[test]
+    fn test_solve_tsplib() {
+        let args = Args::try_parse_from(["tsp", "--input-format", "tsplib", "-a", "5"]).unwrap();
+        assert_eq!(args.input_format, InputFormat::TspLib);
+        let instance = "DIMENSION : 4\nEDGE_WEIGHT_TYPE : EUC_2D\nNODE_COORD_SECTION\n\
+            1 0 0\n2 3 4\n3 3 0\n4 0 4\nEOF\n";
+        let distances = DistancesIdx {
+            graph: DistGraph::Dense(from_tsplib_matrix(instance.as_bytes()).unwrap()),
+        };
+        let config = AcoConfig {
+            seed: Some(42),
+            ..aco_config(&args).unwrap()
+        };
+        for solver in [SolverArg::Aco, SolverArg::NearestNeighbor] {
+            let (tour, dist) = solve_tsplib(&distances, &config, solver, None, false);
+            assert_eq!(validate_tour(&tour, &distances), Ok(14.0), "{solver:?}");
+            assert_eq!(dist, 14.0, "{solver:?}");
+            let (path, dist) = solve_tsplib(&distances, &config, solver, None, true);
+            assert_eq!(validate_path(&path, &distances), Ok(dist), "{solver:?}");
+        }
+    }
+
+    #[test]
+    fn test_aco_config() {
+        let config = aco_config(&Args::parse_from(["tsp", "-a", "10"])).unwrap();
+        assert_eq!(
+            config,
+            AcoConfig {
+                ants: 10,
+                ..AcoConfig::default()
+            }
+        );
+        assert_eq!(
+            aco_config(&Args::parse_from(["tsp", "-a", "1"])),
+            Err(ConfigError::Invalid {
+                field: "ants",
+                reason: "must be at least 2"
+            })
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_aco_config_file() {
+        let path = std::env::temp_dir().join(format!("tsp-test-{}.toml", process::id()));
+        fs::write(&path, "ants = 20\niterations = 30\nseed = 1\n").unwrap();
+        let config_arg = path.to_str().unwrap();
+        let config = aco_config(&Args::parse_from([
+            "tsp", "--config", config_arg, "-a", "40", "--alpha", "2",
+        ]));
+        let invalid = aco_config(&Args::parse_from([
+            "tsp", "--config", config_arg, "-a", "1",
+        ]));
+        fs::remove_file(&path).unwrap();
+        assert_eq!(
+            aco_config(&Args::parse_from(["tsp", "--config", config_arg])),
+            Err(ConfigError::Io {
+                path: path.clone(),
+                kind: io::ErrorKind::NotFound
+            })
+        );
+        assert_eq!(
+            config.unwrap(),
+            AcoConfig {
+                ants: 40,
+                iterations: 30,
+                alpha: 2.0,
+                seed: Some(1),
+                ..AcoConfig::default()
+            }
+        );
+        assert_eq!(
+            invalid,
+            Err(ConfigError::Invalid {
+                field: "ants",
+                reason: "must be at least 2"
+            })
+        );
+    }
+
+    #[test]
+    fn test_path_endpoints() {
+        let apt = |icao: &str, lat: f64, lon: f64| Airport {
+            icao: icao.to_string(),
+            name: icao.to_string(),
+            coord: Coord::try_from((lat, lon)).unwrap(),
+        };
+        let airports = [
+            apt("KDEN", 39.86, -104.67),
+            apt("KJFK", 40.64, -73.78),
+            apt("KSFO", 37.62, -122.38),
+            apt("KORD", 41.98, -87.90),
+        ];
+        let apt_idx = AirportIdx::new(&airports).unwrap();
+        let (start, end) = path_endpoints(&apt_idx, Some("KSFO"), Some("KJFK")).unwrap();
+        assert_eq!((start, end), (Some(2), Some(1)));
+
+        let distances = DistancesIdx::from(&apt_idx, None, &HashMap::new());
+        let AcoResult { tour, dist, .. } = Aco::new(&distances, None, None, None)
+            .with_seed(42)
+            .with_mode(AcoMode::Open { start, end })
+            .aco(10, 5, 0.9, 1.0, 2.0, 0, None, None, None);
+        assert_eq!(tour, vec![2, 0, 3, 1]);
+        assert_eq!(distances.path_distance(&tour), Some(dist));
+
+        assert_eq!(path_endpoints(&apt_idx, None, None), Ok((None, None)));
+        assert_eq!(
+            path_endpoints(&apt_idx, Some("KSFO"), Some("KLAX")),
+            Err("Airport KLAX is not among selected airports".to_string())
+        );
+        assert!(path_endpoints(&apt_idx, Some("KSFO"), Some("KSFO")).is_err());
+    }
+
+    #[test]
+    fn test_parse_excepts() {
+        let excepts = parse_excepts(["KLAX-KSEA", "KLAX-KJFK"]).unwrap();
+        assert_eq!(excepts["KLAX"], HashSet::from(["KSEA", "KJFK"]));
+        assert_eq!(
+            parse_excepts(["KLAX-KSEA", "KJFK"]),
+            Err(r#"Invalid except entry "KJFK": expected ICAO-ICAO format"#.to_string())
+        );
     }
-    writeln!(&mut writable, "Total lengths: {selected_dist:.05}").unwrap();
 }
@@ -1,6 +1,35 @@
 use crate::types::field::coord::Coord;
+use std::f64::consts::{FRAC_PI_2, PI, TAU};
 
-const R2: f64 = 6371.0 * 2.0;
+pub const EARTH_RADIUS: f64 = 6371.0;
+pub const KM_PER_NM: f64 = 1.852;
+pub const METERS_PER_FOOT: f64 = 0.3048;
+const R2: f64 = EARTH_RADIUS * 2.0;
+const WGS84_A: f64 = 6378137.0;
+const WGS84_F: f64 = 1.0 / 298.257223563;
+const WGS84_B: f64 = WGS84_A * (1.0 - WGS84_F);
+const VINCENTY_MAX_ITERATIONS: u32 = 200;
+const VINCENTY_TOLERANCE: f64 = 1e-12;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum DistanceFormula {
+    /// Great circle on the sphere of [`EARTH_RADIUS`]
+    #[default]
+    Haversine,
+    /// Geodesic on the WGS84 ellipsoid, falls back to haversine where Vincenty diverges
+    Vincenty,
+}
+
+impl DistanceFormula {
+    pub fn distance(self, coord1: Coord, coord2: Coord) -> f64 {
+        match self {
+            Self::Haversine => great_circle(coord1, coord2),
+            Self::Vincenty => {
+                vincenty(coord1, coord2).unwrap_or_else(|| great_circle(coord1, coord2))
+            }
+        }
+    }
+}
 
 pub fn great_circle(coord1: Coord, coord2: Coord) -> f64 {
     let delta_lat2 = (coord2.lat - coord1.lat) * 0.5;
@@ -13,9 +42,210 @@ pub fn great_circle(coord1: Coord, coord2: Coord) -> f64 {
     c * R2
 }
 
+/// Distance in kilometers on the WGS84 ellipsoid by the Vincenty inverse formula, `None` for
+/// near-antipodal points where the iteration diverges
+pub fn vincenty(coord1: Coord, coord2: Coord) -> Option<f64> {
+    vincenty_inverse(coord1, coord2).map(|(dist, _, _)| dist)
+}
+
+/// Initial bearing at `coord1` towards `coord2` and the reverse one at `coord2` towards `coord1`,
+/// in radians from `0` to `2π`
+pub fn vincenty_bearing(coord1: Coord, coord2: Coord) -> Option<(f64, f64)> {
+    vincenty_inverse(coord1, coord2).map(|(_, forward, reverse)| (forward, reverse))
+}
+
+// distance in kilometers, forward and reverse bearings
+fn vincenty_inverse(coord1: Coord, coord2: Coord) -> Option<(f64, f64, f64)> {
+    let l = coord2.lon - coord1.lon;
+    let u1 = ((1.0 - WGS84_F) * coord1.lat.tan()).atan();
+    let u2 = ((1.0 - WGS84_F) * coord2.lat.tan()).atan();
+    let (sin_u1, cos_u1) = u1.sin_cos();
+    let (sin_u2, cos_u2) = u2.sin_cos();
+
+    let mut lambda = l;
+    let mut iteration = 0;
+    let (sin_lambda, cos_lambda, sin_sigma, cos_sigma, sigma, cos_sq_alpha, cos_2sigma_m) = loop {
+        let (sin_lambda, cos_lambda) = lambda.sin_cos();
+        let sin_sigma = (cos_u2 * sin_lambda).hypot(cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda);
+        if sin_sigma == 0.0 {
+            // coincident points
+            return Some((0.0, 0.0, 0.0));
+        }
+        let cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
+        let sigma = sin_sigma.atan2(cos_sigma);
+        let sin_alpha = cos_u1 * cos_u2 * sin_lambda / sin_sigma;
+        let cos_sq_alpha = 1.0 - sin_alpha * sin_alpha;
+        // both points on the equator
+        let cos_2sigma_m = if cos_sq_alpha == 0.0 {
+            0.0
+        } else {
+            cos_sigma - 2.0 * sin_u1 * sin_u2 / cos_sq_alpha
+        };
+        let c = WGS84_F / 16.0 * cos_sq_alpha * (4.0 + WGS84_F * (4.0 - 3.0 * cos_sq_alpha));
+        let prev_lambda = lambda;
+        lambda = l
+            + (1.0 - c)
+                * WGS84_F
+                * sin_alpha
+                * (sigma
+                    + c * sin_sigma
+                        * (cos_2sigma_m
+                            + c * cos_sigma * (-1.0 + 2.0 * cos_2sigma_m * cos_2sigma_m)));
+        iteration += 1;
+        if (lambda - l).abs() > PI || iteration > VINCENTY_MAX_ITERATIONS {
+            return None;
+        }
+        if (lambda - prev_lambda).abs() < VINCENTY_TOLERANCE {
+            break (
+                sin_lambda,
+                cos_lambda,
+                sin_sigma,
+                cos_sigma,
+                sigma,
+                cos_sq_alpha,
+                cos_2sigma_m,
+            );
+        }
+    };
+
+    let u_sq = cos_sq_alpha * (WGS84_A * WGS84_A - WGS84_B * WGS84_B) / (WGS84_B * WGS84_B);
+    let a = 1.0 + u_sq / 16384.0 * (4096.0 + u_sq * (-768.0 + u_sq * (320.0 - 175.0 * u_sq)));
+    let b = u_sq / 1024.0 * (256.0 + u_sq * (-128.0 + u_sq * (74.0 - 47.0 * u_sq)));
+    let cos_sq_2sigma_m = cos_2sigma_m * cos_2sigma_m;
+    let delta_sigma = b
+        * sin_sigma
+        * (cos_2sigma_m
+            + b / 4.0
+                * (cos_sigma * (-1.0 + 2.0 * cos_sq_2sigma_m)
+                    - b / 6.0
+                        * cos_2sigma_m
+                        * (-3.0 + 4.0 * sin_sigma * sin_sigma)
+                        * (-3.0 + 4.0 * cos_sq_2sigma_m)));
+    let dist = WGS84_B * a * (sigma - delta_sigma) / 1000.0;
+
+    let forward = (cos_u2 * sin_lambda).atan2(cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda);
+    let final_bearing =
+        (cos_u1 * sin_lambda).atan2(-sin_u1 * cos_u2 + cos_u1 * sin_u2 * cos_lambda);
+    Some((
+        dist,
+        forward.rem_euclid(TAU),
+        (final_bearing + PI).rem_euclid(TAU),
+    ))
+}
+
+/// Point at fraction `t` of the great circle arc from `from` to `to`
+pub fn great_circle_interpolate(from: Coord, to: Coord, t: f64) -> Coord {
+    let angle = great_circle(from, to) / EARTH_RADIUS;
+    let sin_angle = angle.sin();
+    if sin_angle.abs() < f64::EPSILON {
+        return from;
+    }
+    let a = ((1.0 - t) * angle).sin() / sin_angle;
+    let b = (t * angle).sin() / sin_angle;
+    let ((x1, y1, z1), (x2, y2, z2)) = (to_cartesian(from), to_cartesian(to));
+    from_cartesian(a * x1 + b * x2, a * y1 + b * y2, a * z1 + b * z2)
+}
+
+fn to_cartesian(coord: Coord) -> (f64, f64, f64) {
+    (
+        coord.lat.cos() * coord.lon.cos(),
+        coord.lat.cos() * coord.lon.sin(),
+        coord.lat.sin(),
+    )
+}
+
+fn from_cartesian(x: f64, y: f64, z: f64) -> Coord {
+    Coord {
+        lat: z.atan2(x.hypot(y)),
+        lon: y.atan2(x),
+    }
+}
+
+/// Initial bearing (forward azimuth) at `from` towards `to` on the sphere, in radians from `0`
+/// to `2π`
+pub fn initial_bearing(from: Coord, to: Coord) -> f64 {
+    let delta_lon = to.lon - from.lon;
+    let y = delta_lon.sin() * to.lat.cos();
+    let x = from.lat.cos() * to.lat.sin() - from.lat.sin() * to.lat.cos() * delta_lon.cos();
+    y.atan2(x).rem_euclid(TAU)
+}
+
+/// Bearing on arrival at `to` when flying from `from` along the great circle
+pub fn final_bearing(from: Coord, to: Coord) -> f64 {
+    (initial_bearing(to, from) + PI).rem_euclid(TAU)
+}
+
+/// Point reached from `origin` after `distance` kilometers along the great circle starting at
+/// `bearing`
+pub fn destination(origin: Coord, bearing: f64, distance: f64) -> Coord {
+    let angle = distance / EARTH_RADIUS;
+    let (sin_lat1, cos_lat1) = origin.lat.sin_cos();
+    let (sin_angle, cos_angle) = angle.sin_cos();
+    let sin_lat2 = sin_lat1 * cos_angle + cos_lat1 * sin_angle * bearing.cos();
+    let lat = sin_lat2.asin();
+    let lon =
+        origin.lon + (bearing.sin() * sin_angle * cos_lat1).atan2(cos_angle - sin_lat1 * sin_lat2);
+    Coord {
+        lat,
+        lon: (lon + PI).rem_euclid(TAU) - PI,
+    }
+}
+
+/// Signed distance in kilometers from `point` to the great circle through `path_start` and
+/// `path_end`, positive to the right of the track
+pub fn cross_track_distance(path_start: Coord, path_end: Coord, point: Coord) -> f64 {
+    let angle13 = great_circle(path_start, point) / EARTH_RADIUS;
+    let delta_bearing = initial_bearing(path_start, point) - initial_bearing(path_start, path_end);
+    (angle13.sin() * delta_bearing.sin()).asin() * EARTH_RADIUS
+}
+
+/// Distance in kilometers from `path_start` to the point of the track closest to `point`,
+/// negative if that point is behind `path_start`
+pub fn along_track_distance(path_start: Coord, path_end: Coord, point: Coord) -> f64 {
+    let angle13 = great_circle(path_start, point) / EARTH_RADIUS;
+    let delta_bearing = initial_bearing(path_start, point) - initial_bearing(path_start, path_end);
+    let cross_track_angle = (angle13.sin() * delta_bearing.sin()).asin();
+    let along_track_angle = (angle13.cos() / cross_track_angle.cos())
+        .clamp(-1.0, 1.0)
+        .acos();
+    along_track_angle * delta_bearing.cos().signum() * EARTH_RADIUS
+}
+
+/// Middle of the great circle arc. Any great circle joins antipodal points, for them the middle
+/// is a quarter of the circle north of `from` along its meridian
+pub fn midpoint(from: Coord, to: Coord) -> Coord {
+    let ((x1, y1, z1), (x2, y2, z2)) = (to_cartesian(from), to_cartesian(to));
+    let (x, y, z) = (x1 + x2, y1 + y2, z1 + z2);
+    if x.hypot(y).hypot(z) < 1e-12 {
+        return if from.lat > 0.0 {
+            Coord {
+                lat: FRAC_PI_2 - from.lat,
+                lon: (from.lon + TAU).rem_euclid(TAU) - PI,
+            }
+        } else {
+            Coord {
+                lat: from.lat + FRAC_PI_2,
+                lon: from.lon,
+            }
+        };
+    }
+    from_cartesian(x, y, z)
+}
+
+/// `n_segments + 1` points evenly spaced along the great circle arc, including both ends
+pub fn great_circle_arc(from: Coord, to: Coord, n_segments: usize) -> Vec<Coord> {
+    (0..=n_segments)
+        .map(|i| match i {
+            0 => from,
+            i if i == n_segments => to,
+            i => great_circle_interpolate(from, to, i as f64 / n_segments as f64),
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
-    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+    use std::f64::consts::FRAC_PI_4;
 
     use crate::types::field::coord::{
         Latitude, LatitudeHemisphere, Longitude, LongitudeHemisphere,
@@ -23,6 +253,189 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_great_circle_arc() {
+        let from = Coord { lat: 0.0, lon: 0.0 };
+        let to = Coord {
+            lat: 0.0,
+            lon: FRAC_PI_2,
+        };
+        assert_eq!(great_circle_arc(from, to, 1), vec![from, to]);
+
+        let arc = great_circle_arc(from, to, 2);
+        assert_eq!(arc.len(), 3);
+        assert!(arc[1].lat.abs() < 1e-12);
+        assert!((arc[1].lon - FRAC_PI_4).abs() < 1e-12);
+
+        // arc over the pole
+        let from = Coord {
+            lat: FRAC_PI_4,
+            lon: 0.0,
+        };
+        let to = Coord {
+            lat: FRAC_PI_4,
+            lon: PI,
+        };
+        let middle = great_circle_interpolate(from, to, 0.5);
+        assert!((middle.lat - FRAC_PI_2).abs() < 1e-6);
+        let arc = great_circle_arc(from, to, 4);
+        for (coord1, coord2) in arc.iter().zip(&arc[1..]) {
+            assert!((great_circle(*coord1, *coord2) - great_circle(from, to) / 4.0).abs() < 1e-6);
+        }
+        assert_eq!(great_circle_interpolate(from, from, 0.5), from);
+    }
+
+    #[test]
+    fn test_bearings() {
+        let origin = Coord { lat: 0.0, lon: 0.0 };
+        let pole = Coord {
+            lat: FRAC_PI_2,
+            lon: 0.0,
+        };
+        let east = Coord {
+            lat: 0.0,
+            lon: FRAC_PI_2,
+        };
+        assert_eq!(initial_bearing(origin, pole), 0.0);
+        assert_eq!(initial_bearing(origin, east), FRAC_PI_2);
+        assert_eq!(initial_bearing(east, origin), 3.0 * FRAC_PI_2);
+        assert_eq!(final_bearing(origin, east), FRAC_PI_2);
+        // flying east from 45N the great circle turns south by the arrival
+        let from = Coord {
+            lat: FRAC_PI_4,
+            lon: 0.0,
+        };
+        let to = Coord {
+            lat: FRAC_PI_4,
+            lon: FRAC_PI_2,
+        };
+        assert!(initial_bearing(from, to) < FRAC_PI_2);
+        assert!((final_bearing(from, to) - (PI - initial_bearing(from, to))).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_destination() {
+        // reference values are published rounded to arc seconds
+        let origin = Coord {
+            lat: dms(53.0, 19.0, 14.0),
+            lon: dms(-1.0, 43.0, 47.0),
+        };
+        let bearing = dms(96.0, 1.0, 18.0);
+        let reached = destination(origin, bearing, 124.8);
+        assert!((reached.lat - dms(53.0, 11.0, 18.0)).abs() < dms(0.0, 0.0, 0.5));
+        assert!((reached.lon - dms(0.0, 8.0, 0.0)).abs() < dms(0.0, 0.0, 0.5));
+        // and back to the millimeter
+        assert!((great_circle(origin, reached) - 124.8).abs() < 1e-6);
+        assert!((initial_bearing(origin, reached) - bearing).abs() < 1e-12);
+
+        let over_date_line = destination(Coord { lat: 0.0, lon: PI }, FRAC_PI_2, EARTH_RADIUS);
+        assert!(over_date_line.lat.abs() < 1e-12);
+        assert!((over_date_line.lon - (1.0 - PI)).abs() < 1e-12);
+        assert!(great_circle(destination(origin, bearing, 0.0), origin) < 1e-9);
+    }
+
+    #[test]
+    fn test_track_distances() {
+        let degrees = |lat: f64, lon: f64| Coord {
+            lat: lat.to_radians(),
+            lon: lon.to_radians(),
+        };
+        let (start, end) = (degrees(53.3206, -1.7297), degrees(53.1887, 0.1334));
+        let point = degrees(53.2611, -0.7972);
+        let cross_track = cross_track_distance(start, end, point);
+        assert!(
+            (cross_track + 0.3075).abs() < 5e-5,
+            "Cross track: {cross_track}"
+        );
+        assert!((cross_track_distance(end, start, point) + cross_track).abs() < 1e-9);
+        let along_track = along_track_distance(start, end, point);
+        assert!(
+            (along_track - 62.331).abs() < 5e-4,
+            "Along track: {along_track}"
+        );
+
+        // exactly on the equator track, behind the start
+        let equator = |lon: f64| Coord {
+            lat: 0.0,
+            lon: lon.to_radians(),
+        };
+        assert!(cross_track_distance(equator(0.0), equator(10.0), equator(-5.0)).abs() < 1e-9);
+        let behind = along_track_distance(equator(0.0), equator(10.0), equator(-5.0));
+        assert!((behind + great_circle(equator(0.0), equator(-5.0))).abs() < 1e-9);
+        // a point to the north of an eastbound track is on the left
+        assert!(cross_track_distance(equator(0.0), equator(10.0), degrees(1.0, 5.0)) < 0.0);
+    }
+
+    #[test]
+    fn test_midpoint() {
+        let origin = Coord { lat: 0.0, lon: 0.0 };
+        let east = Coord {
+            lat: 0.0,
+            lon: FRAC_PI_2,
+        };
+        let middle = midpoint(origin, east);
+        assert!(middle.lat.abs() < 1e-12);
+        assert!((middle.lon - FRAC_PI_4).abs() < 1e-12);
+        assert_eq!(midpoint(east, east), east);
+
+        let antipode = Coord { lat: 0.0, lon: PI };
+        assert_eq!(
+            midpoint(origin, antipode),
+            Coord {
+                lat: FRAC_PI_2,
+                lon: 0.0
+            }
+        );
+        let north = Coord {
+            lat: FRAC_PI_4,
+            lon: FRAC_PI_2,
+        };
+        let south = Coord {
+            lat: -FRAC_PI_4,
+            lon: -FRAC_PI_2,
+        };
+        let middle = midpoint(north, south);
+        assert!((middle.lat - FRAC_PI_4).abs() < 1e-12);
+        assert!((middle.lon + FRAC_PI_2).abs() < 1e-12);
+        assert!((great_circle(middle, north) - great_circle(middle, south)).abs() < 1e-9);
+    }
+
+    fn dms(degrees: f64, minutes: f64, seconds: f64) -> f64 {
+        (degrees.signum() * (degrees.abs() + minutes / 60.0 + seconds / 3600.0)).to_radians()
+    }
+
+    #[test]
+    fn test_vincenty() {
+        // Geoscience Australia reference: Flinders Peak to Buninyong
+        let flinders_peak = Coord {
+            lat: dms(-37.0, 57.0, 3.72030),
+            lon: dms(144.0, 25.0, 29.52440),
+        };
+        let buninyong = Coord {
+            lat: dms(-37.0, 39.0, 10.15610),
+            lon: dms(143.0, 55.0, 35.38390),
+        };
+        let dist = vincenty(flinders_peak, buninyong).unwrap();
+        assert!((dist - 54.972271).abs() < 1e-6, "Distance: {dist}");
+        assert!((vincenty(buninyong, flinders_peak).unwrap() - dist).abs() < 1e-12);
+
+        let (forward, reverse) = vincenty_bearing(flinders_peak, buninyong).unwrap();
+        assert!((forward - dms(306.0, 52.0, 5.37)).abs() < 1e-7);
+        assert!((reverse - dms(127.0, 10.0, 25.07)).abs() < 1e-7);
+
+        assert_eq!(vincenty(buninyong, buninyong), Some(0.0));
+        let equator = |lon| Coord { lat: 0.0, lon };
+        assert_eq!(vincenty(equator(0.0), equator(PI)), None);
+        assert_eq!(
+            DistanceFormula::Vincenty.distance(equator(0.0), equator(PI)),
+            great_circle(equator(0.0), equator(PI))
+        );
+        // the sphere underestimates the equator
+        let quarter = vincenty(equator(0.0), equator(FRAC_PI_2)).unwrap();
+        assert!((quarter - WGS84_A * FRAC_PI_2 / 1000.0).abs() < 1e-6);
+        assert!(quarter > DistanceFormula::Haversine.distance(equator(0.0), equator(FRAC_PI_2)));
+    }
+
     fn assert_symmetry_eq(coord1: Coord, coord2: Coord, distance: f64) {
         assert_eq!(great_circle(coord1, coord2), distance);
         assert_eq!(great_circle(coord2, coord1), distance);
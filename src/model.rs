@@ -1,7 +1,13 @@
-use crate::math::great_circle;
+use crate::distance::DistancesIdx;
+use crate::kahan::KahanAdder;
+use crate::math::{great_circle, KM_PER_NM};
 use crate::types::field::coord::Coord;
+use crate::types::record::waypoint::TerminalWaypointRecord;
 use crate::types::record::AirportPrimaryRecord;
-use std::collections::HashMap;
+use crate::util::cycling;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Bound;
+use std::time::Duration;
 
 #[derive(Clone, Debug, PartialEq, PartialOrd)]
 pub struct Airport {
@@ -18,6 +24,59 @@ impl Airport {
     pub fn distance_to(&self, other: &Airport) -> f64 {
         self.distance_to_coord(other.coord)
     }
+
+    /// See [`Coord::in_bounding_box`]
+    pub fn in_bounding_box(&self, top_left: Coord, bottom_right: Coord) -> bool {
+        self.coord.in_bounding_box(top_left, bottom_right)
+    }
+
+    pub fn approx_flight_time(&self, other: &Airport, speed_kts: f64) -> Duration {
+        flight_time(self.distance_to(other), speed_kts)
+    }
+
+    /// Up to `n` airports nearest to `from` with distances, ascending. Airports with the same ICAO
+    /// identifier as `from` are skipped.
+    pub fn nearest_n<'a>(
+        airports: &'a [Airport],
+        from: &Airport,
+        n: usize,
+    ) -> Vec<(f64, &'a Airport)> {
+        let mut nearest: Vec<_> = airports
+            .iter()
+            .filter(|apt| apt.icao != from.icao)
+            .map(|apt| (from.distance_to(apt), apt))
+            .collect();
+        nearest.sort_by(|(d1, _), (d2, _)| d1.total_cmp(d2));
+        nearest.truncate(n);
+        nearest
+    }
+
+    /// Airports not farther than `radius_km` from `center`, including `center` itself, in the
+    /// order of `airports`
+    pub fn within_radius<'a>(
+        airports: &'a [Airport],
+        center: &Airport,
+        radius_km: f64,
+    ) -> Vec<&'a Airport> {
+        airports
+            .iter()
+            .filter(|apt| center.distance_to(apt) <= radius_km)
+            .collect()
+    }
+}
+
+/// Flight time over the closed tour, infeasible legs are not accounted
+pub fn total_flight_time(cycle: &[u32], distances: &DistancesIdx, speed_kts: f64) -> Duration {
+    let distance = cycling(cycle)
+        .filter_map(|(&apt1, &apt2)| distances.between(apt1, apt2))
+        .fold(KahanAdder::default(), KahanAdder::push)
+        .result();
+    flight_time(distance, speed_kts)
+}
+
+/// Time to fly `distance` in km at `speed_kts`
+pub fn flight_time(distance: f64, speed_kts: f64) -> Duration {
+    Duration::from_secs_f64(distance / (speed_kts * KM_PER_NM) * 3600.0)
 }
 
 impl<'a: 'b, 'b> From<&'b AirportPrimaryRecord<'a>> for Airport {
@@ -34,10 +93,71 @@ impl<'a: 'b, 'b> From<&'b AirportPrimaryRecord<'a>> for Airport {
     }
 }
 
+/// [`Airport`] with identifiers borrowed from the source record
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct AirportBorrowed<'a> {
+    pub icao: &'a str,
+    pub name: &'a str,
+    pub coord: Coord,
+}
+
+impl Airport {
+    /// Same as [`Airport::from`] without allocations
+    pub fn from_record_borrowed<'a>(rec: &'a AirportPrimaryRecord<'a>) -> AirportBorrowed<'a> {
+        AirportBorrowed {
+            icao: rec.icao_identifier,
+            name: rec.airport_name,
+            coord: (
+                &rec.airport_reference_point_latitude,
+                &rec.airport_reference_point_longitude,
+            )
+                .into(),
+        }
+    }
+}
+
+impl AirportBorrowed<'_> {
+    pub fn to_owned(&self) -> Airport {
+        Airport {
+            icao: self.icao.to_string(),
+            name: self.name.to_string(),
+            coord: self.coord,
+        }
+    }
+}
+
+impl From<AirportBorrowed<'_>> for Airport {
+    fn from(value: AirportBorrowed<'_>) -> Self {
+        value.to_owned()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct Waypoint {
+    pub identifier: String,
+    /// ICAO identifier of the airport
+    pub airport: String,
+    pub name: String,
+    pub coord: Coord,
+}
+
+impl<'a: 'b, 'b> From<&'b TerminalWaypointRecord<'a>> for Waypoint {
+    fn from(value: &TerminalWaypointRecord<'a>) -> Self {
+        Self {
+            identifier: value.waypoint_identifier.to_string(),
+            airport: value.icao_identifier.to_string(),
+            name: value.waypoint_name.to_string(),
+            coord: (&value.latitude, &value.longitude).into(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct AirportIdx<'a> {
     pub aps: &'a [Airport],
     pub idx_by_icao: HashMap<&'a str, u32>,
+    /// Same as `idx_by_icao`, ordered for prefix scans
+    pub idx_by_icao_sorted: BTreeMap<&'a str, u32>,
 }
 
 impl<'a> AirportIdx<'a> {
@@ -50,22 +170,71 @@ impl<'a> AirportIdx<'a> {
         if aps.len() != idx_by_icao.len() {
             None
         } else {
-            Some(Self { aps, idx_by_icao })
+            let idx_by_icao_sorted = idx_by_icao.iter().map(|(&icao, &i)| (icao, i)).collect();
+            Some(Self {
+                aps,
+                idx_by_icao,
+                idx_by_icao_sorted,
+            })
         }
     }
+
+    /// Airports with ICAO identifier starting with `prefix`, ordered by ICAO identifier
+    pub fn lookup_by_prefix<'b>(
+        &'b self,
+        prefix: &'b str,
+    ) -> impl Iterator<Item = (u32, &'a Airport)> + 'b {
+        self.idx_by_icao_sorted
+            .range::<str, _>((Bound::Included(prefix), Bound::Unbounded))
+            .take_while(move |(icao, _)| icao.starts_with(prefix))
+            .map(|(_, &i)| (i, &self.aps[i as usize]))
+    }
+
+    /// Collects `airports` into `storage` and builds the index over it in one go.
+    ///
+    /// The index borrows airports, so the owned storage has to be provided by the caller.
+    pub fn from_iter(
+        airports: impl IntoIterator<Item = Airport>,
+        storage: &'a mut Vec<Airport>,
+    ) -> Option<Self> {
+        storage.clear();
+        storage.extend(airports);
+        Self::new(storage)
+    }
+
+    /// Top left and bottom right corners of the box around airports, widened on each side by
+    /// `margin_frac` of the coordinate range in radians, e.g. `0.05` adds 5% margins.
+    /// Returns `None` if there are no airports.
+    pub fn bounding_box_with_margin_frac(&self, margin_frac: f64) -> Option<(Coord, Coord)> {
+        let (top_left, bottom_right) = Coord::bounding_box(self.aps.iter().map(|apt| apt.coord))?;
+        Some(Coord::with_margin(top_left, bottom_right, margin_frac))
+    }
+
+    /// Builds the index without `icao` airport in `storage`, renumbering the rest consecutively.
+    /// Returns `None` if `icao` is not in the index.
+    pub fn without<'b>(&self, icao: &str, storage: &'b mut Vec<Airport>) -> Option<AirportIdx<'b>> {
+        let removed = *self.idx_by_icao.get(icao)? as usize;
+        let airports = self
+            .aps
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| i != removed)
+            .map(|(_, apt)| apt.clone());
+        AirportIdx::from_iter(airports, storage)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::parser::record::parse_airport_primary_record;
+    use crate::parser::record::waypoint::parse_terminal_waypoint_record;
+    use crate::test_util::{KDEN, KJFK, KLAX, KSEA, KTPA};
 
     #[test]
     fn test_apt_from_apr() {
-        let record = b"SUSAP KLAXK2ALAX     0     \
-        129YHN33563299W118242898E012000128         1800018000C    \
-        MNAR    LOS ANGELES INTL              310231906";
-        let apr = parse_airport_primary_record(&record[..]).unwrap();
+        let record = KLAX.as_bytes();
+        let apr = parse_airport_primary_record(record).unwrap();
         let apt = Airport::from(&apr);
         let coord = (
             &apr.airport_reference_point_latitude,
@@ -82,20 +251,253 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_apt_from_apr_borrowed() {
+        let record = KLAX.as_bytes();
+        let apr = parse_airport_primary_record(record).unwrap();
+        let borrowed = Airport::from_record_borrowed(&apr);
+        assert_eq!(borrowed.icao, "KLAX");
+        assert_eq!(borrowed.name, "LOS ANGELES INTL");
+        assert_eq!(borrowed.to_owned(), Airport::from(&apr));
+        assert_eq!(Airport::from(borrowed), Airport::from(&apr));
+    }
+
+    #[test]
+    fn test_waypoint_from_terminal_waypoint_record() {
+        let record = b"SUSAP KSEAK1CALPSE K10    C     \
+        N47351280W122182907                       E0160     NAR           \
+        ALPSE                    124001807";
+        let rec = parse_terminal_waypoint_record(&record[..]).unwrap();
+        assert_eq!(
+            Waypoint::from(&rec),
+            Waypoint {
+                identifier: "ALPSE".to_string(),
+                airport: "KSEA".to_string(),
+                name: "ALPSE".to_string(),
+                coord: (&rec.latitude, &rec.longitude).into(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_flight_time() {
+        let apt = |icao: &str, lon| Airport {
+            icao: icao.to_string(),
+            name: icao.to_string(),
+            coord: Coord { lat: 0.0, lon },
+        };
+        let (a, b) = (apt("A", 0.0), apt("B", 0.0));
+        assert_eq!(a.approx_flight_time(&b, 100.0), Duration::ZERO);
+
+        // one nautical mile is one minute of arc
+        let b = apt("B", (60.0_f64).to_radians());
+        let minutes = a.distance_to(&b) / KM_PER_NM;
+        let time = a.approx_flight_time(&b, minutes);
+        assert!((time.as_secs_f64() - 3600.0).abs() < 1e-6);
+
+        let c = apt("C", (120.0_f64).to_radians());
+        let airports = [a, b, c];
+        let apt_idx = AirportIdx::new(&airports).unwrap();
+        let distances = DistancesIdx::from(&apt_idx, None, &HashMap::new());
+        let time = total_flight_time(&[0, 1, 2], &distances, minutes);
+        assert!((time.as_secs_f64() - 4.0 * 3600.0).abs() < 1e-6);
+    }
+
+    fn five_airports() -> Vec<Airport> {
+        let records = [
+            KLAX.as_bytes(),
+            KSEA.as_bytes(),
+            KDEN.as_bytes(),
+            KJFK.as_bytes(),
+            KTPA.as_bytes(),
+        ];
+        records
+            .iter()
+            .map(|&rec| Airport::from(&parse_airport_primary_record(rec).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_nearest_n() {
+        let apts = five_airports();
+        let icaos = |nearest: Vec<(f64, &Airport)>| {
+            nearest
+                .into_iter()
+                .map(|(_, apt)| apt.icao.clone())
+                .collect::<Vec<_>>()
+        };
+        let nearest = Airport::nearest_n(&apts, &apts[0], 2);
+        // LAX-DEN is about 1385 km, LAX-SEA is about 1535 km
+        assert!((nearest[0].0 - 1385.0).abs() < 10.0);
+        assert!((nearest[1].0 - 1535.0).abs() < 10.0);
+        assert_eq!(icaos(nearest), vec!["KDEN", "KSEA"]);
+        assert_eq!(
+            icaos(Airport::nearest_n(&apts, &apts[3], 10)),
+            vec!["KTPA", "KDEN", "KSEA", "KLAX"]
+        );
+        assert_eq!(Airport::nearest_n(&apts, &apts[3], 0), vec![]);
+    }
+
+    #[test]
+    fn test_within_radius() {
+        let apts = five_airports();
+        let icaos = |within: Vec<&Airport>| {
+            within
+                .into_iter()
+                .map(|apt| apt.icao.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            icaos(Airport::within_radius(&apts, &apts[0], 1000.0)),
+            vec!["KLAX"]
+        );
+        assert_eq!(
+            icaos(Airport::within_radius(&apts, &apts[0], 1500.0)),
+            vec!["KLAX", "KDEN"]
+        );
+        assert_eq!(
+            icaos(Airport::within_radius(&apts, &apts[0], 1600.0)),
+            vec!["KLAX", "KSEA", "KDEN"]
+        );
+        let exact = apts[0].distance_to(&apts[2]);
+        assert_eq!(
+            icaos(Airport::within_radius(&apts, &apts[0], exact)),
+            vec!["KLAX", "KDEN"]
+        );
+        assert_eq!(
+            icaos(Airport::within_radius(&apts, &apts[0], exact.next_down())),
+            vec!["KLAX"]
+        );
+    }
+
+    #[test]
+    fn test_bounding_box_with_margin_frac() {
+        let apt = |icao: &str, lat, lon| Airport {
+            icao: icao.to_string(),
+            name: icao.to_string(),
+            coord: Coord { lat, lon },
+        };
+        let airports = [apt("A", 0.2, -0.4), apt("B", -0.2, 0.6), apt("C", 0.0, 0.1)];
+        let apt_idx = AirportIdx::new(&airports).unwrap();
+        let (top_left, bottom_right) = apt_idx.bounding_box_with_margin_frac(0.5).unwrap();
+        assert!((top_left.lat - 0.4).abs() < 1e-12);
+        assert!((top_left.lon + 0.9).abs() < 1e-12);
+        assert!((bottom_right.lat + 0.4).abs() < 1e-12);
+        assert!((bottom_right.lon - 1.1).abs() < 1e-12);
+
+        let same_lat = [apt("A", 0.3, -0.1), apt("B", 0.3, 0.1)];
+        let apt_idx = AirportIdx::new(&same_lat).unwrap();
+        let (top_left, bottom_right) = apt_idx.bounding_box_with_margin_frac(0.05).unwrap();
+        assert_eq!(top_left.lat, 0.3);
+        assert_eq!(bottom_right.lat, 0.3);
+        assert!((bottom_right.lon - top_left.lon - 0.22).abs() < 1e-12);
+
+        let apt_idx = AirportIdx::new(&same_lat[..1]).unwrap();
+        assert_eq!(
+            apt_idx.bounding_box_with_margin_frac(0.05),
+            Some((same_lat[0].coord, same_lat[0].coord))
+        );
+        assert_eq!(
+            AirportIdx::new(&[])
+                .unwrap()
+                .bounding_box_with_margin_frac(0.05),
+            None
+        );
+    }
+
     #[test]
     fn test_apt_idx_from_apr() {
-        let record = b"SUSAP KLAXK2ALAX     0     \
-        129YHN33563299W118242898E012000128         1800018000C    \
-        MNAR    LOS ANGELES INTL              310231906";
-        let apr = parse_airport_primary_record(&record[..]).unwrap();
+        let record = KLAX.as_bytes();
+        let apr = parse_airport_primary_record(record).unwrap();
         let apt = [Airport::from(&apr)];
         let apt_idx = AirportIdx::new(&apt);
         assert_eq!(
             apt_idx,
             Some(AirportIdx {
                 aps: &apt,
-                idx_by_icao: HashMap::from([("KLAX", 0)])
+                idx_by_icao: HashMap::from([("KLAX", 0)]),
+                idx_by_icao_sorted: BTreeMap::from([("KLAX", 0)]),
+            })
+        );
+    }
+
+    #[test]
+    fn test_apt_idx_from_iter() {
+        let record = KLAX.as_bytes();
+        let apr = parse_airport_primary_record(record).unwrap();
+
+        let mut storage = vec![];
+        let apt_idx = AirportIdx::from_iter([&apr].into_iter().map(Airport::from), &mut storage);
+        let expected = [Airport::from(&apr)];
+        assert_eq!(
+            apt_idx,
+            Some(AirportIdx {
+                aps: &expected,
+                idx_by_icao: HashMap::from([("KLAX", 0)]),
+                idx_by_icao_sorted: BTreeMap::from([("KLAX", 0)]),
             })
         );
+
+        let mut storage = vec![];
+        let apt_idx =
+            AirportIdx::from_iter([&apr, &apr].into_iter().map(Airport::from), &mut storage);
+        assert_eq!(apt_idx, None);
+    }
+
+    #[test]
+    fn test_lookup_by_prefix() {
+        let airports: Vec<_> = ["KSEA", "CYVR", "KLAX", "EGLL", "KJFK"]
+            .into_iter()
+            .map(|icao| Airport {
+                icao: icao.to_string(),
+                name: icao.to_string(),
+                coord: Coord { lat: 0.0, lon: 0.0 },
+            })
+            .collect();
+        let apt_idx = AirportIdx::new(&airports).unwrap();
+        let lookup = |prefix| {
+            apt_idx
+                .lookup_by_prefix(prefix)
+                .map(|(i, apt)| (i, apt.icao.clone()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            lookup("K"),
+            vec![
+                (4, "KJFK".to_string()),
+                (2, "KLAX".to_string()),
+                (0, "KSEA".to_string())
+            ]
+        );
+        assert_eq!(lookup("KL"), vec![(2, "KLAX".to_string())]);
+        assert_eq!(lookup("CYVR"), vec![(1, "CYVR".to_string())]);
+        assert_eq!(lookup("CYVRX"), vec![]);
+        assert_eq!(lookup("Z"), vec![]);
+        assert_eq!(lookup("").len(), airports.len());
+    }
+
+    #[test]
+    fn test_apt_idx_without() {
+        let records = [KLAX.as_bytes(), KSEA.as_bytes(), KDEN.as_bytes()];
+        let apts: Vec<_> = records
+            .iter()
+            .map(|&rec| Airport::from(&parse_airport_primary_record(rec).unwrap()))
+            .collect();
+        let apt_idx = AirportIdx::new(&apts).unwrap();
+
+        let mut storage = vec![];
+        let without = apt_idx.without("KSEA", &mut storage);
+        let expected = [apts[0].clone(), apts[2].clone()];
+        assert_eq!(
+            without,
+            Some(AirportIdx {
+                aps: &expected,
+                idx_by_icao: HashMap::from([("KLAX", 0), ("KDEN", 1)]),
+                idx_by_icao_sorted: BTreeMap::from([("KLAX", 0), ("KDEN", 1)]),
+            })
+        );
+
+        let mut storage = vec![];
+        assert_eq!(apt_idx.without("KJFK", &mut storage), None);
     }
 }
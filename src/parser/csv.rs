@@ -0,0 +1,270 @@
+use crate::model::Airport;
+use crate::types::field::coord::Coord;
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::io::BufRead;
+
+/// Header of airports CSV, compared case-insensitively
+pub const AIRPORTS_HEADER: [&str; 4] = ["icao", "name", "lat", "lon"];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CsvParseError {
+    pub line: usize,
+    pub column: Option<&'static str>,
+    pub kind: CsvParseErrorKind,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CsvParseErrorKind {
+    Io(io::ErrorKind),
+    UnterminatedQuote,
+    InvalidHeader,
+    MissingColumn,
+    InvalidNumber,
+    InvalidCoord,
+}
+
+impl CsvParseError {
+    pub fn new(line: usize, column: Option<&'static str>, kind: CsvParseErrorKind) -> Self {
+        Self { line, column, kind }
+    }
+}
+
+impl Display for CsvParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let kind = match self.kind {
+            CsvParseErrorKind::Io(kind) => return write!(f, "line {}: {kind}", self.line),
+            CsvParseErrorKind::UnterminatedQuote => "unterminated quote",
+            CsvParseErrorKind::InvalidHeader => "invalid header",
+            CsvParseErrorKind::MissingColumn => "missing column",
+            CsvParseErrorKind::InvalidNumber => "invalid number",
+            CsvParseErrorKind::InvalidCoord => "coordinate out of range",
+        };
+        match self.column {
+            Some(column) => write!(f, "line {}, column {column}: {kind}", self.line),
+            None => write!(f, "line {}: {kind}", self.line),
+        }
+    }
+}
+
+impl Error for CsvParseError {}
+
+/// Splits a CSV record into fields, unquoting them. Quotes inside quoted fields are doubled.
+/// Returns `None` if a quoted field is not terminated.
+pub fn split_csv_record(record: &str) -> Option<Vec<Cow<'_, str>>> {
+    let mut fields = vec![];
+    let mut rest = record;
+    loop {
+        let (field, remaining) = if let Some(quoted) = rest.strip_prefix('"') {
+            let mut field = String::new();
+            let mut chunks = quoted;
+            loop {
+                let end = chunks.find('"')?;
+                field.push_str(&chunks[..end]);
+                chunks = &chunks[end + 1..];
+                match chunks.strip_prefix('"') {
+                    Some(escaped) => {
+                        field.push('"');
+                        chunks = escaped;
+                    }
+                    None => break,
+                }
+            }
+            (Cow::Owned(field), chunks)
+        } else {
+            let end = rest.find(',').unwrap_or(rest.len());
+            (Cow::Borrowed(&rest[..end]), &rest[end..])
+        };
+        fields.push(field);
+        match remaining.strip_prefix(',') {
+            Some(remaining) => rest = remaining,
+            None => break Some(fields),
+        }
+    }
+}
+
+/// Parses airports CSV with [`AIRPORTS_HEADER`] header followed by records with
+/// latitude and longitude in decimal degrees. Blank lines are skipped
+pub fn parse_airports_csv(reader: impl BufRead) -> Result<Vec<Airport>, CsvParseError> {
+    let mut lines = reader.lines().enumerate().map(|(i, line)| {
+        line.map(|line| (i + 1, line))
+            .map_err(|e| CsvParseError::new(i + 1, None, CsvParseErrorKind::Io(e.kind())))
+    });
+    let (_, header) = lines
+        .next()
+        .transpose()?
+        .ok_or_else(|| CsvParseError::new(1, None, CsvParseErrorKind::InvalidHeader))?;
+    let header = split_csv_record(&header)
+        .ok_or_else(|| CsvParseError::new(1, None, CsvParseErrorKind::UnterminatedQuote))?;
+    if header.len() != AIRPORTS_HEADER.len()
+        || !header
+            .iter()
+            .zip(AIRPORTS_HEADER)
+            .all(|(column, expected)| column.trim().eq_ignore_ascii_case(expected))
+    {
+        return Err(CsvParseError::new(
+            1,
+            None,
+            CsvParseErrorKind::InvalidHeader,
+        ));
+    }
+
+    let mut airports = vec![];
+    for line in lines {
+        let (line_no, line) = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        airports.push(parse_airport_record(line_no, &line)?);
+    }
+    Ok(airports)
+}
+
+fn parse_airport_record(line_no: usize, line: &str) -> Result<Airport, CsvParseError> {
+    let fields = split_csv_record(line)
+        .ok_or_else(|| CsvParseError::new(line_no, None, CsvParseErrorKind::UnterminatedQuote))?;
+    let field = |idx: usize| {
+        fields.get(idx).map(|field| field.as_ref()).ok_or_else(|| {
+            CsvParseError::new(
+                line_no,
+                Some(AIRPORTS_HEADER[idx]),
+                CsvParseErrorKind::MissingColumn,
+            )
+        })
+    };
+    let number = |idx: usize| {
+        field(idx)?.trim().parse::<f64>().map_err(|_| {
+            CsvParseError::new(
+                line_no,
+                Some(AIRPORTS_HEADER[idx]),
+                CsvParseErrorKind::InvalidNumber,
+            )
+        })
+    };
+
+    let coord = parse_coord(
+        line_no,
+        (number(2)?, AIRPORTS_HEADER[2]),
+        (number(3)?, AIRPORTS_HEADER[3]),
+    )?;
+    Ok(Airport {
+        icao: field(0)?.to_string(),
+        name: field(1)?.to_string(),
+        coord,
+    })
+}
+
+/// Coordinate of latitude and longitude in degrees, or error in the column of the one out of
+/// range
+pub(crate) fn parse_coord(
+    line_no: usize,
+    (lat, lat_column): (f64, &'static str),
+    (lon, lon_column): (f64, &'static str),
+) -> Result<Coord, CsvParseError> {
+    Coord::try_from((lat, lon)).map_err(|_| {
+        let column = if Coord::try_from((lat, 0.0)).is_err() {
+            lat_column
+        } else {
+            lon_column
+        };
+        CsvParseError::new(line_no, Some(column), CsvParseErrorKind::InvalidCoord)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_parse_airports_csv() {
+        let data = "\
+ICAO,Name,Lat,Lon
+KLAX,LOS ANGELES INTL,33.9425,-118.408
+
+KSEA,\"SEATTLE-TACOMA INTL\",47.45,-122.3117
+KDEN,DENVER INTL,39.8617,-104.6731
+KJFK,JOHN F KENNEDY INTL,40.6398,-73.7789
+KTPA,TAMPA INTL,27.9755,-82.5332
+";
+        let airports = parse_airports_csv(data.as_bytes()).unwrap();
+        let expected = [
+            ("KLAX", "LOS ANGELES INTL", 33.9425, -118.408),
+            ("KSEA", "SEATTLE-TACOMA INTL", 47.45, -122.3117),
+            ("KDEN", "DENVER INTL", 39.8617, -104.6731),
+            ("KJFK", "JOHN F KENNEDY INTL", 40.6398, -73.7789),
+            ("KTPA", "TAMPA INTL", 27.9755, -82.5332),
+        ];
+        assert_eq!(airports.len(), expected.len());
+        for (airport, (icao, name, lat, lon)) in airports.iter().zip(expected) {
+            assert_eq!(airport.icao, icao);
+            assert_eq!(airport.name, name);
+            assert!((airport.coord.lat - lat * PI / 180.0).abs() < 1e-12);
+            assert!((airport.coord.lon - lon * PI / 180.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_parse_airports_csv_errors() {
+        assert_eq!(
+            parse_airports_csv("".as_bytes()),
+            Err(CsvParseError::new(
+                1,
+                None,
+                CsvParseErrorKind::InvalidHeader
+            ))
+        );
+        assert_eq!(
+            parse_airports_csv("icao,name,lon,lat\n".as_bytes()),
+            Err(CsvParseError::new(
+                1,
+                None,
+                CsvParseErrorKind::InvalidHeader
+            ))
+        );
+        assert_eq!(
+            parse_airports_csv("icao,name,lat,lon\nKLAX,LAX,north,0\n".as_bytes()),
+            Err(CsvParseError::new(
+                2,
+                Some("lat"),
+                CsvParseErrorKind::InvalidNumber
+            ))
+        );
+        assert_eq!(
+            parse_airports_csv("icao,name,lat,lon\n\nKLAX,LAX,0\n".as_bytes()),
+            Err(CsvParseError::new(
+                3,
+                Some("lon"),
+                CsvParseErrorKind::MissingColumn
+            ))
+        );
+        assert_eq!(
+            parse_airports_csv("icao,name,lat,lon\nKLAX,LAX,0,181\n".as_bytes()),
+            Err(CsvParseError::new(
+                2,
+                Some("lon"),
+                CsvParseErrorKind::InvalidCoord
+            ))
+        );
+        assert_eq!(
+            parse_airports_csv("icao,name,lat,lon\nKLAX,LAX,-90.5,181\n".as_bytes()),
+            Err(CsvParseError::new(
+                2,
+                Some("lat"),
+                CsvParseErrorKind::InvalidCoord
+            ))
+        );
+    }
+
+    #[test]
+    fn test_split_csv_record() {
+        assert_eq!(
+            split_csv_record(r#"1,"Goroka Airport","Say ""hi"", bye",,-6.08"#).unwrap(),
+            vec!["1", "Goroka Airport", r#"Say "hi", bye"#, "", "-6.08"]
+        );
+        assert_eq!(split_csv_record("").unwrap(), vec![""]);
+        assert_eq!(split_csv_record(r#"1,"unterminated"#), None);
+    }
+}
@@ -0,0 +1,65 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FieldName {
+    RecordType,
+    CustomerAreaCode,
+    IcaoIdentifier,
+    IcaoCode,
+    AtaDesignator,
+    Blank,
+    ContinuationRecordNumber,
+    SpeedLimitAltitude,
+    LongestRunway,
+    IfrCapability,
+    LongestRunwaySurfaceCode,
+    AirportReferencePointLatitude,
+    AirportReferencePointLongitude,
+    MagneticVariation,
+    AirportElevation,
+    SpeedLimit,
+    RecommendedNavaid,
+    TransitionAltitude,
+    TransitionLevel,
+    PublicMilitaryIndicator,
+    TimeZone,
+    DaylightIndicator,
+    MagneticTrueIndicator,
+    DatumCode,
+    AirportName,
+    FileRecordNumber,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    InvalidLength(usize),
+    InvalidSectionCode,
+    InvalidSubsectionCode,
+    InvalidCoordinate(FieldName),
+    InvalidCycleDate,
+    InvalidField(FieldName),
+    ContinuationRecord,
+}
+
+impl Display for FieldName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidLength(len) => write!(f, "invalid record length {len}"),
+            ParseError::InvalidSectionCode => write!(f, "invalid section code"),
+            ParseError::InvalidSubsectionCode => write!(f, "invalid subsection code"),
+            ParseError::InvalidCoordinate(field) => write!(f, "invalid coordinate {field}"),
+            ParseError::InvalidCycleDate => write!(f, "invalid cycle date"),
+            ParseError::InvalidField(field) => write!(f, "invalid field {field}"),
+            ParseError::ContinuationRecord => write!(f, "continuation record"),
+        }
+    }
+}
+
+impl Error for ParseError {}
@@ -1,11 +1,11 @@
 use crate::types::field::coord::{Latitude, LatitudeHemisphere, Longitude, LongitudeHemisphere};
 use crate::types::field::{
     Altitude, CycleDate, MagneticTrueIndicator, MagneticVariation, PublicMilitaryIndicator,
-    RecordType, RunwaySurfaceCode, TimeZone,
+    RecordType, RunwaySurfaceCode, TimeZone, VhfNavaidType,
 };
 use crate::util::{
-    parse_alpha, parse_alphanum, parse_blank_arr, parse_num_u16, parse_num_u32, parse_num_u8,
-    trim_right_spaces,
+    is_alphanum, parse_alpha, parse_alphanum, parse_blank_arr, parse_num_u16, parse_num_u32,
+    parse_num_u8, trim_right_spaces,
 };
 use rust_decimal::Decimal;
 
@@ -191,11 +191,8 @@ pub fn parse_airport_reference_point_longitude(
             && seconds == 0
             && fractional_seconds == 0
             && hemisphere != LongitudeHemisphere::East)
-            || (degrees == 180
-                && (minutes != 0
-                    || seconds != 0
-                    || fractional_seconds != 0
-                    || hemisphere != LongitudeHemisphere::East))
+            // E180 and W180 denote the same meridian, both are accepted
+            || (degrees == 180 && (minutes != 0 || seconds != 0 || fractional_seconds != 0))
         {
             None
         } else {
@@ -271,6 +268,92 @@ pub fn parse_longest_runway_surface_code(
     })
 }
 
+// 5.46 Runway Identifier
+pub fn parse_runway_identifier(runway_identifier: &[u8]) -> Option<&str> {
+    if runway_identifier.len() != 5 || &runway_identifier[..2] != b"RW" {
+        return None;
+    }
+    parse_num_u8(&runway_identifier[2..4], 2..=2, ..=36)?;
+    parse_alphanum(runway_identifier, 4..=5)
+}
+
+// 5.57 Runway Length
+pub fn parse_runway_length(runway_length: &[u8]) -> Option<u16> {
+    parse_num_u16(runway_length, 5..=5, ..)
+}
+
+// 5.109 Runway Width
+pub fn parse_runway_width(runway_width: &[u8]) -> Option<u16> {
+    parse_num_u16(runway_width, 3..=3, ..)
+}
+
+// 5.69 Displaced Threshold Distance
+pub fn parse_displaced_threshold_distance(displaced_threshold_distance: &[u8]) -> Option<u16> {
+    parse_num_u16(displaced_threshold_distance, 4..=4, ..)
+}
+
+// 5.59 Runway Description
+pub fn parse_runway_description(runway_description: &[u8]) -> Option<&str> {
+    parse_alphanum(runway_description, ..=22)
+}
+
+// signed number with explicit sign, blank when absent
+fn parse_optional_signed(signed: &[u8], digits: usize) -> Option<Option<i32>> {
+    if signed.len() != digits + 1 {
+        return None;
+    }
+    Some(match parse_blank_arr(signed, ..) {
+        None => {
+            let val = parse_num_u32(&signed[1..], digits..=digits, ..)? as i32;
+            Some(match signed[0] {
+                b'+' => val,
+                b'-' => -val,
+                _ => None?,
+            })
+        }
+        Some(_) => None,
+    })
+}
+
+// 5.212 Runway Gradient
+pub fn parse_runway_gradient(runway_gradient: &[u8]) -> Option<Option<i32>> {
+    parse_optional_signed(runway_gradient, 4)
+}
+
+// 5.225 Ellipsoidal Height
+pub fn parse_ellipsoidal_height(ellipsoidal_height: &[u8]) -> Option<Option<i32>> {
+    parse_optional_signed(ellipsoidal_height, 5)
+}
+
+// 5.13 Waypoint Identifier
+pub fn parse_waypoint_identifier(waypoint_identifier: &[u8]) -> Option<&str> {
+    parse_alphanum(waypoint_identifier, 1..=5)
+}
+
+// 5.42 Waypoint Type
+pub fn parse_waypoint_type(waypoint_type: &[u8]) -> Option<&str> {
+    if waypoint_type.len() != 3 || waypoint_type[0] == b' ' || !is_alphanum(waypoint_type) {
+        return None;
+    }
+    std::str::from_utf8(waypoint_type).ok()
+}
+
+// 5.82 Waypoint Usage
+pub fn parse_waypoint_usage(waypoint_usage: &[u8]) -> Option<&str> {
+    if waypoint_usage.len() != 2
+        || !matches!(waypoint_usage[0], b' ' | b'R')
+        || !matches!(waypoint_usage[1], b' ' | b'H' | b'L' | b'B')
+    {
+        return None;
+    }
+    std::str::from_utf8(waypoint_usage).ok()
+}
+
+// 5.43 Waypoint Name/Description
+pub fn parse_waypoint_name(waypoint_name: &[u8]) -> Option<&str> {
+    parse_alphanum(waypoint_name, ..=25)
+}
+
 // 5.108 IFR Capability
 pub fn parse_ifr_capability(ifr_capability: u8) -> Option<bool> {
     Some(match ifr_capability {
@@ -339,6 +422,91 @@ pub fn parse_customer_area_code(customer_area_code: &[u8]) -> Option<&str> {
     parse_alpha(customer_area_code, ..=3)
 }
 
+// 5.34 VOR/NDB Frequency, VHF ones in tenths of kHz
+pub fn parse_vhf_frequency(frequency: &[u8]) -> Option<u32> {
+    parse_num_u32(frequency, 5..=5, ..).map(|frequency| frequency * 100)
+}
+
+// 5.35 Navaid Class
+pub fn parse_vhf_navaid_class(class: &[u8]) -> Option<(VhfNavaidType, &str)> {
+    if class.len() != 5 {
+        return None;
+    }
+    let navaid_type = match (class[0], class[1]) {
+        (b'V', b' ') => VhfNavaidType::Vor,
+        (b'V', b'D') => VhfNavaidType::VorDme,
+        (b'V', b'T' | b'M') => VhfNavaidType::Vortac,
+        (b' ', b'D') => VhfNavaidType::Dme,
+        (b' ', b'T' | b'M') => VhfNavaidType::Tacan,
+        (b' ', b'I') => VhfNavaidType::IlsDme,
+        (b' ', b'N' | b'P') => VhfNavaidType::MlsDme,
+        _ => None?,
+    };
+    Some((navaid_type, std::str::from_utf8(class).ok()?))
+}
+
+// 5.34 VOR/NDB Frequency, NDB ones in kHz
+pub fn parse_ndb_frequency(frequency: &[u8]) -> Option<u16> {
+    parse_num_u16(frequency, 5..=5, ..).map(|frequency| frequency / 10)
+}
+
+// 5.35 Navaid Class
+pub fn parse_ndb_navaid_class(class: &[u8]) -> Option<&str> {
+    if class.len() != 5 || !matches!(class[0], b'H' | b'S' | b'M' | b' ') {
+        return None;
+    }
+    std::str::from_utf8(class).ok()
+}
+
+// 5.33 VOR/NDB Identifier, 5.38 DME Identifier
+pub fn parse_navaid_identifier(navaid_identifier: &[u8]) -> Option<&str> {
+    parse_alphanum(navaid_identifier, 1..=4)
+}
+
+pub fn parse_optional_navaid_identifier(navaid_identifier: &[u8]) -> Option<Option<&str>> {
+    Some(match parse_blank_arr(navaid_identifier, 4..=4) {
+        None => Some(parse_navaid_identifier(navaid_identifier)?),
+        Some(_) => None,
+    })
+}
+
+// 5.71 Name
+pub fn parse_navaid_name(navaid_name: &[u8]) -> Option<&str> {
+    parse_alpha(navaid_name, ..=30)
+}
+
+// 5.36 Latitude, blank for absent facility
+pub fn parse_optional_latitude(latitude: &[u8]) -> Option<Option<Latitude>> {
+    Some(match parse_blank_arr(latitude, 9..=9) {
+        None => Some(parse_airport_reference_point_latitude(latitude)?),
+        Some(_) => None,
+    })
+}
+
+// 5.37 Longitude, blank for absent facility
+pub fn parse_optional_longitude(longitude: &[u8]) -> Option<Option<Longitude>> {
+    Some(match parse_blank_arr(longitude, 10..=10) {
+        None => Some(parse_airport_reference_point_longitude(longitude)?),
+        Some(_) => None,
+    })
+}
+
+// 5.66 Station Declination
+pub fn parse_station_declination(station_declination: &[u8]) -> Option<Option<MagneticVariation>> {
+    Some(match parse_blank_arr(station_declination, 5..=5) {
+        None => Some(parse_magnetic_variation(station_declination)?),
+        Some(_) => None,
+    })
+}
+
+// 5.40 DME Elevation
+pub fn parse_optional_elevation(elevation: &[u8]) -> Option<Option<i32>> {
+    Some(match parse_blank_arr(elevation, 5..=5) {
+        None => Some(parse_airport_elevation(elevation)?),
+        Some(_) => None,
+    })
+}
+
 // 5.2 Record Type
 pub fn parse_record_type(record_type: u8) -> Option<RecordType> {
     Some(match record_type {
@@ -347,3 +515,97 @@ pub fn parse_record_type(record_type: u8) -> Option<RecordType> {
         _ => None?,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_longitude_180() {
+        for (raw, hemisphere) in [
+            (b"E180000000", LongitudeHemisphere::East),
+            (b"W180000000", LongitudeHemisphere::West),
+        ] {
+            assert_eq!(
+                parse_airport_reference_point_longitude(&raw[..]),
+                Some(Longitude {
+                    hemisphere,
+                    degrees: 180,
+                    minutes: 0,
+                    seconds: 0,
+                    fractional_seconds: 0,
+                })
+            );
+        }
+        assert_eq!(parse_airport_reference_point_longitude(b"E180000001"), None);
+        assert_eq!(parse_airport_reference_point_longitude(b"W180010000"), None);
+        assert_eq!(parse_airport_reference_point_longitude(b"W181000000"), None);
+    }
+
+    #[test]
+    fn parse_longitude_zero() {
+        assert!(parse_airport_reference_point_longitude(b"E000000000").is_some());
+        assert_eq!(parse_airport_reference_point_longitude(b"W000000000"), None);
+    }
+
+    #[test]
+    fn parse_time_zone_offsets() {
+        assert_eq!(
+            parse_time_zone(b"E30").unwrap().unwrap().offset_seconds(),
+            -(5 * 3600 + 30 * 60)
+        );
+        assert_eq!(
+            parse_time_zone(b"Y45").unwrap().unwrap().offset_seconds(),
+            12 * 3600 + 45 * 60
+        );
+        assert_eq!(
+            parse_time_zone(b"Z00").unwrap().unwrap().offset_seconds(),
+            0
+        );
+
+        for letter in (b'A'..=b'Z').filter(|&letter| letter != b'J') {
+            for minute in [b"00", b"30", b"45"] {
+                let raw = [letter, minute[0], minute[1]];
+                let time_zone = parse_time_zone(&raw).unwrap().unwrap();
+                assert_eq!(
+                    TimeZone::from_offset_seconds(time_zone.offset_seconds()),
+                    Some(time_zone),
+                    "{}",
+                    String::from_utf8_lossy(&raw)
+                );
+            }
+        }
+
+        assert_eq!(TimeZone::from_offset_seconds(30), None);
+        assert_eq!(TimeZone::from_offset_seconds(-30 * 60), None);
+        assert_eq!(TimeZone::from_offset_seconds(13 * 3600), None);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn parse_time_zone_fixed_offset() {
+        use crate::types::field::TimeZoneOutOfRange;
+        use chrono::FixedOffset;
+
+        assert_eq!(
+            FixedOffset::from(parse_time_zone(b"E30").unwrap().unwrap()),
+            FixedOffset::west_opt(5 * 3600 + 30 * 60).unwrap()
+        );
+        for letter in (b'A'..=b'Z').filter(|&letter| letter != b'J') {
+            for minute in [b"00", b"30", b"45"] {
+                let raw = [letter, minute[0], minute[1]];
+                let time_zone = parse_time_zone(&raw).unwrap().unwrap();
+                assert_eq!(
+                    TimeZone::try_from(FixedOffset::from(time_zone)),
+                    Ok(time_zone),
+                    "{}",
+                    String::from_utf8_lossy(&raw)
+                );
+            }
+        }
+        assert_eq!(
+            TimeZone::try_from(FixedOffset::east_opt(13 * 3600).unwrap()),
+            Err(TimeZoneOutOfRange)
+        );
+    }
+}
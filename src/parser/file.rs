@@ -1,9 +1,400 @@
-use crate::parser::record::parse_airport_primary_record;
-use crate::types::record::AirportPrimaryRecord;
+use crate::parser::error::ParseError;
+use crate::parser::field::parse_continuation_record_number;
+use crate::parser::record::navaid::{parse_ndb_navaid_record, parse_vhf_navaid_record};
+use crate::parser::record::runway::parse_runway_record;
+use crate::parser::record::waypoint::parse_terminal_waypoint_record;
+use crate::parser::record::{
+    parse_airport_primary_record, parse_airport_primary_record_checked, ENTRY_LEN,
+};
+use crate::types::record::navaid::{NdbNavaidRecord, VhfNavaidRecord};
+use crate::types::record::runway::RunwayRecord;
+use crate::types::record::waypoint::TerminalWaypointRecord;
+use crate::types::record::{AirportPrimaryRecord, OwnedAirportPrimaryRecord};
 use crate::util::trim_0d;
+use std::collections::HashSet;
+use std::io;
+use std::io::BufRead;
 
-pub fn parse_airport_primary_records(buf: &[u8]) -> impl Iterator<Item = AirportPrimaryRecord> {
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParseStats {
+    /// Non-empty lines
+    pub total_lines: usize,
+    pub valid_airport_pa_records: usize,
+    pub skipped_other_sections: usize,
+    pub skipped_continuation_records: usize,
+    pub failed_to_parse: usize,
+}
+
+pub fn parse_airport_primary_records(buf: &[u8]) -> impl Iterator<Item = AirportPrimaryRecord<'_>> {
+    parse_airport_primary_records_checked(buf).filter_map(Result::ok)
+}
+
+/// Airport primary records with customer area code from `areas`
+pub fn parse_airport_primary_records_for_areas<'a>(
+    buf: &'a [u8],
+    areas: &'a HashSet<&str>,
+) -> impl Iterator<Item = AirportPrimaryRecord<'a>> {
+    parse_airport_primary_records(buf).filter(|rec| areas.contains(rec.customer_area_code))
+}
+
+/// Airport primary records, or why the line failed to parse. Lines of other sections and
+/// continuation records are skipped.
+pub fn parse_airport_primary_records_checked(
+    buf: &[u8],
+) -> impl Iterator<Item = Result<AirportPrimaryRecord<'_>, ParseError>> {
+    classify_airport_primary_records(buf).filter(|rec| {
+        !matches!(
+            rec,
+            Err(ParseError::InvalidSectionCode
+                | ParseError::InvalidSubsectionCode
+                | ParseError::ContinuationRecord)
+        )
+    })
+}
+
+// Airport primary records of non-empty lines, records of other sections and continuation records
+// are told apart before the line is parsed
+fn classify_airport_primary_records(
+    buf: &[u8],
+) -> impl Iterator<Item = Result<AirportPrimaryRecord<'_>, ParseError>> {
+    buf.split(|&c| c == b'\n')
+        .map(trim_0d)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            // section code in column 5 and airport subsection code in column 13
+            if line.get(4) != Some(&b'P') {
+                return Err(ParseError::InvalidSectionCode);
+            }
+            if line.get(12) != Some(&b'A') {
+                return Err(ParseError::InvalidSubsectionCode);
+            }
+            if line
+                .get(21)
+                .is_some_and(|&c| parse_continuation_record_number(c, false).is_some())
+            {
+                return Err(ParseError::ContinuationRecord);
+            }
+            parse_airport_primary_record_checked(line)
+        })
+}
+
+/// Reads records line by line into a fixed buffer, longer lines are skipped. An I/O error is
+/// yielded once and ends the records.
+pub fn parse_airport_primary_records_streaming<R: BufRead>(
+    mut reader: R,
+) -> impl Iterator<Item = io::Result<OwnedAirportPrimaryRecord>> {
+    // room for trailing '\r'
+    let mut line = [0; ENTRY_LEN + 1];
+    let mut failed = false;
+    std::iter::from_fn(move || loop {
+        if failed {
+            return None;
+        }
+        let len = match read_line_into(&mut reader, &mut line) {
+            Ok(len) => len?,
+            Err(e) => {
+                failed = true;
+                return Some(Err(e));
+            }
+        };
+        if len > line.len() {
+            continue;
+        }
+        if let Some(rec) = parse_airport_primary_record(trim_0d(&line[..len])) {
+            return Some(Ok(OwnedAirportPrimaryRecord::from(&rec)));
+        }
+    })
+}
+
+// Copies the line without '\n' into `line` as far as it fits, returns the full length of the line
+// or `None` at the end of input
+fn read_line_into(reader: &mut impl BufRead, line: &mut [u8]) -> io::Result<Option<usize>> {
+    let mut len = 0;
+    let mut started = false;
+    loop {
+        let available = reader.fill_buf()?;
+        if available.is_empty() {
+            return Ok(started.then_some(len));
+        }
+        started = true;
+        let newline = available.iter().position(|&c| c == b'\n');
+        let chunk = &available[..newline.unwrap_or(available.len())];
+        if len < line.len() {
+            let copied = chunk.len().min(line.len() - len);
+            line[len..len + copied].copy_from_slice(&chunk[..copied]);
+        }
+        len += chunk.len();
+        let consumed = chunk.len() + newline.map_or(0, |_| 1);
+        reader.consume(consumed);
+        if newline.is_some() {
+            return Ok(Some(len));
+        }
+    }
+}
+
+pub fn parse_vhf_navaid_records(buf: &[u8]) -> impl Iterator<Item = VhfNavaidRecord<'_>> {
+    buf.split(|&c| c == b'\n')
+        .map(trim_0d)
+        .filter_map(parse_vhf_navaid_record)
+}
+
+pub fn parse_ndb_navaid_records(buf: &[u8]) -> impl Iterator<Item = NdbNavaidRecord<'_>> {
+    buf.split(|&c| c == b'\n')
+        .map(trim_0d)
+        .filter_map(parse_ndb_navaid_record)
+}
+
+pub fn parse_runway_records(buf: &[u8]) -> impl Iterator<Item = RunwayRecord<'_>> {
     buf.split(|&c| c == b'\n')
         .map(trim_0d)
-        .filter_map(parse_airport_primary_record)
+        .filter_map(parse_runway_record)
+}
+
+pub fn parse_terminal_waypoint_records(
+    buf: &[u8],
+) -> impl Iterator<Item = TerminalWaypointRecord<'_>> {
+    buf.split(|&c| c == b'\n')
+        .map(trim_0d)
+        .filter_map(parse_terminal_waypoint_record)
+}
+
+/// Same as [`parse_airport_primary_records`], with byte offsets of the record lines in `buf`
+pub fn parse_airport_primary_records_with_index(
+    buf: &[u8],
+) -> impl Iterator<Item = (usize, AirportPrimaryRecord<'_>)> {
+    buf.split(|&c| c == b'\n')
+        .scan(0, |offset, line| {
+            let line_offset = *offset;
+            *offset += line.len() + 1;
+            Some((line_offset, line))
+        })
+        .filter_map(|(offset, line)| {
+            parse_airport_primary_record(trim_0d(line)).map(|rec| (offset, rec))
+        })
+}
+
+/// Eagerly parses airport primary records, counting skipped lines
+pub fn parse_airport_primary_records_counted(
+    buf: &[u8],
+) -> (impl Iterator<Item = AirportPrimaryRecord<'_>>, ParseStats) {
+    let mut stats = ParseStats::default();
+    let recs: Vec<_> = classify_airport_primary_records(buf)
+        .inspect(|rec| {
+            stats.total_lines += 1;
+            match rec {
+                Ok(_) => stats.valid_airport_pa_records += 1,
+                Err(ParseError::InvalidSectionCode | ParseError::InvalidSubsectionCode) => {
+                    stats.skipped_other_sections += 1
+                }
+                Err(ParseError::ContinuationRecord) => stats.skipped_continuation_records += 1,
+                Err(_) => stats.failed_to_parse += 1,
+            }
+        })
+        .flatten()
+        .collect();
+    (recs.into_iter(), stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::error::FieldName;
+    use crate::test_util::{KLAX, KSEA};
+
+    #[test]
+    fn test_parse_airport_primary_records_checked() {
+        let broken = KLAX.replace("YHN3356", "YHQ3356");
+        let continuation = format!("{}2{}", &KLAX[..21], &KLAX[22..]);
+        let buf = format!("HDR01\r\n{KLAX}\r\n{broken}\r\n{continuation}\r\n\r\n{KSEA}\r\n");
+        let parsed: Vec<_> = parse_airport_primary_records_checked(buf.as_bytes()).collect();
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[0].map(|rec| rec.icao_identifier), Ok("KLAX"));
+        assert_eq!(
+            parsed[1],
+            Err(ParseError::InvalidCoordinate(
+                FieldName::AirportReferencePointLatitude
+            ))
+        );
+        assert_eq!(parsed[2].map(|rec| rec.icao_identifier), Ok("KSEA"));
+        assert_eq!(
+            parse_airport_primary_records(buf.as_bytes())
+                .map(|rec| rec.icao_identifier)
+                .collect::<Vec<_>>(),
+            vec!["KLAX", "KSEA"]
+        );
+    }
+
+    #[test]
+    fn test_parse_airport_primary_records_for_areas() {
+        let cyvr = "SCANP CYVRCYAYVR     0     \
+        140YHN49113900W123105000E020000014         1800018000C    \
+        MNAR    VANCOUVER INTL                310231906";
+        let buf = format!("{KLAX}\n{cyvr}\n{KSEA}\n");
+        let icaos = |areas: &HashSet<&str>| {
+            parse_airport_primary_records_for_areas(buf.as_bytes(), areas)
+                .map(|rec| rec.icao_identifier.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(icaos(&HashSet::from(["CAN"])), vec!["CYVR"]);
+        assert_eq!(icaos(&HashSet::from(["USA"])), vec!["KLAX", "KSEA"]);
+        assert_eq!(
+            icaos(&HashSet::from(["USA", "CAN"])),
+            vec!["KLAX", "CYVR", "KSEA"]
+        );
+        assert_eq!(icaos(&HashSet::from(["EUR"])), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_parse_airport_primary_records_streaming() {
+        let too_long = format!("{KLAX}{KLAX}");
+        let buf = format!("HDR01\r\n{KLAX}\r\n{too_long}\n\n{KSEA}");
+        let expected: Vec<_> = parse_airport_primary_records(buf.as_bytes())
+            .map(|rec| OwnedAirportPrimaryRecord::from(&rec))
+            .collect();
+        assert_eq!(expected.len(), 2);
+        for capacity in [1, 7, 132, 8192] {
+            let reader = io::BufReader::with_capacity(capacity, buf.as_bytes());
+            let parsed: Vec<_> = parse_airport_primary_records_streaming(reader)
+                .collect::<io::Result<_>>()
+                .unwrap();
+            assert_eq!(parsed, expected, "capacity: {capacity}");
+        }
+        assert_eq!(
+            expected[1].as_record(),
+            parse_airport_primary_records(KSEA.as_bytes())
+                .next()
+                .unwrap()
+        );
+        assert_eq!(
+            parse_airport_primary_records_streaming(io::empty()).count(),
+            0
+        );
+
+        // the record read before the error is kept, nothing is read after it
+        let failing = io::Read::chain(&buf.as_bytes()[..buf.len() / 2], FailingReader);
+        let parsed: Vec<_> = parse_airport_primary_records_streaming(io::BufReader::new(failing))
+            .map(|rec| rec.map_err(|e| e.kind()))
+            .collect();
+        assert_eq!(
+            parsed,
+            vec![Ok(expected[0].clone()), Err(io::ErrorKind::Other)]
+        );
+    }
+
+    struct FailingReader;
+
+    impl io::Read for FailingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("broken pipe"))
+        }
+    }
+
+    #[test]
+    fn test_parse_airport_primary_records_with_index() {
+        let buf = format!("{KLAX}\r\nHDR01\n{KLAX}\n");
+        let parsed: Vec<_> = parse_airport_primary_records_with_index(buf.as_bytes()).collect();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].0, 0);
+        assert_eq!(parsed[1].0, KLAX.len() + 2 + 6);
+        assert_eq!(parsed[0].1, parsed[1].1);
+        assert_eq!(
+            parsed.into_iter().map(|(_, rec)| rec).collect::<Vec<_>>(),
+            parse_airport_primary_records(buf.as_bytes()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_parse_navaid_records() {
+        let lax = "SUSAD        LAX   K2011360\
+        VTHW N33560033W118255598LAX N33560033W118255598E0150001811     \
+        NARLOS ANGELES                   123451906";
+        let os = "SUSADBKSEAK1 OS    K1003290\
+        HLW  N47383935W122183891                       E0180           NAR\
+        BLAKO                         567891807";
+        let buf = format!("HDR01\r\n{KLAX}\r\n{lax}\r\n{os}\r\n");
+        assert_eq!(
+            parse_vhf_navaid_records(buf.as_bytes())
+                .map(|rec| rec.navaid_identifier)
+                .collect::<Vec<_>>(),
+            vec!["LAX"]
+        );
+        assert_eq!(
+            parse_ndb_navaid_records(buf.as_bytes())
+                .map(|rec| rec.ndb_identifier)
+                .collect::<Vec<_>>(),
+            vec!["OS"]
+        );
+    }
+
+    #[test]
+    fn test_parse_runway_records() {
+        let rw16c = "SUSAP KSEAK1GRW16C   009426\
+        1610 N47274586W122182009-0530          00429000052150 ISZI3              \
+        H                      123501807";
+        let rw34c = "SUSAP KSEAK1GRW34C   009426\
+        3410 N47255295W122181570+0530          00363050058150 IBEJ10600           \
+        DISPLACED THR         123511807";
+        let buf = format!("{KSEA}\n{rw16c}\n{rw34c}\n");
+        let runways: Vec<_> = parse_runway_records(buf.as_bytes()).collect();
+        assert_eq!(
+            runways
+                .iter()
+                .map(|rec| (rec.icao_identifier, rec.runway_identifier))
+                .collect::<Vec<_>>(),
+            vec![("KSEA", "RW16C"), ("KSEA", "RW34C")]
+        );
+        assert!(runways
+            .iter()
+            .all(|rec| rec.runway_length == 9426 && rec.runway_width == 150));
+        assert_eq!(parse_airport_primary_records(buf.as_bytes()).count(), 1);
+    }
+
+    #[test]
+    fn test_parse_terminal_waypoint_records() {
+        let alpse = "SUSAP KSEAK1CALPSE K10    C     \
+        N47351280W122182907                       E0160     NAR           \
+        ALPSE                    124001807";
+        let rw16c = "SUSAP KSEAK1GRW16C   009426\
+        1610 N47274586W122182009-0530          00429000052150 ISZI3              \
+        H                      123501807";
+        let buf = format!("{alpse}\n{rw16c}\n{alpse}");
+        assert_eq!(parse_terminal_waypoint_records(buf.as_bytes()).count(), 2);
+    }
+
+    #[test]
+    fn test_parse_airport_primary_records_counted() {
+        let continuation = format!("{}2{}", &KLAX[..21], &KLAX[22..]);
+        let broken = KLAX.replace("YHN3356", "YHQ3356");
+        let enroute = format!("{}E{}", &KLAX[..4], &KLAX[5..]);
+        let buf = format!("HDR01\n{KLAX}\n{continuation}\n{broken}\n{enroute}\n\n{KLAX}\n");
+
+        assert_eq!(
+            classify_airport_primary_records(buf.as_bytes())
+                .map(|rec| rec.map(|rec| rec.icao_identifier))
+                .collect::<Vec<_>>(),
+            vec![
+                Err(ParseError::InvalidSectionCode),
+                Ok("KLAX"),
+                Err(ParseError::ContinuationRecord),
+                Err(ParseError::InvalidCoordinate(
+                    FieldName::AirportReferencePointLatitude
+                )),
+                Err(ParseError::InvalidSectionCode),
+                Ok("KLAX"),
+            ]
+        );
+
+        let (recs, stats) = parse_airport_primary_records_counted(buf.as_bytes());
+        assert_eq!(recs.count(), 2);
+        assert_eq!(
+            stats,
+            ParseStats {
+                total_lines: 6,
+                valid_airport_pa_records: 2,
+                skipped_other_sections: 2,
+                skipped_continuation_records: 1,
+                failed_to_parse: 1,
+            }
+        );
+    }
 }
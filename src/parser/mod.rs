@@ -1,3 +1,7 @@
+pub mod csv;
+pub mod error;
 pub mod field;
 pub mod file;
+pub mod openflights;
 pub mod record;
+pub mod tsplib;
@@ -0,0 +1,107 @@
+use crate::model::Airport;
+use crate::parser::csv::{parse_coord, split_csv_record, CsvParseError, CsvParseErrorKind};
+use std::io;
+use std::io::BufRead;
+
+// columns of https://openflights.org/data.php#airport
+const NAME: (usize, &str) = (1, "name");
+const ICAO: (usize, &str) = (5, "icao");
+const LATITUDE: (usize, &str) = (6, "latitude");
+const LONGITUDE: (usize, &str) = (7, "longitude");
+const NULL: &str = "\\N";
+/// Optional header line, `airports.dat` itself has none
+pub const HEADER: &str =
+    "id,name,city,country,iata,icao,latitude,longitude,altitude,timezone,dst,tz,type,source";
+
+/// Parses OpenFlights `airports.dat`, skipping records without ICAO code and [`HEADER`]
+pub fn parse_openflights_csv(
+    reader: impl BufRead,
+) -> impl Iterator<Item = Result<Airport, CsvParseError>> {
+    reader
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| parse_openflights_record(i + 1, line).transpose())
+}
+
+fn parse_openflights_record(
+    line_no: usize,
+    line: io::Result<String>,
+) -> Result<Option<Airport>, CsvParseError> {
+    let line =
+        line.map_err(|e| CsvParseError::new(line_no, None, CsvParseErrorKind::Io(e.kind())))?;
+    if line.is_empty() || (line_no == 1 && line == HEADER) {
+        return Ok(None);
+    }
+    let fields = split_csv_record(&line)
+        .ok_or_else(|| CsvParseError::new(line_no, None, CsvParseErrorKind::UnterminatedQuote))?;
+    let field = |(idx, column): (usize, &'static str)| {
+        fields.get(idx).map(|field| field.as_ref()).ok_or_else(|| {
+            CsvParseError::new(line_no, Some(column), CsvParseErrorKind::MissingColumn)
+        })
+    };
+    let number = |column: (usize, &'static str)| {
+        field(column)?.parse::<f64>().map_err(|_| {
+            CsvParseError::new(line_no, Some(column.1), CsvParseErrorKind::InvalidNumber)
+        })
+    };
+
+    let icao = field(ICAO)?;
+    if icao.is_empty() || icao == NULL {
+        return Ok(None);
+    }
+    let name = field(NAME)?;
+    let coord = parse_coord(
+        line_no,
+        (number(LATITUDE)?, LATITUDE.1),
+        (number(LONGITUDE)?, LONGITUDE.1),
+    )?;
+    Ok(Some(Airport {
+        icao: icao.to_string(),
+        name: name.to_string(),
+        coord,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::field::coord::Coord;
+
+    #[test]
+    fn test_parse_openflights_csv() {
+        let data = "\
+1,\"Goroka Airport\",\"Goroka\",\"Papua New Guinea\",\"GKA\",\"AYGA\",-6.081689834590001,145.391998291,5282,10,\"U\",\"Pacific/Port_Moresby\",\"airport\",\"OurAirports\"
+5388,\"Kadena Air Base\",\"Okinawa\",\"Japan\",\"DNA\",\\N,26.3556,127.768,143,9,\"U\",\"Asia/Tokyo\",\"airport\",\"OurAirports\"
+
+3484,\"Los Angeles International Airport\",\"Los Angeles\",\"United States\",\"LAX\",\"KLAX\",33.94250107,-118.4079971,125,-8,\"A\",\"America/Los_Angeles\",\"airport\",\"OurAirports\"
+9,\"Broken\",\"Nowhere\",\"Nowhere\",\"\",\"XXXX\",91.0,0.0,0,0,\"U\",\"\",\"airport\",\"OurAirports\"
+10,\"Short\",\"Nowhere\"
+";
+        let parsed: Vec<_> = parse_openflights_csv(data.as_bytes()).collect();
+        assert_eq!(parsed.len(), 4);
+        let goroka = parsed[0].as_ref().unwrap();
+        assert_eq!(goroka.icao, "AYGA");
+        assert_eq!(goroka.name, "Goroka Airport");
+        assert_eq!(
+            goroka.coord,
+            Coord::try_from((-6.081689834590001, 145.391998291)).unwrap()
+        );
+        assert_eq!(parsed[1].as_ref().unwrap().icao, "KLAX");
+        assert_eq!(
+            parsed[2],
+            Err(CsvParseError::new(
+                5,
+                Some("latitude"),
+                CsvParseErrorKind::InvalidCoord
+            ))
+        );
+        assert_eq!(
+            parsed[3],
+            Err(CsvParseError::new(
+                6,
+                Some("icao"),
+                CsvParseErrorKind::MissingColumn
+            ))
+        );
+    }
+}
@@ -1,3 +1,8 @@
+pub mod navaid;
+pub mod runway;
+pub mod waypoint;
+
+use crate::parser::error::{FieldName, ParseError};
 use crate::parser::field::section_code::{parse_section_code, parse_subsection_code};
 use crate::parser::field::{
     parse_airport_elevation, parse_airport_name, parse_airport_reference_point_latitude,
@@ -13,61 +18,89 @@ use crate::types::field::section_code::{AirportSubsectionCode, EnrichedSectionCo
 use crate::types::record::AirportPrimaryRecord;
 use crate::util::{parse_blank, parse_blank_arr};
 
-const ENTRY_LEN: usize = 132;
+pub const ENTRY_LEN: usize = 132;
+
+pub fn parse_airport_primary_record(rec: &[u8]) -> Option<AirportPrimaryRecord<'_>> {
+    parse_airport_primary_record_checked(rec).ok()
+}
+
+/// Same as [`parse_airport_primary_record`], with the first field failed to parse
+pub fn parse_airport_primary_record_checked(
+    rec: &[u8],
+) -> Result<AirportPrimaryRecord<'_>, ParseError> {
+    use FieldName::*;
+    use ParseError::*;
 
-pub fn parse_airport_primary_record(rec: &[u8]) -> Option<AirportPrimaryRecord> {
     if rec.len() != ENTRY_LEN {
-        return None;
+        return Err(InvalidLength(rec.len()));
     }
-    let record_type = parse_record_type(rec[0])?; // 5.2
-    let customer_area_code = parse_customer_area_code(&rec[1..4])?; // 5.3
-    let section_code = parse_section_code(rec[4])?; // 5.4
+    let record_type = parse_record_type(rec[0]).ok_or(InvalidField(RecordType))?; // 5.2
+    let customer_area_code =
+        parse_customer_area_code(&rec[1..4]).ok_or(InvalidField(CustomerAreaCode))?; // 5.3
+    let section_code = parse_section_code(rec[4]).ok_or(InvalidSectionCode)?; // 5.4
     if section_code != SectionCode::Airport {
-        return None;
+        return Err(InvalidSectionCode);
     }
-    parse_blank(rec[5])?;
-    let icao_identifier = parse_icao_identifier(&rec[6..10])?; // 5.6
-    let mut icao_code = parse_icao_code(&rec[10..12])?; // 5.14
-    let enriched_section_code = parse_subsection_code(section_code, rec[12])?; // 5.5
+    parse_blank(rec[5]).ok_or(InvalidField(Blank))?;
+    let icao_identifier = parse_icao_identifier(&rec[6..10]).ok_or(InvalidField(IcaoIdentifier))?; // 5.6
+    let mut icao_code = parse_icao_code(&rec[10..12]).ok_or(InvalidField(IcaoCode))?; // 5.14
+    let enriched_section_code =
+        parse_subsection_code(section_code, rec[12]).ok_or(InvalidSubsectionCode)?; // 5.5
     if enriched_section_code != EnrichedSectionCode::Airport(AirportSubsectionCode::ReferencePoints)
     {
-        return None;
+        return Err(InvalidSubsectionCode);
     }
-    let ata_designator = parse_ata_designator(&rec[13..16])?; // 5.107
+    let ata_designator = parse_ata_designator(&rec[13..16]).ok_or(InvalidField(AtaDesignator))?; // 5.107
     let _reserved = &rec[16..18];
-    parse_blank_arr(&rec[18..21], 3..=3)?;
-    let continuation_record_number = parse_continuation_record_number(rec[21], true)?; // 5.16
-    if !(..=1).contains(&continuation_record_number) {
-        return None;
-    }
-    let speed_limit_altitude = parse_speed_limit_altitude(&rec[22..27])?; // 5.73
-    let longest_runway = parse_longest_runway(&rec[27..30])?; // 5.54
-    let ifr_capability = parse_ifr_capability(rec[30])?; // 5.108
-    let longest_runway_surface_code = parse_longest_runway_surface_code(rec[31])?; // 5.249
-    let airport_reference_point_latitude = parse_airport_reference_point_latitude(&rec[32..41])?; // 5.36
-    let airport_reference_point_longitude = parse_airport_reference_point_longitude(&rec[41..51])?; // 5.37
-    let magnetic_variation = parse_magnetic_variation(&rec[51..56])?; // 5.39
-    let airport_elevation = parse_airport_elevation(&rec[56..61])?; // 5.55
-    let speed_limit = parse_speed_limit(&rec[61..64])?; // 5.72
-    let recommended_navaid = parse_recommended_navaid(&rec[64..68])?; // 5.23
-    let icao_code2 = parse_icao_code(&rec[68..70])?; // 5.14
+    parse_blank_arr(&rec[18..21], 3..=3).ok_or(InvalidField(Blank))?;
+    let continuation_record_number = parse_continuation_record_number(rec[21], true)
+        .filter(|n| (..=1).contains(n))
+        .ok_or(InvalidField(ContinuationRecordNumber))?; // 5.16
+    let speed_limit_altitude =
+        parse_speed_limit_altitude(&rec[22..27]).ok_or(InvalidField(SpeedLimitAltitude))?; // 5.73
+    let longest_runway = parse_longest_runway(&rec[27..30]).ok_or(InvalidField(LongestRunway))?; // 5.54
+    let ifr_capability = parse_ifr_capability(rec[30]).ok_or(InvalidField(IfrCapability))?; // 5.108
+    let longest_runway_surface_code =
+        parse_longest_runway_surface_code(rec[31]).ok_or(InvalidField(LongestRunwaySurfaceCode))?; // 5.249
+    let airport_reference_point_latitude = parse_airport_reference_point_latitude(&rec[32..41])
+        .ok_or(InvalidCoordinate(AirportReferencePointLatitude))?; // 5.36
+    let airport_reference_point_longitude =
+        parse_airport_reference_point_longitude(&rec[41..51])
+            .ok_or(InvalidCoordinate(AirportReferencePointLongitude))?; // 5.37
+    let magnetic_variation =
+        parse_magnetic_variation(&rec[51..56]).ok_or(InvalidField(MagneticVariation))?; // 5.39
+    let airport_elevation =
+        parse_airport_elevation(&rec[56..61]).ok_or(InvalidField(AirportElevation))?; // 5.55
+    let speed_limit = parse_speed_limit(&rec[61..64]).ok_or(InvalidField(SpeedLimit))?; // 5.72
+    let recommended_navaid =
+        parse_recommended_navaid(&rec[64..68]).ok_or(InvalidField(RecommendedNavaid))?; // 5.23
+
+    // 5.14 ICAO Code in columns 69-70 qualifies the Recommended Navaid (5.23), it is expected to
+    // be the same region as the airport one in columns 11-12, which is substituted when blank
+    let icao_code2 = parse_icao_code(&rec[68..70]).ok_or(InvalidField(IcaoCode))?; // 5.14
     if !(icao_code.is_empty() || icao_code2.is_empty()) && icao_code != icao_code2 {
-        return None;
+        return Err(InvalidField(IcaoCode));
     } else if icao_code.is_empty() {
         icao_code = icao_code2;
     }
-    let transition_altitude = parse_transition_altitude(&rec[70..75])?; // 5.53
-    let transition_level = parse_transition_altitude(&rec[75..80])?; // 5.53
-    let public_military_indicator = parse_public_military_indicator(rec[80])?; // 5.177
-    let time_zone = parse_time_zone(&rec[81..84])?; // 5.178
-    let daylight_indicator = parse_daylight_indicator(rec[84])?; // 5.179
-    let magnetic_true_indicator = parse_magnetic_true_indicator(rec[85])?; // 5.165
-    let datum_code = parse_datum_code(&rec[86..89])?; //5.197
+    let transition_altitude =
+        parse_transition_altitude(&rec[70..75]).ok_or(InvalidField(TransitionAltitude))?; // 5.53
+    let transition_level =
+        parse_transition_altitude(&rec[75..80]).ok_or(InvalidField(TransitionLevel))?; // 5.53
+    let public_military_indicator =
+        parse_public_military_indicator(rec[80]).ok_or(InvalidField(PublicMilitaryIndicator))?; // 5.177
+    let time_zone = parse_time_zone(&rec[81..84]).ok_or(InvalidField(TimeZone))?; // 5.178
+    let daylight_indicator =
+        parse_daylight_indicator(rec[84]).ok_or(InvalidField(DaylightIndicator))?; // 5.179
+    let magnetic_true_indicator =
+        parse_magnetic_true_indicator(rec[85]).ok_or(InvalidField(MagneticTrueIndicator))?; // 5.165
+    let datum_code = parse_datum_code(&rec[86..89]).ok_or(InvalidField(DatumCode))?; //5.197
     let _reserved = &rec[89..93];
-    let airport_name = parse_airport_name(&rec[93..123])?; // 5.71
-    let file_record_number = parse_file_record_number(&rec[123..128])?; // 5.31
-    let cycle_date = parse_cycle_date(&rec[128..132])?; // 5.32
-    Some(AirportPrimaryRecord {
+    let airport_name = parse_airport_name(&rec[93..123]).ok_or(InvalidField(AirportName))?; // 5.71
+    let file_record_number =
+        parse_file_record_number(&rec[123..128]).ok_or(InvalidField(FileRecordNumber))?; // 5.31
+    let cycle_date = parse_cycle_date(&rec[128..132]).ok_or(InvalidCycleDate)?; // 5.32
+    Ok(AirportPrimaryRecord {
         record_type,
         customer_area_code,
         icao_identifier,
@@ -100,6 +133,7 @@ pub fn parse_airport_primary_record(rec: &[u8]) -> Option<AirportPrimaryRecord>
 
 #[cfg(test)]
 mod tests {
+    use crate::test_util::{KDEN, KJFK, KLAX, KSEA, KTPA};
     use std::str::FromStr;
 
     use rust_decimal::Decimal;
@@ -107,6 +141,7 @@ mod tests {
     use crate::types::field::coord::{
         Latitude, LatitudeHemisphere, Longitude, LongitudeHemisphere,
     };
+    use crate::types::field::icao_region::IcaoRegion;
     use crate::types::field::{
         CycleDate, MagneticTrueIndicator, MagneticVariation, PublicMilitaryIndicator, RecordType,
         RunwaySurfaceCode,
@@ -116,10 +151,8 @@ mod tests {
 
     #[test]
     fn parse_klax() {
-        let record = b"SUSAP KLAXK2ALAX     0     \
-        129YHN33563299W118242898E012000128         1800018000C    \
-        MNAR    LOS ANGELES INTL              310231906";
-        let parsed = parse_airport_primary_record(&record[..]).unwrap();
+        let record = KLAX.as_bytes();
+        let parsed = parse_airport_primary_record(record).unwrap();
         assert_eq!(
             parsed,
             AirportPrimaryRecord {
@@ -170,10 +203,8 @@ mod tests {
 
     #[test]
     fn parse_ksea() {
-        let record = b"SUSAP KSEAK1ASEA     0     \
-        119YHN47265960W122184240E016000432         1800018000C    \
-        MNAR    SEATTLE-TACOMA INTL           065001807";
-        let parsed = parse_airport_primary_record(&record[..]).unwrap();
+        let record = KSEA.as_bytes();
+        let parsed = parse_airport_primary_record(record).unwrap();
         assert_eq!(
             parsed,
             AirportPrimaryRecord {
@@ -224,10 +255,8 @@ mod tests {
 
     #[test]
     fn parse_kden() {
-        let record = b"SUSAP KDENK2ADEN     0     \
-        160YHN39514200W104402340E008005434         1800018000C    \
-        MNAR    DENVER INTL                   630481208";
-        let parsed = parse_airport_primary_record(&record[..]).unwrap();
+        let record = KDEN.as_bytes();
+        let parsed = parse_airport_primary_record(record).unwrap();
         assert_eq!(
             parsed,
             AirportPrimaryRecord {
@@ -278,10 +307,8 @@ mod tests {
 
     #[test]
     fn parse_kjfk() {
-        let record = b"SUSAP KJFKK6AJFK     0     \
-        145YHN40382374W073464329W013000013         1800018000C    \
-        MNAR    JOHN F KENNEDY INTL           257211912";
-        let parsed = parse_airport_primary_record(&record[..]).unwrap();
+        let record = KJFK.as_bytes();
+        let parsed = parse_airport_primary_record(record).unwrap();
         assert_eq!(
             parsed,
             AirportPrimaryRecord {
@@ -335,10 +362,8 @@ mod tests {
 
     #[test]
     fn parse_ktpa() {
-        let record = b"SUSAP KTPAK7ATPA     0     \
-        110YHN27583170W082315970W005000026         1800018000C    \
-        MNAR    TAMPA INTL                    267161101";
-        let parsed = parse_airport_primary_record(&record[..]).unwrap();
+        let record = KTPA.as_bytes();
+        let parsed = parse_airport_primary_record(record).unwrap();
         assert_eq!(
             parsed,
             AirportPrimaryRecord {
@@ -386,4 +411,130 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn parse_errors() {
+        let record = KLAX.as_bytes();
+        let with = |pos: usize, patch: &[u8]| {
+            let mut rec = record.to_vec();
+            rec[pos..pos + patch.len()].copy_from_slice(patch);
+            parse_airport_primary_record_checked(&rec).map(|_| ())
+        };
+        assert_eq!(
+            parse_airport_primary_record_checked(&record[1..]).map(|_| ()),
+            Err(ParseError::InvalidLength(131))
+        );
+        assert_eq!(with(4, b"X"), Err(ParseError::InvalidSectionCode));
+        assert_eq!(with(4, b"D"), Err(ParseError::InvalidSectionCode));
+        assert_eq!(with(12, b"G"), Err(ParseError::InvalidSubsectionCode));
+        assert_eq!(
+            with(41, b"X"),
+            Err(ParseError::InvalidCoordinate(
+                FieldName::AirportReferencePointLongitude
+            ))
+        );
+        assert_eq!(with(128, b"1X"), Err(ParseError::InvalidCycleDate));
+        assert_eq!(
+            with(21, b"2"),
+            Err(ParseError::InvalidField(
+                FieldName::ContinuationRecordNumber
+            ))
+        );
+        assert_eq!(
+            with(68, b"K3"),
+            Err(ParseError::InvalidField(FieldName::IcaoCode))
+        );
+        assert_eq!(with(0, b"S"), Ok(()));
+    }
+
+    #[test]
+    fn country_code() {
+        let record = KLAX.as_bytes();
+        let parsed = parse_airport_primary_record(record).unwrap();
+        assert_eq!(parsed.country_code(), Some(IcaoRegion::NorthAmerica));
+        for (icao_identifier, region) in [
+            ("EGLL", Some(IcaoRegion::Europe)),
+            ("YSSY", Some(IcaoRegion::SouthwestPacific)),
+            ("1234", None),
+            ("", None),
+        ] {
+            let rec = AirportPrimaryRecord {
+                icao_identifier,
+                ..parsed
+            };
+            assert_eq!(rec.country_code(), region, "{icao_identifier}");
+        }
+    }
+
+    #[test]
+    fn full_icao_code() {
+        let record = KLAX.as_bytes();
+        let parsed = parse_airport_primary_record(record).unwrap();
+        assert_eq!(parsed.full_icao_code(), "KLAX/K2");
+        let rec = AirportPrimaryRecord {
+            icao_code: "",
+            ..parsed
+        };
+        assert_eq!(rec.full_icao_code(), "KLAX");
+    }
+
+    #[test]
+    fn display() {
+        let record = KLAX.as_bytes();
+        let parsed = parse_airport_primary_record(record).unwrap();
+        assert_eq!(
+            format!("{parsed}"),
+            "KLAX (LOS ANGELES INTL) @ 33°56′32.99″N 118°24′28.98″W elev=128ft"
+        );
+        assert_eq!(format!("{}", parsed.cycle_date), "AIRAC 1906");
+
+        let record = KSEA.as_bytes();
+        let parsed = parse_airport_primary_record(record).unwrap();
+        assert_eq!(
+            format!("{parsed}"),
+            "KSEA (SEATTLE-TACOMA INTL) @ 47°26′59.60″N 122°18′42.40″W elev=432ft"
+        );
+        assert_eq!(format!("{}", parsed.cycle_date), "AIRAC 1807");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        use crate::types::record::OwnedAirportPrimaryRecord;
+
+        let record = KSEA.as_bytes();
+        let parsed = parse_airport_primary_record(record).unwrap();
+        let json = serde_json::to_string(&parsed).unwrap();
+        let restored: AirportPrimaryRecord = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, parsed);
+
+        let owned = OwnedAirportPrimaryRecord::from(&parsed);
+        let json = serde_json::to_string(&owned).unwrap();
+        let restored: OwnedAirportPrimaryRecord = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, owned);
+        assert_eq!(restored.as_record(), parsed);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn local_time() {
+        use crate::types::field::TimeZone;
+        use chrono::{TimeZone as _, Utc};
+
+        let record = KLAX.as_bytes();
+        let parsed = parse_airport_primary_record(record).unwrap();
+        let utc = Utc.with_ymd_and_hms(2019, 6, 1, 12, 0, 0).unwrap();
+        assert_eq!(parsed.local_time(utc), None);
+
+        let rec = AirportPrimaryRecord {
+            time_zone: Some(TimeZone {
+                hour: -8,
+                minute: 0,
+            }),
+            ..parsed
+        };
+        let local = rec.local_time(utc).unwrap();
+        assert_eq!(local, utc);
+        assert_eq!(local.to_rfc3339(), "2019-06-01T04:00:00-08:00");
+    }
 }
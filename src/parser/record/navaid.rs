@@ -0,0 +1,386 @@
+use crate::parser::field::section_code::{parse_section_code, parse_subsection_code};
+use crate::parser::field::{
+    parse_airport_reference_point_latitude, parse_airport_reference_point_longitude,
+    parse_continuation_record_number, parse_customer_area_code, parse_cycle_date, parse_datum_code,
+    parse_file_record_number, parse_icao_code, parse_icao_identifier, parse_magnetic_variation,
+    parse_navaid_identifier, parse_navaid_name, parse_ndb_frequency, parse_ndb_navaid_class,
+    parse_optional_elevation, parse_optional_latitude, parse_optional_longitude,
+    parse_optional_navaid_identifier, parse_record_type, parse_station_declination,
+    parse_vhf_frequency, parse_vhf_navaid_class,
+};
+use crate::parser::record::ENTRY_LEN;
+use crate::types::field::section_code::{EnrichedSectionCode, NavaidSubsectionCode, SectionCode};
+use crate::types::record::navaid::{NdbNavaidRecord, VhfNavaidRecord};
+use crate::util::{parse_blank, parse_blank_arr};
+
+pub fn parse_vhf_navaid_record(rec: &[u8]) -> Option<VhfNavaidRecord<'_>> {
+    if rec.len() != ENTRY_LEN {
+        return None;
+    }
+    let record_type = parse_record_type(rec[0])?; // 5.2
+    let customer_area_code = parse_customer_area_code(&rec[1..4])?; // 5.3
+    let section_code = parse_section_code(rec[4])?; // 5.4
+    if section_code != SectionCode::Navaid {
+        return None;
+    }
+    let enriched_section_code = parse_subsection_code(section_code, rec[5])?; // 5.5
+    if enriched_section_code != EnrichedSectionCode::Navaid(NavaidSubsectionCode::VhfNavaid) {
+        return None;
+    }
+    let icao_identifier = parse_icao_identifier(&rec[6..10])?; // 5.6
+    let _airport_icao_code = parse_icao_code(&rec[10..12])?; // 5.14
+    parse_blank(rec[12])?;
+    let navaid_identifier = parse_navaid_identifier(&rec[13..17])?; // 5.33
+    parse_blank_arr(&rec[17..19], 2..=2)?;
+    let icao_code = parse_icao_code(&rec[19..21])?; // 5.14
+    let continuation_record_number = parse_continuation_record_number(rec[21], true)?; // 5.16
+    if !(..=1).contains(&continuation_record_number) {
+        return None;
+    }
+    let frequency = parse_vhf_frequency(&rec[22..27])?; // 5.34
+    let (navaid_type, class) = parse_vhf_navaid_class(&rec[27..32])?; // 5.35
+    let vor_latitude = parse_optional_latitude(&rec[32..41])?; // 5.36
+    let vor_longitude = parse_optional_longitude(&rec[41..51])?; // 5.37
+    let dme_identifier = parse_optional_navaid_identifier(&rec[51..55])?; // 5.38
+    let dme_latitude = parse_optional_latitude(&rec[55..64])?; // 5.36
+    let dme_longitude = parse_optional_longitude(&rec[64..74])?; // 5.37
+    let magnetic_variation = parse_station_declination(&rec[74..79])?; // 5.66
+    let elevation = parse_optional_elevation(&rec[79..84])?; // 5.40
+    let _figure_of_merit = rec[84]; // 5.149
+    let _ils_dme_bias = &rec[85..87]; // 5.90
+    let _frequency_protection = &rec[87..90]; // 5.150
+    let datum_code = parse_datum_code(&rec[90..93])?; // 5.197
+    let navaid_name = parse_navaid_name(&rec[93..123])?; // 5.71
+    let file_record_number = parse_file_record_number(&rec[123..128])?; // 5.31
+    let cycle_date = parse_cycle_date(&rec[128..132])?; // 5.32
+    Some(VhfNavaidRecord {
+        record_type,
+        customer_area_code,
+        enriched_section_code,
+        icao_identifier,
+        navaid_identifier,
+        icao_code,
+        continuation_record_number,
+        frequency,
+        navaid_type,
+        class,
+        vor_latitude,
+        vor_longitude,
+        dme_identifier,
+        dme_latitude,
+        dme_longitude,
+        magnetic_variation,
+        elevation,
+        datum_code,
+        navaid_name,
+        file_record_number,
+        cycle_date,
+    })
+}
+
+pub fn parse_ndb_navaid_record(rec: &[u8]) -> Option<NdbNavaidRecord<'_>> {
+    if rec.len() != ENTRY_LEN {
+        return None;
+    }
+    let record_type = parse_record_type(rec[0])?; // 5.2
+    let customer_area_code = parse_customer_area_code(&rec[1..4])?; // 5.3
+    let section_code = parse_section_code(rec[4])?; // 5.4
+    if section_code != SectionCode::Navaid {
+        return None;
+    }
+    let enriched_section_code = parse_subsection_code(section_code, rec[5])?; // 5.5
+    if enriched_section_code != EnrichedSectionCode::Navaid(NavaidSubsectionCode::NdbNavaid) {
+        return None;
+    }
+    let icao_identifier = parse_icao_identifier(&rec[6..10])?; // 5.6
+    let _airport_icao_code = parse_icao_code(&rec[10..12])?; // 5.14
+    parse_blank(rec[12])?;
+    let ndb_identifier = parse_navaid_identifier(&rec[13..17])?; // 5.33
+    parse_blank_arr(&rec[17..19], 2..=2)?;
+    let icao_code = parse_icao_code(&rec[19..21])?; // 5.14
+    let continuation_record_number = parse_continuation_record_number(rec[21], true)?; // 5.16
+    if !(..=1).contains(&continuation_record_number) {
+        return None;
+    }
+    let ndb_frequency = parse_ndb_frequency(&rec[22..27])?; // 5.34
+    let ndb_class = parse_ndb_navaid_class(&rec[27..32])?; // 5.35
+    let latitude = parse_airport_reference_point_latitude(&rec[32..41])?; // 5.36
+    let longitude = parse_airport_reference_point_longitude(&rec[41..51])?; // 5.37
+    parse_blank_arr(&rec[51..74], 23..=23)?;
+    let magnetic_variation = parse_magnetic_variation(&rec[74..79])?; // 5.39
+    parse_blank_arr(&rec[79..85], 6..=6)?;
+    let _reserved = &rec[85..90];
+    let datum_code = parse_datum_code(&rec[90..93])?; // 5.197
+    let navaid_name = parse_navaid_name(&rec[93..123])?; // 5.71
+    let file_record_number = parse_file_record_number(&rec[123..128])?; // 5.31
+    let cycle_date = parse_cycle_date(&rec[128..132])?; // 5.32
+    Some(NdbNavaidRecord {
+        record_type,
+        customer_area_code,
+        enriched_section_code,
+        icao_identifier,
+        ndb_identifier,
+        icao_code,
+        continuation_record_number,
+        ndb_frequency,
+        ndb_class,
+        latitude,
+        longitude,
+        magnetic_variation,
+        datum_code,
+        navaid_name,
+        file_record_number,
+        cycle_date,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use rust_decimal::Decimal;
+
+    use crate::types::field::coord::{
+        Latitude, LatitudeHemisphere, Longitude, LongitudeHemisphere,
+    };
+    use crate::types::field::{CycleDate, MagneticVariation, RecordType, VhfNavaidType};
+
+    use super::*;
+
+    #[test]
+    fn parse_lax_vortac() {
+        let record = b"SUSAD        LAX   K2011360\
+        VTHW N33560033W118255598LAX N33560033W118255598E0150001811     \
+        NARLOS ANGELES                   123451906";
+        let latitude = Latitude {
+            hemisphere: LatitudeHemisphere::North,
+            degrees: 33,
+            minutes: 56,
+            seconds: 0,
+            fractional_seconds: 33,
+        };
+        let longitude = Longitude {
+            hemisphere: LongitudeHemisphere::West,
+            degrees: 118,
+            minutes: 25,
+            seconds: 55,
+            fractional_seconds: 98,
+        };
+        let parsed = parse_vhf_navaid_record(&record[..]).unwrap();
+        assert_eq!(
+            parsed,
+            VhfNavaidRecord {
+                record_type: RecordType::Standard,
+                customer_area_code: "USA",
+                enriched_section_code: EnrichedSectionCode::Navaid(NavaidSubsectionCode::VhfNavaid),
+                icao_identifier: "",
+                navaid_identifier: "LAX",
+                icao_code: "K2",
+                continuation_record_number: 0,
+                frequency: 1_136_000,
+                navaid_type: VhfNavaidType::Vortac,
+                class: "VTHW ",
+                vor_latitude: Some(latitude),
+                vor_longitude: Some(longitude),
+                dme_identifier: Some("LAX"),
+                dme_latitude: Some(latitude),
+                dme_longitude: Some(longitude),
+                magnetic_variation: Some(MagneticVariation::East(Decimal::from_str("15").unwrap())),
+                elevation: Some(181),
+                datum_code: "NAR",
+                navaid_name: "LOS ANGELES",
+                file_record_number: 12345,
+                cycle_date: CycleDate { year: 19, cycle: 6 },
+            }
+        );
+    }
+
+    #[test]
+    fn parse_ksea_ils_dme() {
+        let record = b"SUSAD KSEAK1 ISEA  K1011090 \
+        IT                     ISEAN47263963W122181392E0200003800     \
+        NARSEATTLE-TACOMA                234561807";
+        let parsed = parse_vhf_navaid_record(&record[..]).unwrap();
+        assert_eq!(
+            parsed,
+            VhfNavaidRecord {
+                record_type: RecordType::Standard,
+                customer_area_code: "USA",
+                enriched_section_code: EnrichedSectionCode::Navaid(NavaidSubsectionCode::VhfNavaid),
+                icao_identifier: "KSEA",
+                navaid_identifier: "ISEA",
+                icao_code: "K1",
+                continuation_record_number: 0,
+                frequency: 1_109_000,
+                navaid_type: VhfNavaidType::IlsDme,
+                class: " IT  ",
+                vor_latitude: None,
+                vor_longitude: None,
+                dme_identifier: Some("ISEA"),
+                dme_latitude: Some(Latitude {
+                    hemisphere: LatitudeHemisphere::North,
+                    degrees: 47,
+                    minutes: 26,
+                    seconds: 39,
+                    fractional_seconds: 63,
+                }),
+                dme_longitude: Some(Longitude {
+                    hemisphere: LongitudeHemisphere::West,
+                    degrees: 122,
+                    minutes: 18,
+                    seconds: 13,
+                    fractional_seconds: 92,
+                }),
+                magnetic_variation: Some(MagneticVariation::East(Decimal::from_str("20").unwrap())),
+                elevation: Some(380),
+                datum_code: "NAR",
+                navaid_name: "SEATTLE-TACOMA",
+                file_record_number: 23456,
+                cycle_date: CycleDate { year: 18, cycle: 7 },
+            }
+        );
+    }
+
+    #[test]
+    fn parse_olm_vor() {
+        let record = b"SUSAD        OLM   K1011320\
+        V LW N46581370W122540496                       E0200     1     \
+        NAROLYMPIA                       345671807";
+        let parsed = parse_vhf_navaid_record(&record[..]).unwrap();
+        assert_eq!(
+            parsed,
+            VhfNavaidRecord {
+                record_type: RecordType::Standard,
+                customer_area_code: "USA",
+                enriched_section_code: EnrichedSectionCode::Navaid(NavaidSubsectionCode::VhfNavaid),
+                icao_identifier: "",
+                navaid_identifier: "OLM",
+                icao_code: "K1",
+                continuation_record_number: 0,
+                frequency: 1_132_000,
+                navaid_type: VhfNavaidType::Vor,
+                class: "V LW ",
+                vor_latitude: Some(Latitude {
+                    hemisphere: LatitudeHemisphere::North,
+                    degrees: 46,
+                    minutes: 58,
+                    seconds: 13,
+                    fractional_seconds: 70,
+                }),
+                vor_longitude: Some(Longitude {
+                    hemisphere: LongitudeHemisphere::West,
+                    degrees: 122,
+                    minutes: 54,
+                    seconds: 4,
+                    fractional_seconds: 96,
+                }),
+                dme_identifier: None,
+                dme_latitude: None,
+                dme_longitude: None,
+                magnetic_variation: Some(MagneticVariation::East(Decimal::from_str("20").unwrap())),
+                elevation: None,
+                datum_code: "NAR",
+                navaid_name: "OLYMPIA",
+                file_record_number: 34567,
+                cycle_date: CycleDate { year: 18, cycle: 7 },
+            }
+        );
+    }
+
+    #[test]
+    fn parse_ywg_ndb() {
+        let record = b"SCANDB       YWG   CY002480\
+        HW   N49533694W097235700                       E0030           NAR\
+        WINNIPEG                      456781903";
+        let parsed = parse_ndb_navaid_record(&record[..]).unwrap();
+        assert_eq!(
+            parsed,
+            NdbNavaidRecord {
+                record_type: RecordType::Standard,
+                customer_area_code: "CAN",
+                enriched_section_code: EnrichedSectionCode::Navaid(NavaidSubsectionCode::NdbNavaid),
+                icao_identifier: "",
+                ndb_identifier: "YWG",
+                icao_code: "CY",
+                continuation_record_number: 0,
+                ndb_frequency: 248,
+                ndb_class: "HW   ",
+                latitude: Latitude {
+                    hemisphere: LatitudeHemisphere::North,
+                    degrees: 49,
+                    minutes: 53,
+                    seconds: 36,
+                    fractional_seconds: 94,
+                },
+                longitude: Longitude {
+                    hemisphere: LongitudeHemisphere::West,
+                    degrees: 97,
+                    minutes: 23,
+                    seconds: 57,
+                    fractional_seconds: 0,
+                },
+                magnetic_variation: MagneticVariation::East(Decimal::from_str("3").unwrap()),
+                datum_code: "NAR",
+                navaid_name: "WINNIPEG",
+                file_record_number: 45678,
+                cycle_date: CycleDate { year: 19, cycle: 3 },
+            }
+        );
+    }
+
+    #[test]
+    fn parse_ksea_ndb() {
+        let record = b"SUSADBKSEAK1 OS    K1003290\
+        HLW  N47383935W122183891                       E0180           NAR\
+        BLAKO                         567891807";
+        let parsed = parse_ndb_navaid_record(&record[..]).unwrap();
+        assert_eq!(
+            parsed,
+            NdbNavaidRecord {
+                record_type: RecordType::Standard,
+                customer_area_code: "USA",
+                enriched_section_code: EnrichedSectionCode::Navaid(NavaidSubsectionCode::NdbNavaid),
+                icao_identifier: "KSEA",
+                ndb_identifier: "OS",
+                icao_code: "K1",
+                continuation_record_number: 0,
+                ndb_frequency: 329,
+                ndb_class: "HLW  ",
+                latitude: Latitude {
+                    hemisphere: LatitudeHemisphere::North,
+                    degrees: 47,
+                    minutes: 38,
+                    seconds: 39,
+                    fractional_seconds: 35,
+                },
+                longitude: Longitude {
+                    hemisphere: LongitudeHemisphere::West,
+                    degrees: 122,
+                    minutes: 18,
+                    seconds: 38,
+                    fractional_seconds: 91,
+                },
+                magnetic_variation: MagneticVariation::East(Decimal::from_str("18").unwrap()),
+                datum_code: "NAR",
+                navaid_name: "BLAKO",
+                file_record_number: 56789,
+                cycle_date: CycleDate { year: 18, cycle: 7 },
+            }
+        );
+        assert_eq!(parse_vhf_navaid_record(&record[..]), None);
+    }
+
+    #[test]
+    fn reject_other_records() {
+        let record = b"SUSAD        LAX   K2011360\
+        VTHW N33560033W118255598LAX N33560033W118255598E0150001811     \
+        NARLOS ANGELES                   123451906";
+        let ndb = [&record[..5], b"B", &record[6..]].concat();
+        assert_eq!(parse_vhf_navaid_record(&ndb), None);
+        let continuation = [&record[..21], b"2", &record[22..]].concat();
+        assert_eq!(parse_vhf_navaid_record(&continuation), None);
+        let unknown_class = [&record[..27], b"X", &record[28..]].concat();
+        assert_eq!(parse_vhf_navaid_record(&unknown_class), None);
+        assert_eq!(parse_vhf_navaid_record(&record[..131]), None);
+    }
+}
@@ -0,0 +1,156 @@
+use crate::parser::field::section_code::{parse_section_code, parse_subsection_code};
+use crate::parser::field::{
+    parse_airport_elevation, parse_airport_reference_point_latitude,
+    parse_airport_reference_point_longitude, parse_continuation_record_number,
+    parse_customer_area_code, parse_cycle_date, parse_displaced_threshold_distance,
+    parse_ellipsoidal_height, parse_file_record_number, parse_icao_code, parse_icao_identifier,
+    parse_record_type, parse_runway_description, parse_runway_gradient, parse_runway_identifier,
+    parse_runway_length, parse_runway_width,
+};
+use crate::parser::record::ENTRY_LEN;
+use crate::types::field::section_code::{AirportSubsectionCode, EnrichedSectionCode, SectionCode};
+use crate::types::record::runway::RunwayRecord;
+use crate::util::{parse_blank, parse_blank_arr};
+
+pub fn parse_runway_record(rec: &[u8]) -> Option<RunwayRecord<'_>> {
+    if rec.len() != ENTRY_LEN {
+        return None;
+    }
+    let record_type = parse_record_type(rec[0])?; // 5.2
+    let customer_area_code = parse_customer_area_code(&rec[1..4])?; // 5.3
+    let section_code = parse_section_code(rec[4])?; // 5.4
+    if section_code != SectionCode::Airport {
+        return None;
+    }
+    parse_blank(rec[5])?;
+    let icao_identifier = parse_icao_identifier(&rec[6..10])?; // 5.6
+    let icao_code = parse_icao_code(&rec[10..12])?; // 5.14
+    let enriched_section_code = parse_subsection_code(section_code, rec[12])?; // 5.5
+    if enriched_section_code != EnrichedSectionCode::Airport(AirportSubsectionCode::Runways) {
+        return None;
+    }
+    let runway_identifier = parse_runway_identifier(&rec[13..18])?; // 5.46
+    parse_blank_arr(&rec[18..21], 3..=3)?;
+    let continuation_record_number = parse_continuation_record_number(rec[21], true)?; // 5.16
+    if !(..=1).contains(&continuation_record_number) {
+        return None;
+    }
+    let runway_length = parse_runway_length(&rec[22..27])?; // 5.57
+    let _runway_magnetic_bearing = &rec[27..31]; // 5.58
+    parse_blank(rec[31])?;
+    let threshold_latitude = parse_airport_reference_point_latitude(&rec[32..41])?; // 5.36
+    let threshold_longitude = parse_airport_reference_point_longitude(&rec[41..51])?; // 5.37
+    let runway_gradient = parse_runway_gradient(&rec[51..56])?; // 5.212
+    parse_blank_arr(&rec[56..60], 4..=4)?;
+    let threshold_elevation = parse_ellipsoidal_height(&rec[60..66])?; // 5.225
+    let landing_threshold_elevation = parse_airport_elevation(&rec[66..71])?; // 5.68
+    let displaced_threshold_distance = parse_displaced_threshold_distance(&rec[71..75])?; // 5.69
+    let _threshold_crossing_height = &rec[75..77]; // 5.67
+    let runway_width = parse_runway_width(&rec[77..80])?; // 5.109
+    let _tch_value_indicator = rec[80]; // 5.270
+    let _localizer_identifiers = &rec[81..95]; // 5.44, 5.80, 5.79
+
+    // runway primary records have no surface code, column 101 is reserved as well
+    let _reserved = &rec[95..101];
+    let runway_description = parse_runway_description(&rec[101..123])?; // 5.59
+    let file_record_number = parse_file_record_number(&rec[123..128])?; // 5.31
+    let cycle_date = parse_cycle_date(&rec[128..132])?; // 5.32
+    Some(RunwayRecord {
+        record_type,
+        customer_area_code,
+        icao_identifier,
+        icao_code,
+        enriched_section_code,
+        runway_identifier,
+        continuation_record_number,
+        runway_length,
+        runway_gradient,
+        runway_width,
+        threshold_latitude,
+        threshold_longitude,
+        threshold_elevation,
+        landing_threshold_elevation,
+        displaced_threshold_distance,
+        runway_description,
+        file_record_number,
+        cycle_date,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::field::coord::{
+        Latitude, LatitudeHemisphere, Longitude, LongitudeHemisphere,
+    };
+    use crate::types::field::{CycleDate, RecordType};
+
+    use super::*;
+
+    #[test]
+    fn parse_ksea_16c() {
+        let record = b"SUSAP KSEAK1GRW16C   009426\
+        1610 N47274586W122182009-0530          00429000052150 ISZI3              \
+        H                      123501807";
+        let parsed = parse_runway_record(&record[..]).unwrap();
+        assert_eq!(
+            parsed,
+            RunwayRecord {
+                record_type: RecordType::Standard,
+                customer_area_code: "USA",
+                icao_identifier: "KSEA",
+                icao_code: "K1",
+                enriched_section_code: EnrichedSectionCode::Airport(AirportSubsectionCode::Runways),
+                runway_identifier: "RW16C",
+                continuation_record_number: 0,
+                runway_length: 9426,
+                runway_gradient: Some(-530),
+                runway_width: 150,
+                threshold_latitude: Latitude {
+                    hemisphere: LatitudeHemisphere::North,
+                    degrees: 47,
+                    minutes: 27,
+                    seconds: 45,
+                    fractional_seconds: 86,
+                },
+                threshold_longitude: Longitude {
+                    hemisphere: LongitudeHemisphere::West,
+                    degrees: 122,
+                    minutes: 18,
+                    seconds: 20,
+                    fractional_seconds: 9,
+                },
+                threshold_elevation: None,
+                landing_threshold_elevation: 429,
+                displaced_threshold_distance: 0,
+                runway_description: "",
+                file_record_number: 12350,
+                cycle_date: CycleDate { year: 18, cycle: 7 },
+            }
+        );
+    }
+
+    #[test]
+    fn parse_ksea_34c() {
+        let record = b"SUSAP KSEAK1GRW34C   009426\
+        3410 N47255295W122181570+0530          00363050058150 IBEJ10600           \
+        DISPLACED THR         123511807";
+        let parsed = parse_runway_record(&record[..]).unwrap();
+        assert_eq!(parsed.runway_identifier, "RW34C");
+        assert_eq!(parsed.runway_gradient, Some(530));
+        assert_eq!(parsed.landing_threshold_elevation, 363);
+        assert_eq!(parsed.displaced_threshold_distance, 500);
+        assert_eq!(parsed.runway_description, "DISPLACED THR");
+
+        let with_height = [&record[..60], b"+01234", &record[66..]].concat();
+        assert_eq!(
+            parse_runway_record(&with_height)
+                .unwrap()
+                .threshold_elevation,
+            Some(1234)
+        );
+        let bad_identifier = [&record[..13], b"RX34C", &record[18..]].concat();
+        assert_eq!(parse_runway_record(&bad_identifier), None);
+        let airport = [&record[..12], b"A", &record[13..]].concat();
+        assert_eq!(parse_runway_record(&airport), None);
+    }
+}
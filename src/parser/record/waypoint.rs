@@ -0,0 +1,220 @@
+use crate::parser::field::section_code::{parse_section_code, parse_subsection_code};
+use crate::parser::field::{
+    parse_airport_reference_point_latitude, parse_airport_reference_point_longitude,
+    parse_continuation_record_number, parse_customer_area_code, parse_cycle_date, parse_datum_code,
+    parse_file_record_number, parse_icao_code, parse_icao_identifier, parse_magnetic_variation,
+    parse_record_type, parse_waypoint_identifier, parse_waypoint_name, parse_waypoint_type,
+    parse_waypoint_usage,
+};
+use crate::parser::record::ENTRY_LEN;
+use crate::types::field::section_code::{AirportSubsectionCode, EnrichedSectionCode, SectionCode};
+use crate::types::record::waypoint::TerminalWaypointRecord;
+use crate::util::{parse_blank, parse_blank_arr};
+
+pub fn parse_terminal_waypoint_record(rec: &[u8]) -> Option<TerminalWaypointRecord<'_>> {
+    if rec.len() != ENTRY_LEN {
+        return None;
+    }
+    let record_type = parse_record_type(rec[0])?; // 5.2
+    let customer_area_code = parse_customer_area_code(&rec[1..4])?; // 5.3
+    let section_code = parse_section_code(rec[4])?; // 5.4
+    if section_code != SectionCode::Airport {
+        return None;
+    }
+    parse_blank(rec[5])?;
+    let icao_identifier = parse_icao_identifier(&rec[6..10])?; // 5.6
+    let _airport_icao_code = parse_icao_code(&rec[10..12])?; // 5.14
+    let enriched_section_code = parse_subsection_code(section_code, rec[12])?; // 5.5
+    if enriched_section_code
+        != EnrichedSectionCode::Airport(AirportSubsectionCode::TerminalWaypoints)
+    {
+        return None;
+    }
+    let waypoint_identifier = parse_waypoint_identifier(&rec[13..18])?; // 5.13
+    parse_blank(rec[18])?;
+    let icao_code = parse_icao_code(&rec[19..21])?; // 5.14
+    let continuation_record_number = parse_continuation_record_number(rec[21], true)?; // 5.16
+    if !(..=1).contains(&continuation_record_number) {
+        return None;
+    }
+    parse_blank_arr(&rec[22..26], 4..=4)?;
+    let waypoint_type = parse_waypoint_type(&rec[26..29])?; // 5.42
+    let waypoint_usage = parse_waypoint_usage(&rec[29..31])?; // 5.82
+    parse_blank(rec[31])?;
+    let latitude = parse_airport_reference_point_latitude(&rec[32..41])?; // 5.36
+    let longitude = parse_airport_reference_point_longitude(&rec[41..51])?; // 5.37
+    parse_blank_arr(&rec[51..74], 23..=23)?;
+    let magnetic_variation = parse_magnetic_variation(&rec[74..79])?; // 5.39
+    let _reserved = &rec[79..84];
+    let datum_code = parse_datum_code(&rec[84..87])?; // 5.197
+    let _reserved = &rec[87..95];
+    let _name_format_indicator = &rec[95..98]; // 5.196
+    let waypoint_name = parse_waypoint_name(&rec[98..123])?; // 5.43
+    let file_record_number = parse_file_record_number(&rec[123..128])?; // 5.31
+    let cycle_date = parse_cycle_date(&rec[128..132])?; // 5.32
+    Some(TerminalWaypointRecord {
+        record_type,
+        customer_area_code,
+        icao_identifier,
+        enriched_section_code,
+        waypoint_identifier,
+        icao_code,
+        continuation_record_number,
+        waypoint_type,
+        waypoint_usage,
+        latitude,
+        longitude,
+        magnetic_variation,
+        datum_code,
+        waypoint_name,
+        file_record_number,
+        cycle_date,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use rust_decimal::Decimal;
+
+    use crate::types::field::coord::{
+        Latitude, LatitudeHemisphere, Longitude, LongitudeHemisphere,
+    };
+    use crate::types::field::{CycleDate, MagneticVariation, RecordType};
+
+    use super::*;
+
+    #[test]
+    fn parse_ksea_alpse() {
+        let record = b"SUSAP KSEAK1CALPSE K10    C     \
+        N47351280W122182907                       E0160     NAR           \
+        ALPSE                    124001807";
+        let parsed = parse_terminal_waypoint_record(&record[..]).unwrap();
+        assert_eq!(
+            parsed,
+            TerminalWaypointRecord {
+                record_type: RecordType::Standard,
+                customer_area_code: "USA",
+                icao_identifier: "KSEA",
+                enriched_section_code: EnrichedSectionCode::Airport(
+                    AirportSubsectionCode::TerminalWaypoints
+                ),
+                waypoint_identifier: "ALPSE",
+                icao_code: "K1",
+                continuation_record_number: 0,
+                waypoint_type: "C  ",
+                waypoint_usage: "  ",
+                latitude: Latitude {
+                    hemisphere: LatitudeHemisphere::North,
+                    degrees: 47,
+                    minutes: 35,
+                    seconds: 12,
+                    fractional_seconds: 80,
+                },
+                longitude: Longitude {
+                    hemisphere: LongitudeHemisphere::West,
+                    degrees: 122,
+                    minutes: 18,
+                    seconds: 29,
+                    fractional_seconds: 7,
+                },
+                magnetic_variation: MagneticVariation::East(Decimal::from_str("16").unwrap()),
+                datum_code: "NAR",
+                waypoint_name: "ALPSE",
+                file_record_number: 12400,
+                cycle_date: CycleDate { year: 18, cycle: 7 },
+            }
+        );
+    }
+
+    #[test]
+    fn parse_ksea_rw16c() {
+        let record = b"SUSAP KSEAK1CRW16C K10    G     \
+        N47274586W122182009                       E0160     NAR           \
+        RW16C                    124011807";
+        let parsed = parse_terminal_waypoint_record(&record[..]).unwrap();
+        assert_eq!(
+            parsed,
+            TerminalWaypointRecord {
+                record_type: RecordType::Standard,
+                customer_area_code: "USA",
+                icao_identifier: "KSEA",
+                enriched_section_code: EnrichedSectionCode::Airport(
+                    AirportSubsectionCode::TerminalWaypoints
+                ),
+                waypoint_identifier: "RW16C",
+                icao_code: "K1",
+                continuation_record_number: 0,
+                waypoint_type: "G  ",
+                waypoint_usage: "  ",
+                latitude: Latitude {
+                    hemisphere: LatitudeHemisphere::North,
+                    degrees: 47,
+                    minutes: 27,
+                    seconds: 45,
+                    fractional_seconds: 86,
+                },
+                longitude: Longitude {
+                    hemisphere: LongitudeHemisphere::West,
+                    degrees: 122,
+                    minutes: 18,
+                    seconds: 20,
+                    fractional_seconds: 9,
+                },
+                magnetic_variation: MagneticVariation::East(Decimal::from_str("16").unwrap()),
+                datum_code: "NAR",
+                waypoint_name: "RW16C",
+                file_record_number: 12401,
+                cycle_date: CycleDate { year: 18, cycle: 7 },
+            }
+        );
+    }
+
+    #[test]
+    fn parse_klax_summr() {
+        let record = b"SUSAP KLAXK2CSUMMR K20    W  \
+        R  N33574896W118160087                       E0120     NAR           \
+        SUMMR                    124021906";
+        let parsed = parse_terminal_waypoint_record(&record[..]).unwrap();
+        assert_eq!(
+            parsed,
+            TerminalWaypointRecord {
+                record_type: RecordType::Standard,
+                customer_area_code: "USA",
+                icao_identifier: "KLAX",
+                enriched_section_code: EnrichedSectionCode::Airport(
+                    AirportSubsectionCode::TerminalWaypoints
+                ),
+                waypoint_identifier: "SUMMR",
+                icao_code: "K2",
+                continuation_record_number: 0,
+                waypoint_type: "W  ",
+                waypoint_usage: "R ",
+                latitude: Latitude {
+                    hemisphere: LatitudeHemisphere::North,
+                    degrees: 33,
+                    minutes: 57,
+                    seconds: 48,
+                    fractional_seconds: 96,
+                },
+                longitude: Longitude {
+                    hemisphere: LongitudeHemisphere::West,
+                    degrees: 118,
+                    minutes: 16,
+                    seconds: 0,
+                    fractional_seconds: 87,
+                },
+                magnetic_variation: MagneticVariation::East(Decimal::from_str("12").unwrap()),
+                datum_code: "NAR",
+                waypoint_name: "SUMMR",
+                file_record_number: 12402,
+                cycle_date: CycleDate { year: 19, cycle: 6 },
+            }
+        );
+        let unknown_usage = [&record[..29], b"X", &record[30..]].concat();
+        assert_eq!(parse_terminal_waypoint_record(&unknown_usage), None);
+        let runway = [&record[..12], b"G", &record[13..]].concat();
+        assert_eq!(parse_terminal_waypoint_record(&runway), None);
+    }
+}
@@ -0,0 +1,339 @@
+use crate::graph::GraphIdx;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io;
+use std::io::{BufRead, BufReader};
+use std::marker::PhantomData;
+use std::path::Path;
+
+#[derive(Debug)]
+pub enum TspLibError {
+    Io(io::Error),
+    UnsupportedEdgeWeightType(String),
+    UnsupportedEdgeWeightFormat(String),
+    /// Line number, starting from 1
+    ParseError(usize),
+}
+
+impl Display for TspLibError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TspLibError::Io(e) => write!(f, "{e}"),
+            TspLibError::UnsupportedEdgeWeightType(t) => {
+                write!(f, "unsupported edge weight type {t}")
+            }
+            TspLibError::UnsupportedEdgeWeightFormat(t) => {
+                write!(f, "unsupported edge weight format {t}")
+            }
+            TspLibError::ParseError(line) => write!(f, "parse error at line {line}"),
+        }
+    }
+}
+
+impl Error for TspLibError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TspLibError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum EdgeWeightType {
+    Euc2d,
+    Explicit,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum EdgeWeightFormat {
+    FullMatrix,
+    LowerRow,
+    LowerDiagRow,
+    UpperRow,
+    UpperDiagRow,
+}
+
+/// Parses TSPLib instance with `EUC_2D` or `EXPLICIT` edge weights. `EUC_2D` distances are
+/// rounded to the nearest integer as TSPLib defines.
+pub fn from_tsplib_matrix(
+    reader: impl BufRead,
+) -> Result<GraphIdx<'static, Option<f64>>, TspLibError> {
+    let mut lines = reader
+        .lines()
+        .enumerate()
+        .map(|(i, line)| line.map(|line| (i + 1, line)).map_err(TspLibError::Io));
+    let mut dimension = None;
+    let mut edge_weight_type = None;
+    let mut edge_weight_format = None;
+    let mut graph = None;
+    let mut last_line = 0;
+
+    while let Some(line) = lines.next() {
+        let (line_no, line) = line?;
+        last_line = line_no;
+        let required_dimension = || dimension.ok_or(TspLibError::ParseError(line_no));
+        match line.trim() {
+            "" => {}
+            "EOF" => break,
+            "NODE_COORD_SECTION" => {
+                if edge_weight_type != Some(EdgeWeightType::Euc2d) {
+                    return Err(TspLibError::ParseError(line_no));
+                }
+                let coords = read_coords(&mut lines, required_dimension()?)?;
+                graph = Some(euc_2d(&coords));
+            }
+            "DISPLAY_DATA_SECTION" => {
+                read_coords(&mut lines, required_dimension()?)?;
+            }
+            "EDGE_WEIGHT_SECTION" => {
+                let (Some(EdgeWeightType::Explicit), Some(format)) =
+                    (edge_weight_type, edge_weight_format)
+                else {
+                    return Err(TspLibError::ParseError(line_no));
+                };
+                graph = Some(explicit(&mut lines, required_dimension()?, format)?);
+            }
+            line => {
+                let (key, value) = line
+                    .split_once(':')
+                    .ok_or(TspLibError::ParseError(line_no))?;
+                let value = value.trim();
+                match key.trim() {
+                    "DIMENSION" => {
+                        dimension = Some(
+                            value
+                                .parse::<u32>()
+                                .map_err(|_| TspLibError::ParseError(line_no))?,
+                        )
+                    }
+                    "EDGE_WEIGHT_TYPE" => {
+                        edge_weight_type = Some(match value {
+                            "EUC_2D" => EdgeWeightType::Euc2d,
+                            "EXPLICIT" => EdgeWeightType::Explicit,
+                            _ => {
+                                return Err(TspLibError::UnsupportedEdgeWeightType(
+                                    value.to_string(),
+                                ))
+                            }
+                        })
+                    }
+                    "EDGE_WEIGHT_FORMAT" => {
+                        edge_weight_format = Some(match value {
+                            "FULL_MATRIX" => EdgeWeightFormat::FullMatrix,
+                            "LOWER_ROW" => EdgeWeightFormat::LowerRow,
+                            "LOWER_DIAG_ROW" => EdgeWeightFormat::LowerDiagRow,
+                            "UPPER_ROW" => EdgeWeightFormat::UpperRow,
+                            "UPPER_DIAG_ROW" => EdgeWeightFormat::UpperDiagRow,
+                            _ => {
+                                return Err(TspLibError::UnsupportedEdgeWeightFormat(
+                                    value.to_string(),
+                                ))
+                            }
+                        })
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    graph.ok_or(TspLibError::ParseError(last_line))
+}
+
+impl GraphIdx<'static, Option<f64>> {
+    /// Reads TSPLib instance from `path`, see [`from_tsplib_matrix`]. Malformed and unsupported
+    /// instances are reported as [`io::ErrorKind::InvalidData`].
+    pub fn from_distance_matrix_file(path: &Path) -> Result<Self, io::Error> {
+        from_tsplib_matrix(BufReader::new(File::open(path)?)).map_err(|e| match e {
+            TspLibError::Io(e) => e,
+            e => io::Error::new(io::ErrorKind::InvalidData, e),
+        })
+    }
+}
+
+fn read_coords(
+    lines: &mut impl Iterator<Item = Result<(usize, String), TspLibError>>,
+    dimension: u32,
+) -> Result<Vec<(f64, f64)>, TspLibError> {
+    let mut coords = vec![None; dimension as usize];
+    let mut last_line = 0;
+    for _ in 0..dimension {
+        let (line_no, line) = next_non_empty(lines, last_line)?;
+        last_line = line_no;
+        let parse_error = || TspLibError::ParseError(line_no);
+        let mut tokens = line.split_whitespace();
+        let mut next = || tokens.next().ok_or_else(parse_error);
+        let node = next()?.parse::<usize>().map_err(|_| parse_error())?;
+        let x = next()?.parse::<f64>().map_err(|_| parse_error())?;
+        let y = next()?.parse::<f64>().map_err(|_| parse_error())?;
+        let coord = node
+            .checked_sub(1)
+            .and_then(|node| coords.get_mut(node))
+            .filter(|coord| coord.is_none())
+            .ok_or_else(parse_error)?;
+        *coord = Some((x, y));
+    }
+    Ok(coords.into_iter().flatten().collect())
+}
+
+fn next_non_empty(
+    lines: &mut impl Iterator<Item = Result<(usize, String), TspLibError>>,
+    last_line: usize,
+) -> Result<(usize, String), TspLibError> {
+    for line in lines {
+        let (line_no, line) = line?;
+        if !line.trim().is_empty() {
+            return Ok((line_no, line));
+        }
+    }
+    Err(TspLibError::ParseError(last_line + 1))
+}
+
+fn euc_2d(coords: &[(f64, f64)]) -> GraphIdx<'static, Option<f64>> {
+    GraphIdx {
+        size: coords.len() as u32,
+        edges: coords
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &(x1, y1))| {
+                coords[..i]
+                    .iter()
+                    .map(move |&(x2, y2)| Some(f64::hypot(x1 - x2, y1 - y2).round()))
+            })
+            .collect(),
+        _pd: PhantomData,
+    }
+}
+
+fn explicit(
+    lines: &mut impl Iterator<Item = Result<(usize, String), TspLibError>>,
+    dimension: u32,
+    format: EdgeWeightFormat,
+) -> Result<GraphIdx<'static, Option<f64>>, TspLibError> {
+    let n = dimension;
+    // (row, column) of each weight in order of appearance
+    let positions: Box<dyn Iterator<Item = (u32, u32)>> = match format {
+        EdgeWeightFormat::FullMatrix => {
+            Box::new((0..n).flat_map(move |i| (0..n).map(move |j| (i, j))))
+        }
+        EdgeWeightFormat::LowerRow => Box::new((0..n).flat_map(|i| (0..i).map(move |j| (i, j)))),
+        EdgeWeightFormat::LowerDiagRow => {
+            Box::new((0..n).flat_map(|i| (0..=i).map(move |j| (i, j))))
+        }
+        EdgeWeightFormat::UpperRow => {
+            Box::new((0..n).flat_map(move |i| (i + 1..n).map(move |j| (i, j))))
+        }
+        EdgeWeightFormat::UpperDiagRow => {
+            Box::new((0..n).flat_map(move |i| (i..n).map(move |j| (i, j))))
+        }
+    };
+    let mut positions = positions.peekable();
+    let mut graph = GraphIdx {
+        size: n,
+        edges: vec![None; (n as usize * n.saturating_sub(1) as usize) / 2],
+        _pd: PhantomData,
+    };
+    let mut last_line = 0;
+    while positions.peek().is_some() {
+        let (line_no, line) = next_non_empty(lines, last_line)?;
+        last_line = line_no;
+        for token in line.split_whitespace() {
+            let (i, j) = positions.next().ok_or(TspLibError::ParseError(line_no))?;
+            let weight = token
+                .parse::<f64>()
+                .map_err(|_| TspLibError::ParseError(line_no))?;
+            // the matrix is symmetric, diagonal is ignored
+            graph.set(i, j, Some(weight));
+        }
+    }
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn explicit_instance(format: &str, weights: &str) -> String {
+        format!(
+            "NAME: test\nTYPE: TSP\nDIMENSION: 3\nEDGE_WEIGHT_TYPE: EXPLICIT\n\
+            EDGE_WEIGHT_FORMAT: {format}\nEDGE_WEIGHT_SECTION\n{weights}\nEOF\n"
+        )
+    }
+
+    #[test]
+    fn test_euc_2d() {
+        let instance = "NAME : square\nCOMMENT : unit square\nTYPE : TSP\nDIMENSION : 4\n\
+            EDGE_WEIGHT_TYPE : EUC_2D\nNODE_COORD_SECTION\n1 0 0\n2 3 0\n3 3 4\n4 0 4\nEOF\n";
+        let graph = from_tsplib_matrix(instance.as_bytes()).unwrap();
+        assert_eq!(graph.size, 4);
+        assert_eq!(graph.between(None, 0, 1), Some(Some(3.0)));
+        assert_eq!(graph.between(None, 0, 2), Some(Some(5.0)));
+        assert_eq!(graph.between(None, 3, 1), Some(Some(5.0)));
+        assert_eq!(graph.between(None, 3, 2), Some(Some(3.0)));
+    }
+
+    #[test]
+    fn test_explicit() {
+        let expected =
+            from_tsplib_matrix(explicit_instance("LOWER_ROW", "1\n2 3").as_bytes()).unwrap();
+        assert_eq!(expected.edges, vec![Some(1.0), Some(2.0), Some(3.0)]);
+        for (format, weights) in [
+            ("FULL_MATRIX", "0 1 2\n1 0 3\n2 3 0"),
+            ("LOWER_DIAG_ROW", "0 1 0 2 3 0"),
+            ("UPPER_ROW", "1 2\n3"),
+            ("UPPER_DIAG_ROW", "0 1 2\n0 3\n0"),
+        ] {
+            assert_eq!(
+                from_tsplib_matrix(explicit_instance(format, weights).as_bytes()).unwrap(),
+                expected,
+                "{format}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            from_tsplib_matrix("DIMENSION: 3\nEDGE_WEIGHT_TYPE: GEO\n".as_bytes()),
+            Err(TspLibError::UnsupportedEdgeWeightType(t)) if t == "GEO"
+        ));
+        assert!(matches!(
+            from_tsplib_matrix(explicit_instance("LOWER_ROW", "1\n2 x").as_bytes()),
+            Err(TspLibError::ParseError(8))
+        ));
+        assert!(matches!(
+            from_tsplib_matrix(explicit_instance("LOWER_ROW", "1\n2 3 4").as_bytes()),
+            Err(TspLibError::ParseError(8))
+        ));
+        assert!(matches!(
+            from_tsplib_matrix("DIMENSION: 3\n".as_bytes()),
+            Err(TspLibError::ParseError(1))
+        ));
+        let invalid_utf8: &[u8] = b"DIMENSION: 3\n\xff\n";
+        let Err(TspLibError::Io(e)) = from_tsplib_matrix(invalid_utf8) else {
+            panic!("I/O error expected");
+        };
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+        assert!(e.to_string().contains("UTF-8"), "{e}");
+    }
+
+    #[test]
+    fn test_from_distance_matrix_file() {
+        let path = std::env::temp_dir().join(format!("tsp-test-{}.tsp", std::process::id()));
+        std::fs::write(&path, explicit_instance("UPPER_ROW", "1 2\n3")).unwrap();
+        let graph = GraphIdx::from_distance_matrix_file(&path);
+        std::fs::write(&path, explicit_instance("LOWER_ROW", "1\n2 x")).unwrap();
+        let malformed = GraphIdx::from_distance_matrix_file(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(graph.unwrap().edges, vec![Some(1.0), Some(2.0), Some(3.0)]);
+        assert_eq!(malformed.unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            GraphIdx::from_distance_matrix_file(&path)
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::NotFound
+        );
+    }
+}
@@ -12,6 +12,7 @@
 
 use core::cmp::Ordering;
 use core::cmp::PartialOrd;
+use core::ops::{AddAssign, SubAssign};
 use rand::distributions::uniform::{SampleBorrow, SampleUniform, UniformSampler};
 use rand::distributions::{Distribution, WeightedError};
 use rand::Rng;
@@ -100,20 +101,43 @@ pub struct ReusableWeightedIndex<'a, X: SampleUniform + PartialOrd> {
 #[derive(Debug, Clone, PartialEq)]
 pub struct CumulativeWeightsWrapper<X> {
     cumulative_weights: Vec<X>,
+    // sum of all weights of the last successful fill, distinguishes wrapper filled with single
+    // weight from unfilled one, both have no cumulative weights
+    total_weight: Option<X>,
 }
 
 impl<X: SampleUniform + PartialOrd> CumulativeWeightsWrapper<X> {
     pub fn new() -> Self {
         Self {
             cumulative_weights: vec![],
+            total_weight: None,
         }
     }
 
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
             cumulative_weights: Vec::with_capacity(capacity),
+            total_weight: None,
         }
     }
+
+    /// Number of weights of the last successful fill, 0 if the last fill failed
+    pub fn len(&self) -> usize {
+        if self.total_weight.is_some() {
+            self.cumulative_weights.len() + 1
+        } else {
+            0
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn clear(&mut self) {
+        self.cumulative_weights.clear();
+        self.total_weight = None;
+    }
 }
 
 impl<X: SampleUniform + PartialOrd> Default for CumulativeWeightsWrapper<X> {
@@ -140,7 +164,7 @@ impl<X: SampleUniform + PartialOrd + Default> CumulativeWeightsWrapper<X> {
         I::Item: SampleBorrow<X>,
         X: for<'b> core::ops::AddAssign<&'b X> + Clone + Default,
     {
-        self.cumulative_weights.clear();
+        self.clear();
         let mut iter = weights.into_iter();
         let mut total_weight: X = iter.next().ok_or(WeightedError::NoItem)?.borrow().clone();
         let zero = <X as Default>::default();
@@ -153,7 +177,7 @@ impl<X: SampleUniform + PartialOrd + Default> CumulativeWeightsWrapper<X> {
             // Note that `!(w >= x)` is not equivalent to `w < x` for partially
             // ordered types due to NaNs which are equal to nothing.
             if matches!(w.borrow().partial_cmp(&zero), None | Some(Ordering::Less)) {
-                self.cumulative_weights.clear();
+                self.clear();
                 return Err(WeightedError::InvalidWeight);
             }
             self.cumulative_weights.push(total_weight.clone());
@@ -161,11 +185,12 @@ impl<X: SampleUniform + PartialOrd + Default> CumulativeWeightsWrapper<X> {
         }
 
         if total_weight == zero {
-            self.cumulative_weights.clear();
+            self.clear();
             return Err(WeightedError::AllWeightsZero);
         }
 
         let weight_distribution = X::Sampler::new(zero, total_weight.clone());
+        self.total_weight = Some(total_weight.clone());
 
         Ok(ReusableWeightedIndex {
             wrapper: self,
@@ -173,9 +198,121 @@ impl<X: SampleUniform + PartialOrd + Default> CumulativeWeightsWrapper<X> {
             total_weight,
         })
     }
+
+    /// `ReusableWeightedIndex` over the weights of the last successful [`fill`](Self::fill) and
+    /// updates after it, `None` if the wrapper is not filled
+    pub fn weighted_index(&self) -> Option<ReusableWeightedIndex<'_, X>>
+    where
+        X: Clone,
+    {
+        let total_weight = self.total_weight.clone()?;
+        Some(ReusableWeightedIndex {
+            wrapper: self,
+            weight_distribution: X::Sampler::new(X::default(), total_weight.clone()),
+            total_weight,
+        })
+    }
+}
+
+impl<X> CumulativeWeightsWrapper<X>
+where
+    X: SampleUniform
+        + PartialOrd
+        + for<'b> AddAssign<&'b X>
+        + for<'b> SubAssign<&'b X>
+        + Default
+        + Clone,
+{
+    /// Replaces the weight at `index` of the last successful [`fill`](Self::fill), recomputing
+    /// cumulative weights from `index` onward only.
+    ///
+    /// Returns error if the wrapper is not filled, deinitializes it and returns error if the
+    /// weight is `< 0` or the total value becomes 0
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of range.
+    pub fn update_weight(&mut self, index: usize, new_weight: X) -> Result<(), WeightedError> {
+        self.update_weights_batch(&[(index, new_weight)])
+    }
+
+    /// Same as [`update_weight`](Self::update_weight) for several weights, sorted by index and
+    /// applied in a single forward pass. The last update wins if an index is repeated.
+    pub fn update_weights_batch(&mut self, updates: &[(usize, X)]) -> Result<(), WeightedError> {
+        let Some(total_weight) = &self.total_weight else {
+            return Err(WeightedError::NoItem);
+        };
+        let len = self.len();
+        assert!(
+            updates.iter().all(|&(index, _)| index < len),
+            "Weight index out of range"
+        );
+        let zero = <X as Default>::default();
+        if updates
+            .iter()
+            .any(|(_, w)| matches!(w.partial_cmp(&zero), None | Some(Ordering::Less)))
+        {
+            self.clear();
+            return Err(WeightedError::InvalidWeight);
+        }
+        let mut updates: Vec<_> = updates.iter().enumerate().collect();
+        // the later of the repeated updates comes first and wins
+        updates.sort_unstable_by(|(order1, (index1, _)), (order2, (index2, _))| {
+            index1.cmp(index2).then(order2.cmp(order1))
+        });
+        updates.dedup_by_key(|(_, (index, _))| *index);
+        let Some(&(_, &(from, _))) = updates.first() else {
+            return Ok(());
+        };
+
+        // old weights are told from the differences of old cumulative weights
+        let mut old_cumulative = if from == 0 {
+            zero.clone()
+        } else {
+            self.cumulative_weights[from - 1].clone()
+        };
+        let mut cumulative = old_cumulative.clone();
+        let mut total_weight = total_weight.clone();
+        let mut updates = updates.into_iter().map(|(_, update)| update).peekable();
+        for i in from..len {
+            let next_old_cumulative = self
+                .cumulative_weights
+                .get(i)
+                .unwrap_or(&total_weight)
+                .clone();
+            match updates.next_if(|&&(index, _)| index == i) {
+                Some((_, w)) => cumulative += w,
+                None => {
+                    let mut w = next_old_cumulative.clone();
+                    w -= &old_cumulative;
+                    cumulative += &w;
+                }
+            }
+            old_cumulative = next_old_cumulative;
+            match self.cumulative_weights.get_mut(i) {
+                Some(cumulative_weight) => *cumulative_weight = cumulative.clone(),
+                None => total_weight = cumulative.clone(),
+            }
+        }
+
+        if total_weight == zero {
+            self.clear();
+            return Err(WeightedError::AllWeightsZero);
+        }
+        self.total_weight = Some(total_weight);
+        Ok(())
+    }
 }
 
-impl<'a, X: SampleUniform + PartialOrd> ReusableWeightedIndex<'a, X> {}
+impl<'a, X: SampleUniform + PartialOrd> ReusableWeightedIndex<'a, X> {
+    pub fn len(&self) -> usize {
+        self.wrapper.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.wrapper.is_empty()
+    }
+}
 
 impl<'a, X> Distribution<usize> for ReusableWeightedIndex<'a, X>
 where
@@ -340,6 +477,96 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_len() {
+        let mut distr = CumulativeWeightsWrapper::new();
+        assert_eq!(distr.len(), 0);
+        assert!(distr.is_empty());
+        assert_eq!(distr.fill([1]).unwrap().len(), 1);
+        assert_eq!(distr.len(), 1);
+        assert!(!distr.is_empty());
+        assert_eq!(distr.fill([1, 2, 3]).unwrap().len(), 3);
+        assert!(distr.fill([1, -2]).is_err());
+        assert_eq!(distr.len(), 0);
+        assert!(distr.is_empty());
+    }
+
+    #[test]
+    fn test_failed_fill_clears() {
+        let mut distr = CumulativeWeightsWrapper::new();
+        distr.fill([1.0, 2.0, 3.0]).unwrap();
+        assert_eq!(
+            distr.fill([1.0, 2.0, f64::NAN, 3.0]),
+            Err(WeightedError::InvalidWeight)
+        );
+        assert_eq!(distr.len(), 0);
+        assert!(distr.is_empty());
+        assert_eq!(distr, CumulativeWeightsWrapper::new());
+        assert_eq!(distr.fill([0.0, 0.0]), Err(WeightedError::AllWeightsZero));
+        assert_eq!(distr, CumulativeWeightsWrapper::new());
+        assert_eq!(distr.fill([4.0]).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_update_weight() {
+        let mut r = rng(702);
+        let mut weights: Vec<u32> = (0..100).map(|_| r.gen_range(0..10)).collect();
+        let mut distr = CumulativeWeightsWrapper::new();
+        let mut expected = CumulativeWeightsWrapper::new();
+        distr.fill(&weights).unwrap();
+        for round in 0..50 {
+            let updates: Vec<_> = (0..round % 4 + 1)
+                .map(|_| (r.gen_range(0..100), r.gen_range(0..10)))
+                .collect();
+            for &(index, w) in &updates {
+                weights[index] = w;
+            }
+            let updated = if let [(index, w)] = updates[..] {
+                distr.update_weight(index, w)
+            } else {
+                distr.update_weights_batch(&updates)
+            };
+            assert_eq!(updated, Ok(()), "round {round}");
+            expected.fill(&weights).unwrap();
+            assert_eq!(distr, expected, "round {round}");
+        }
+        assert_eq!(distr.update_weights_batch(&[]), Ok(()));
+        assert_eq!(distr, expected);
+        distr
+            .update_weights_batch(&[(3, 1), (99, 4), (3, 2)])
+            .unwrap();
+        weights[3] = 2;
+        weights[99] = 4;
+        assert_eq!(distr.weighted_index(), expected.fill(&weights).ok());
+
+        let mut distr = CumulativeWeightsWrapper::new();
+        assert_eq!(distr.update_weight(0, 1), Err(WeightedError::NoItem));
+        assert_eq!(distr.weighted_index(), None);
+        distr.fill([1, 0]).unwrap();
+        assert_eq!(
+            distr.update_weight(0, 0),
+            Err(WeightedError::AllWeightsZero)
+        );
+        assert!(distr.is_empty());
+        distr.fill([1, 2, 3]).unwrap();
+        assert_eq!(
+            distr.update_weight(2, -1),
+            Err(WeightedError::InvalidWeight)
+        );
+        assert!(distr.is_empty());
+        distr.fill([1]).unwrap();
+        distr.update_weight(0, 5).unwrap();
+        assert_eq!(distr.weighted_index().unwrap().len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Weight index out of range")]
+    fn test_update_weight_out_of_range() {
+        let mut distr = CumulativeWeightsWrapper::new();
+        distr.fill([1, 2]).unwrap();
+        let _ = distr.update_weight(2, 1);
+    }
+
     #[test]
     fn weighted_index_distributions_can_be_compared() {
         let mut distr1 = CumulativeWeightsWrapper::new();
@@ -1,4 +1,34 @@
+use crate::math::EARTH_RADIUS;
 use crate::types::field::coord::Coord;
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ScalerProjection {
+    /// Latitude and longitude are linear
+    #[default]
+    Equirectangular,
+    /// Web Mercator, latitudes are clamped to about ±85.05°
+    Mercator,
+}
+
+impl ScalerProjection {
+    fn project_lat(self, lat: f64) -> f64 {
+        match self {
+            Self::Equirectangular => lat,
+            Self::Mercator => {
+                let max_lat = PI.sinh().atan();
+                (FRAC_PI_4 + lat.clamp(-max_lat, max_lat) / 2.0).tan().ln()
+            }
+        }
+    }
+
+    fn unproject_lat(self, y: f64) -> f64 {
+        match self {
+            Self::Equirectangular => y,
+            Self::Mercator => 2.0 * y.exp().atan() - FRAC_PI_2,
+        }
+    }
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 pub struct Scaler {
@@ -6,35 +36,99 @@ pub struct Scaler {
     scale_y: f64,
     offset_x: f64,
     offset_y: f64,
+    projection: ScalerProjection,
 }
 
 impl Scaler {
     pub fn new(top_left: Coord, bottom_right: Coord, width: u32, height: u32) -> Self {
+        Self::new_with_projection(
+            top_left,
+            bottom_right,
+            width,
+            height,
+            ScalerProjection::Equirectangular,
+        )
+    }
+
+    pub fn new_with_projection(
+        top_left: Coord,
+        bottom_right: Coord,
+        width: u32,
+        height: u32,
+        projection: ScalerProjection,
+    ) -> Self {
+        let (top, bottom) = (
+            projection.project_lat(top_left.lat),
+            projection.project_lat(bottom_right.lat),
+        );
         let scale_x = (width - 1) as f64 / (bottom_right.lon - top_left.lon);
-        let scale_y = (height - 1) as f64 / (bottom_right.lat - top_left.lat);
+        let scale_y = (height - 1) as f64 / (bottom - top);
         let offset_x = top_left.lon * scale_x;
-        let offset_y = top_left.lat * scale_y;
+        let offset_y = top * scale_y;
         Self {
             scale_x,
             scale_y,
             offset_x,
             offset_y,
+            projection,
         }
     }
 
+    pub fn new_centered(center: Coord, radius_km: f64, width: u32, height: u32) -> Self {
+        let (w, h) = ((width - 1) as f64, (height - 1) as f64);
+        let radius = radius_km / EARTH_RADIUS;
+        // pixels per radian of latitude, so that the radius fits into the smaller side
+        let scale = w.min(h) * 0.5 / radius;
+        let half_lat = h * 0.5 / scale;
+        let half_lon = w * 0.5 / (scale * center.lat.cos());
+        Self::new(
+            Coord {
+                lat: center.lat + half_lat,
+                lon: center.lon - half_lon,
+            },
+            Coord {
+                lat: center.lat - half_lat,
+                lon: center.lon + half_lon,
+            },
+            width,
+            height,
+        )
+    }
+
     pub fn map(&self, coord: Coord) -> (i32, i32) {
-        let x = coord.lon * self.scale_x - self.offset_x;
-        let x = x.round() as i32;
-        let y = coord.lat * self.scale_y - self.offset_y;
-        let y = y.round() as i32;
-        (x, y)
+        let (x, y) = self.map_f64(coord);
+        (x.round() as i32, y.round() as i32)
     }
 
     pub fn map_f32(&self, coord: Coord) -> (f32, f32) {
-        let x = coord.lon * self.scale_x - self.offset_x;
-        let y = coord.lat * self.scale_y - self.offset_y;
+        let (x, y) = self.map_f64(coord);
         (x as f32, y as f32)
     }
+
+    fn map_f64(&self, coord: Coord) -> (f64, f64) {
+        let x = coord.lon * self.scale_x - self.offset_x;
+        let y = self.projection.project_lat(coord.lat) * self.scale_y - self.offset_y;
+        (x, y)
+    }
+
+    /// Inverse of [`Scaler::map`], up to rounding to pixels
+    pub fn unmap(&self, x: i32, y: i32) -> Coord {
+        self.unmap_f64(x as f64, y as f64)
+    }
+
+    /// Inverse of [`Scaler::map_f32`]
+    pub fn unmap_f32(&self, x: f32, y: f32) -> Coord {
+        self.unmap_f64(x as f64, y as f64)
+    }
+
+    fn unmap_f64(&self, x: f64, y: f64) -> Coord {
+        Coord {
+            lat: self
+                .projection
+                .unproject_lat((y + self.offset_y) / self.scale_y),
+            lon: (x + self.offset_x) / self.scale_x,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -56,7 +150,8 @@ mod tests {
                 scale_x: 99.0,
                 scale_y: -199.0,
                 offset_x: 0.0,
-                offset_y: -199.0
+                offset_y: -199.0,
+                projection: ScalerProjection::Equirectangular,
             }
         );
 
@@ -79,7 +174,8 @@ mod tests {
                 scale_x: 49.5,
                 scale_y: -99.5,
                 offset_x: -49.5,
-                offset_y: -99.5
+                offset_y: -99.5,
+                projection: ScalerProjection::Equirectangular,
             }
         );
     }
@@ -137,4 +233,144 @@ mod tests {
         assert_eq!(scaler.map(Coord { lat: 0.0, lon: 0.0 }), (50, 100));
         assert_eq!(scaler.map(Coord { lat: 0.5, lon: 0.5 }), (74, 50));
     }
+
+    #[test]
+    fn test_scaler_unmap() {
+        let scalers = [
+            Scaler::new(
+                Coord { lat: 1.0, lon: 0.0 },
+                Coord { lat: 0.0, lon: 1.0 },
+                100,
+                200,
+            ),
+            Scaler::new(
+                Coord {
+                    lat: 1.0,
+                    lon: -1.0,
+                },
+                Coord {
+                    lat: -1.0,
+                    lon: 1.0,
+                },
+                100,
+                200,
+            ),
+            Scaler::new_with_projection(
+                Coord {
+                    lat: 1.0,
+                    lon: -1.0,
+                },
+                Coord {
+                    lat: -1.0,
+                    lon: 1.0,
+                },
+                100,
+                200,
+                ScalerProjection::Mercator,
+            ),
+        ];
+        for scaler in scalers {
+            for c in [
+                Coord { lat: 0.0, lon: 0.0 },
+                Coord { lat: 1.0, lon: 0.0 },
+                Coord { lat: 0.0, lon: 1.0 },
+                Coord { lat: 1.0, lon: 1.0 },
+                Coord { lat: 0.5, lon: 0.5 },
+                Coord {
+                    lat: -0.3,
+                    lon: 0.7,
+                },
+            ] {
+                let (x, y) = scaler.map_f64(c);
+                let c2 = scaler.unmap_f64(x, y);
+                assert!((c.lat - c2.lat).abs() < 1e-9, "{c:?} {c2:?}");
+                assert!((c.lon - c2.lon).abs() < 1e-9, "{c:?} {c2:?}");
+
+                let (x, y) = scaler.map_f32(c);
+                let c2 = scaler.unmap_f32(x, y);
+                assert!((c.lat - c2.lat).abs() < 1e-6, "{c:?} {c2:?}");
+                assert!((c.lon - c2.lon).abs() < 1e-6, "{c:?} {c2:?}");
+            }
+            for (x, y) in [(0, 0), (99, 199), (50, 100), (17, 123)] {
+                assert_eq!(scaler.map(scaler.unmap(x, y)), (x, y));
+            }
+        }
+
+        let scaler = scalers[0];
+        let c = scaler.unmap(0, 199);
+        assert!((c.lat - 0.0).abs() < 1e-9);
+        assert!((c.lon - 0.0).abs() < 1e-9);
+        let c = scaler.unmap(99, 0);
+        assert!((c.lat - 1.0).abs() < 1e-9);
+        assert!((c.lon - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_scaler_mercator() {
+        let (top_left, bottom_right) = (
+            Coord::try_from((60.0, -10.0)).unwrap(),
+            Coord::try_from((0.0, 10.0)).unwrap(),
+        );
+        let scaler =
+            |projection| Scaler::new_with_projection(top_left, bottom_right, 101, 101, projection);
+        let (equirectangular, mercator) = (
+            scaler(ScalerProjection::Equirectangular),
+            scaler(ScalerProjection::Mercator),
+        );
+        for scaler in [equirectangular, mercator] {
+            assert_eq!(scaler.map(top_left), (0, 0));
+            assert_eq!(scaler.map(bottom_right), (100, 100));
+        }
+
+        let (lat_45, lat_60) = (
+            Coord::try_from((45.0, 0.0)).unwrap(),
+            Coord::try_from((60.0, 0.0)).unwrap(),
+        );
+        let gap = |scaler: Scaler| scaler.map_f32(lat_45).1 - scaler.map_f32(lat_60).1;
+        assert!((gap(equirectangular) - 25.0).abs() < 1e-4);
+        assert!(gap(mercator) > gap(equirectangular));
+        assert_eq!(mercator.map(lat_45), (50, 33));
+
+        let pole = Coord {
+            lat: std::f64::consts::FRAC_PI_2,
+            lon: 0.0,
+        };
+        assert!(mercator.map_f32(pole).1.is_finite());
+    }
+
+    #[test]
+    fn test_scaler_new_centered() {
+        let radius = EARTH_RADIUS * 0.1;
+        let scaler = Scaler::new_centered(Coord { lat: 0.0, lon: 0.0 }, radius, 101, 201);
+
+        assert_eq!(scaler.map(Coord { lat: 0.0, lon: 0.0 }), (50, 100));
+        assert_eq!(
+            scaler.map(Coord {
+                lat: 0.0,
+                lon: -0.1
+            }),
+            (0, 100)
+        );
+        assert_eq!(scaler.map(Coord { lat: 0.0, lon: 0.1 }), (100, 100));
+        assert_eq!(scaler.map(Coord { lat: 0.1, lon: 0.0 }), (50, 50));
+        assert_eq!(
+            scaler.map(Coord {
+                lat: -0.1,
+                lon: 0.0
+            }),
+            (50, 150)
+        );
+
+        let lat = std::f64::consts::FRAC_PI_3;
+        let scaler = Scaler::new_centered(Coord { lat, lon: 0.0 }, radius, 101, 101);
+        assert_eq!(scaler.map(Coord { lat, lon: 0.0 }), (50, 50));
+        assert_eq!(
+            scaler.map(Coord {
+                lat: lat + 0.1,
+                lon: 0.0
+            }),
+            (50, 0)
+        );
+        assert_eq!(scaler.map(Coord { lat, lon: 0.2 }), (100, 50));
+    }
 }
@@ -0,0 +1,210 @@
+use crate::types::field::coord::{Latitude, LatitudeHemisphere, Longitude, LongitudeHemisphere};
+use crate::types::field::{
+    Altitude, CycleDate, MagneticTrueIndicator, MagneticVariation, PublicMilitaryIndicator,
+    RecordType, RunwaySurfaceCode, TimeZone,
+};
+use crate::util::{write_alpha, write_blank, write_num};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+
+pub mod section_code;
+
+// 5.32 Cycle Date
+pub fn serialize_cycle_date(cycle_date: CycleDate, buf: &mut [u8]) {
+    write_num(cycle_date.year as u32, &mut buf[..2]);
+    write_num(cycle_date.cycle as u32, &mut buf[2..4]);
+}
+
+// 5.165 Magnetic/True Indicator
+pub fn serialize_magnetic_true_indicator(
+    magnetic_true_indicator: Option<MagneticTrueIndicator>,
+) -> u8 {
+    match magnetic_true_indicator {
+        Some(MagneticTrueIndicator::Magnetic) => b'M',
+        Some(MagneticTrueIndicator::True) => b'T',
+        None => b' ',
+    }
+}
+
+// 5.179 Daylight Indicator
+pub fn serialize_daylight_indicator(daylight_indicator: Option<bool>) -> u8 {
+    match daylight_indicator {
+        Some(true) => b'Y',
+        Some(false) => b'N',
+        None => b' ',
+    }
+}
+
+// 5.178 Time Zone
+pub fn serialize_time_zone(time_zone: Option<TimeZone>, buf: &mut [u8]) {
+    match time_zone {
+        Some(TimeZone { hour, minute }) => {
+            buf[0] = match hour {
+                0 => b'Z',
+                -9..=-1 => b'A' + (-hour - 1) as u8,
+                -12..=-10 => b'K' + (-hour - 10) as u8,
+                1..=12 => b'N' + (hour - 1) as u8,
+                _ => b' ',
+            };
+            write_num(minute as u32, &mut buf[1..3]);
+        }
+        None => write_blank(&mut buf[..3]),
+    }
+}
+
+// 5.177 Public/Military Indicator
+pub fn serialize_public_military_indicator(
+    public_military_indicator: PublicMilitaryIndicator,
+) -> u8 {
+    match public_military_indicator {
+        PublicMilitaryIndicator::Civil => b'C',
+        PublicMilitaryIndicator::Military => b'M',
+        PublicMilitaryIndicator::Private => b'P',
+    }
+}
+
+// 5.53 Transition Altitude
+pub fn serialize_transition_altitude(transition_altitude: Option<u32>, buf: &mut [u8]) {
+    match transition_altitude {
+        Some(transition_altitude) => write_num(transition_altitude, &mut buf[..5]),
+        None => write_blank(&mut buf[..5]),
+    }
+}
+
+// 5.23 Recommended Navaid
+pub fn serialize_recommended_navaid(recommended_navaid: Option<&str>, buf: &mut [u8]) {
+    write_alpha(recommended_navaid.unwrap_or_default(), &mut buf[..4]);
+}
+
+// 5.72 Speed Limit
+pub fn serialize_speed_limit(speed_limit: Option<u16>, buf: &mut [u8]) {
+    match speed_limit {
+        Some(speed_limit) => write_num(speed_limit as u32, &mut buf[..3]),
+        None => write_blank(&mut buf[..3]),
+    }
+}
+
+// 5.55 Airport Elevation
+pub fn serialize_airport_elevation(airport_elevation: i32, buf: &mut [u8]) {
+    if airport_elevation < 0 {
+        buf[0] = b'-';
+        write_num(airport_elevation.unsigned_abs(), &mut buf[1..5]);
+    } else {
+        write_num(airport_elevation as u32, &mut buf[..5]);
+    }
+}
+
+// 5.39 Magnetic Variation
+pub fn serialize_magnetic_variation(magnetic_variation: MagneticVariation, buf: &mut [u8]) {
+    let (c, dec) = match magnetic_variation {
+        MagneticVariation::East(dec) => (b'E', dec),
+        MagneticVariation::West(dec) => (b'W', dec),
+        MagneticVariation::True => (b'T', Decimal::ZERO),
+    };
+    buf[0] = c;
+    write_num(
+        (dec * Decimal::TEN).trunc().to_u32().unwrap_or_default(),
+        &mut buf[1..5],
+    );
+}
+
+// 5.37 Airport Reference Point Longitude
+pub fn serialize_airport_reference_point_longitude(
+    airport_reference_point_longitude: &Longitude,
+    buf: &mut [u8],
+) {
+    buf[0] = match airport_reference_point_longitude.hemisphere {
+        LongitudeHemisphere::East => b'E',
+        LongitudeHemisphere::West => b'W',
+    };
+    write_num(
+        airport_reference_point_longitude.degrees as u32,
+        &mut buf[1..4],
+    );
+    write_num(
+        airport_reference_point_longitude.minutes as u32,
+        &mut buf[4..6],
+    );
+    write_num(
+        airport_reference_point_longitude.seconds as u32,
+        &mut buf[6..8],
+    );
+    write_num(
+        airport_reference_point_longitude.fractional_seconds as u32,
+        &mut buf[8..10],
+    );
+}
+
+// 5.36 Airport Reference Point Latitude
+pub fn serialize_airport_reference_point_latitude(
+    airport_reference_point_latitude: &Latitude,
+    buf: &mut [u8],
+) {
+    buf[0] = match airport_reference_point_latitude.hemisphere {
+        LatitudeHemisphere::North => b'N',
+        LatitudeHemisphere::South => b'S',
+    };
+    write_num(
+        airport_reference_point_latitude.degrees as u32,
+        &mut buf[1..3],
+    );
+    write_num(
+        airport_reference_point_latitude.minutes as u32,
+        &mut buf[3..5],
+    );
+    write_num(
+        airport_reference_point_latitude.seconds as u32,
+        &mut buf[5..7],
+    );
+    write_num(
+        airport_reference_point_latitude.fractional_seconds as u32,
+        &mut buf[7..9],
+    );
+}
+
+// 5.249 Longest Runway Surface Code
+pub fn serialize_longest_runway_surface_code(longest_runway_surface_code: RunwaySurfaceCode) -> u8 {
+    match longest_runway_surface_code {
+        RunwaySurfaceCode::HardSurface => b'H',
+        RunwaySurfaceCode::SoftSurface => b'S',
+        RunwaySurfaceCode::WaterRunway => b'W',
+        RunwaySurfaceCode::Undefined => b'U',
+    }
+}
+
+// 5.108 IFR Capability
+pub fn serialize_ifr_capability(ifr_capability: bool) -> u8 {
+    if ifr_capability {
+        b'Y'
+    } else {
+        b'N'
+    }
+}
+
+// 5.73 Speed Limit Altitude
+pub fn serialize_speed_limit_altitude(speed_limit_altitude: Option<Altitude>, buf: &mut [u8]) {
+    match speed_limit_altitude {
+        Some(Altitude::Fl(fl)) => {
+            buf[..2].copy_from_slice(b"FL");
+            write_num(fl as u32, &mut buf[2..5]);
+        }
+        Some(Altitude::Msl(msl)) => write_num(msl, &mut buf[..5]),
+        None => write_blank(&mut buf[..5]),
+    }
+}
+
+// 5.16 Continuation Record Number
+pub fn serialize_continuation_record_number(continuation_record_number: u8) -> u8 {
+    match continuation_record_number {
+        0..=9 => b'0' + continuation_record_number,
+        _ => b'A' + continuation_record_number - 10,
+    }
+}
+
+// 5.2 Record Type
+pub fn serialize_record_type(record_type: RecordType) -> u8 {
+    match record_type {
+        RecordType::Standard => b'S',
+        RecordType::Tailored => b'T',
+    }
+}
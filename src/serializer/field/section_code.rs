@@ -0,0 +1,139 @@
+use crate::types::field::section_code::{
+    AirportSubsectionCode, AirspaceSubsectionCode, CompanyRoutesSubsectionCode,
+    EnrichedSectionCode, EnrouteSubsectionCode, HeliportSubsectionCode, MoraSubsectionCode,
+    NavaidSubsectionCode, SectionCode, TablesSubsectionCode,
+};
+
+// 5.4 Section Code
+pub fn serialize_section_code(section_code: SectionCode) -> u8 {
+    match section_code {
+        SectionCode::Mora => b'A',
+        SectionCode::Navaid => b'D',
+        SectionCode::Enroute => b'E',
+        SectionCode::Heliport => b'H',
+        SectionCode::Airport => b'P',
+        SectionCode::CompanyRoutes => b'R',
+        SectionCode::Tables => b'T',
+        SectionCode::Airspace => b'U',
+    }
+}
+
+// 5.4 Section Code, 5.5 Subsection Code
+pub fn serialize_enriched_section_code(enriched_section_code: EnrichedSectionCode) -> (u8, u8) {
+    match enriched_section_code {
+        EnrichedSectionCode::Mora(code) => (
+            serialize_section_code(SectionCode::Mora),
+            serialize_mora_subsection_code(code),
+        ),
+        EnrichedSectionCode::Navaid(code) => (
+            serialize_section_code(SectionCode::Navaid),
+            serialize_navaid_subsection_code(code),
+        ),
+        EnrichedSectionCode::Enroute(code) => (
+            serialize_section_code(SectionCode::Enroute),
+            serialize_enroute_subsection_code(code),
+        ),
+        EnrichedSectionCode::Heliport(code) => (
+            serialize_section_code(SectionCode::Heliport),
+            serialize_heliport_subsection_code(code),
+        ),
+        EnrichedSectionCode::Airport(code) => (
+            serialize_section_code(SectionCode::Airport),
+            serialize_airport_subsection_code(code),
+        ),
+        EnrichedSectionCode::CompanyRoutes(code) => (
+            serialize_section_code(SectionCode::CompanyRoutes),
+            serialize_company_routes_subsection_code(code),
+        ),
+        EnrichedSectionCode::Tables(code) => (
+            serialize_section_code(SectionCode::Tables),
+            serialize_tables_subsection_code(code),
+        ),
+        EnrichedSectionCode::Airspace(code) => (
+            serialize_section_code(SectionCode::Airspace),
+            serialize_airspace_subsection_code(code),
+        ),
+    }
+}
+
+fn serialize_airspace_subsection_code(subsection_code: AirspaceSubsectionCode) -> u8 {
+    match subsection_code {
+        AirspaceSubsectionCode::ControlledAirspace => b'C',
+        AirspaceSubsectionCode::FirUir => b'F',
+        AirspaceSubsectionCode::RestrictiveAirspace => b'R',
+    }
+}
+
+fn serialize_tables_subsection_code(subsection_code: TablesSubsectionCode) -> u8 {
+    match subsection_code {
+        TablesSubsectionCode::CruisingTables => b'C',
+        TablesSubsectionCode::GeographicalReference => b'G',
+    }
+}
+
+fn serialize_company_routes_subsection_code(subsection_code: CompanyRoutesSubsectionCode) -> u8 {
+    match subsection_code {
+        CompanyRoutesSubsectionCode::CompanyRoutes => b' ',
+        CompanyRoutesSubsectionCode::AlternateRecords => b'A',
+    }
+}
+
+fn serialize_airport_subsection_code(subsection_code: AirportSubsectionCode) -> u8 {
+    match subsection_code {
+        AirportSubsectionCode::ReferencePoints => b'A',
+        AirportSubsectionCode::Gates => b'B',
+        AirportSubsectionCode::TerminalWaypoints => b'C',
+        AirportSubsectionCode::Sids => b'D',
+        AirportSubsectionCode::Stars => b'E',
+        AirportSubsectionCode::ApproachProcedures => b'F',
+        AirportSubsectionCode::Runways => b'G',
+        AirportSubsectionCode::LocalizerGlideSlope => b'I',
+        AirportSubsectionCode::Taa => b'K',
+        AirportSubsectionCode::Mls => b'L',
+        AirportSubsectionCode::LocalizerMarker => b'M',
+        AirportSubsectionCode::TerminalNdb => b'N',
+        AirportSubsectionCode::PathPoint => b'P',
+        AirportSubsectionCode::FltPlanningArrDep => b'R',
+        AirportSubsectionCode::Msa => b'S',
+        AirportSubsectionCode::GlsStation => b'T',
+        AirportSubsectionCode::Communications => b'V',
+    }
+}
+
+fn serialize_heliport_subsection_code(subsection_code: HeliportSubsectionCode) -> u8 {
+    match subsection_code {
+        HeliportSubsectionCode::Pads => b'A',
+        HeliportSubsectionCode::TerminalWaypoints => b'C',
+        HeliportSubsectionCode::Sids => b'D',
+        HeliportSubsectionCode::Stars => b'E',
+        HeliportSubsectionCode::ApproachProcedures => b'F',
+        HeliportSubsectionCode::Taa => b'K',
+        HeliportSubsectionCode::Msa => b'S',
+        HeliportSubsectionCode::Communications => b'V',
+    }
+}
+
+fn serialize_enroute_subsection_code(subsection_code: EnrouteSubsectionCode) -> u8 {
+    match subsection_code {
+        EnrouteSubsectionCode::Waypoints => b'A',
+        EnrouteSubsectionCode::AirwayMarkers => b'M',
+        EnrouteSubsectionCode::HoldingPatterns => b'P',
+        EnrouteSubsectionCode::AirwaysAndRoutes => b'R',
+        EnrouteSubsectionCode::PreferredRoutes => b'T',
+        EnrouteSubsectionCode::AirwayRestrictions => b'U',
+        EnrouteSubsectionCode::Communications => b'V',
+    }
+}
+
+fn serialize_navaid_subsection_code(subsection_code: NavaidSubsectionCode) -> u8 {
+    match subsection_code {
+        NavaidSubsectionCode::VhfNavaid => b' ',
+        NavaidSubsectionCode::NdbNavaid => b'B',
+    }
+}
+
+fn serialize_mora_subsection_code(subsection_code: MoraSubsectionCode) -> u8 {
+    match subsection_code {
+        MoraSubsectionCode::GridMora => b'S',
+    }
+}
@@ -0,0 +1,66 @@
+use crate::parser::record::ENTRY_LEN;
+use crate::types::record::AirportPrimaryRecord;
+use std::io;
+use std::io::Write;
+
+/// Writes records in ARINC 424 format, one per line
+pub fn serialize_airport_primary_records<'a, 'b: 'a, W: Write>(
+    recs: impl Iterator<Item = &'a AirportPrimaryRecord<'b>>,
+    writer: &mut W,
+) -> io::Result<()> {
+    for rec in recs {
+        writer.write_all(&rec.to_bytes())?;
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+pub fn tour_to_arinc424(recs: &[AirportPrimaryRecord], aco: &[u32]) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(aco.len() * (ENTRY_LEN + 1));
+    serialize_airport_primary_records(aco.iter().map(|&i| &recs[i as usize]), &mut buf)
+        .unwrap_or_else(|_| unreachable!("Writing to Vec should not fail"));
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::file::parse_airport_primary_records;
+    use crate::parser::record::parse_airport_primary_record;
+    use crate::test_util::{KDEN, KLAX, KSEA};
+
+    #[test]
+    fn test_serialize_airport_primary_records() {
+        let buf = format!("{KLAX}\n{KSEA}\n");
+        let recs: Vec<_> = parse_airport_primary_records(buf.as_bytes()).collect();
+        let mut written = vec![];
+        serialize_airport_primary_records(recs.iter(), &mut written).unwrap();
+        assert_eq!(written, buf.as_bytes());
+
+        let mut full = [0; ENTRY_LEN + 10];
+        assert_eq!(
+            serialize_airport_primary_records(recs.iter(), &mut &mut full[..])
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::WriteZero
+        );
+    }
+
+    #[test]
+    fn test_tour_to_arinc424() {
+        let records = [KLAX.as_bytes(), KSEA.as_bytes(), KDEN.as_bytes()];
+        let recs: Vec<_> = records
+            .iter()
+            .map(|&rec| parse_airport_primary_record(rec).unwrap())
+            .collect();
+        let aco = [2, 0, 1];
+
+        let buf = tour_to_arinc424(&recs, &aco);
+
+        assert_eq!(buf.len(), aco.len() * (ENTRY_LEN + 1));
+        assert_eq!(
+            parse_airport_primary_records(&buf).collect::<Vec<_>>(),
+            vec![recs[2], recs[0], recs[1]]
+        );
+    }
+}
@@ -0,0 +1,4 @@
+pub mod field;
+pub mod file;
+pub mod openflights;
+pub mod record;
@@ -0,0 +1,72 @@
+use crate::model::Airport;
+use crate::parser::openflights::HEADER;
+use crate::util::csv_quote;
+use std::io;
+use std::io::Write;
+
+impl Airport {
+    /// Row of OpenFlights `airports.dat` with name, ICAO code and coordinates, zero altitude, other
+    /// fields are empty
+    pub fn to_openflights_row(&self) -> String {
+        let (lat, lon) = self.coord.to_degrees();
+        // id, name, city, country, iata, icao, latitude, longitude, altitude, timezone, dst, tz,
+        // type, source
+        format!(
+            r#",{},"","","",{},{lat},{lon},0,,"","","airport","""#,
+            csv_quote(&self.name, true),
+            csv_quote(&self.icao, true),
+        )
+    }
+}
+
+pub fn write_openflights_csv(output: &mut dyn Write, airports: &[Airport]) -> io::Result<()> {
+    writeln!(output, "{HEADER}")?;
+    for apt in airports {
+        writeln!(output, "{}", apt.to_openflights_row())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::openflights::parse_openflights_csv;
+    use crate::types::field::coord::Coord;
+
+    #[test]
+    fn test_write_openflights_csv() {
+        let airports = [
+            Airport {
+                icao: "KLAX".to_string(),
+                name: "LOS ANGELES \"INTL\"".to_string(),
+                coord: Coord::try_from((33.9425, -118.408)).unwrap(),
+            },
+            Airport {
+                icao: "AYGA".to_string(),
+                name: "Goroka, PNG".to_string(),
+                coord: Coord::try_from((-6.08, 145.39)).unwrap(),
+            },
+        ];
+        assert_eq!(
+            airports[0].to_openflights_row(),
+            format!(
+                r#","LOS ANGELES ""INTL""","","","","KLAX",{},{},0,,"","","airport","""#,
+                airports[0].coord.to_degrees().0,
+                airports[0].coord.to_degrees().1
+            )
+        );
+
+        let mut buf = vec![];
+        write_openflights_csv(&mut buf, &airports).unwrap();
+        let parsed: Vec<_> = parse_openflights_csv(&buf[..])
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(parsed.len(), 2);
+        for (parsed, apt) in parsed.iter().zip(&airports) {
+            assert_eq!(parsed.icao, apt.icao);
+            assert_eq!(parsed.name, apt.name);
+            assert!((parsed.coord.lat - apt.coord.lat).abs() < 1e-12);
+            assert!((parsed.coord.lon - apt.coord.lon).abs() < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use crate::parser::record::ENTRY_LEN;
+use crate::serializer::field::section_code::serialize_enriched_section_code;
+use crate::serializer::field::{
+    serialize_airport_elevation, serialize_airport_reference_point_latitude,
+    serialize_airport_reference_point_longitude, serialize_continuation_record_number,
+    serialize_cycle_date, serialize_daylight_indicator, serialize_ifr_capability,
+    serialize_longest_runway_surface_code, serialize_magnetic_true_indicator,
+    serialize_magnetic_variation, serialize_public_military_indicator,
+    serialize_recommended_navaid, serialize_record_type, serialize_speed_limit,
+    serialize_speed_limit_altitude, serialize_time_zone, serialize_transition_altitude,
+};
+use crate::types::record::AirportPrimaryRecord;
+use crate::util::{write_alpha, write_blank, write_num};
+
+pub fn serialize_airport_primary_record(rec: &AirportPrimaryRecord, buf: &mut [u8; ENTRY_LEN]) {
+    buf[0] = serialize_record_type(rec.record_type); // 5.2
+    write_alpha(rec.customer_area_code, &mut buf[1..4]); // 5.3
+    let (section_code, subsection_code) =
+        serialize_enriched_section_code(rec.enriched_section_code);
+    buf[4] = section_code; // 5.4
+    buf[5] = b' ';
+    write_alpha(rec.icao_identifier, &mut buf[6..10]); // 5.6
+    write_alpha(rec.icao_code, &mut buf[10..12]); // 5.14
+    buf[12] = subsection_code; // 5.5
+    write_alpha(rec.ata_designator, &mut buf[13..16]); // 5.107
+    write_blank(&mut buf[16..21]);
+    buf[21] = serialize_continuation_record_number(rec.continuation_record_number); // 5.16
+    serialize_speed_limit_altitude(rec.speed_limit_altitude, &mut buf[22..27]); // 5.73
+    write_num(rec.longest_runway as u32, &mut buf[27..30]); // 5.54
+    buf[30] = serialize_ifr_capability(rec.ifr_capability); // 5.108
+    buf[31] = serialize_longest_runway_surface_code(rec.longest_runway_surface_code); // 5.249
+    serialize_airport_reference_point_latitude(
+        &rec.airport_reference_point_latitude,
+        &mut buf[32..41],
+    ); // 5.36
+    serialize_airport_reference_point_longitude(
+        &rec.airport_reference_point_longitude,
+        &mut buf[41..51],
+    ); // 5.37
+    serialize_magnetic_variation(rec.magnetic_variation, &mut buf[51..56]); // 5.39
+    serialize_airport_elevation(rec.airport_elevation, &mut buf[56..61]); // 5.55
+    serialize_speed_limit(rec.speed_limit, &mut buf[61..64]); // 5.72
+    serialize_recommended_navaid(rec.recommended_navaid, &mut buf[64..68]); // 5.23
+
+    // ICAO code of the recommended navaid, which the parser merges into the airport one
+    write_alpha(
+        rec.recommended_navaid.map_or("", |_| rec.icao_code),
+        &mut buf[68..70],
+    ); // 5.14
+    serialize_transition_altitude(rec.transition_altitude, &mut buf[70..75]); // 5.53
+    serialize_transition_altitude(rec.transition_level, &mut buf[75..80]); // 5.53
+    buf[80] = serialize_public_military_indicator(rec.public_military_indicator); // 5.177
+    serialize_time_zone(rec.time_zone, &mut buf[81..84]); // 5.178
+    buf[84] = serialize_daylight_indicator(rec.daylight_indicator); // 5.179
+    buf[85] = serialize_magnetic_true_indicator(rec.magnetic_true_indicator); // 5.165
+    write_alpha(rec.datum_code, &mut buf[86..89]); // 5.197
+    write_blank(&mut buf[89..93]);
+    write_alpha(rec.airport_name, &mut buf[93..123]); // 5.71
+    write_num(rec.file_record_number, &mut buf[123..128]); // 5.31
+    serialize_cycle_date(rec.cycle_date, &mut buf[128..132]); // 5.32
+}
+
+impl<'a> AirportPrimaryRecord<'a> {
+    pub fn to_bytes(&self) -> [u8; ENTRY_LEN] {
+        let mut buf = [b' '; ENTRY_LEN];
+        serialize_airport_primary_record(self, &mut buf);
+        buf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::record::parse_airport_primary_record;
+    use crate::test_util::{KDEN, KJFK, KLAX, KSEA, KTPA};
+
+    const RECORDS: [&[u8]; 5] = [
+        KLAX.as_bytes(),
+        KSEA.as_bytes(),
+        KDEN.as_bytes(),
+        KJFK.as_bytes(),
+        KTPA.as_bytes(),
+    ];
+
+    #[test]
+    fn round_trip() {
+        for record in RECORDS {
+            let parsed = parse_airport_primary_record(record).unwrap();
+            let mut buf = [0; ENTRY_LEN];
+            serialize_airport_primary_record(&parsed, &mut buf);
+            assert_eq!(parse_airport_primary_record(&buf), Some(parsed));
+            assert_eq!(&buf[..], record);
+        }
+    }
+
+    #[test]
+    fn serialize_klax() {
+        let record = KLAX.as_bytes();
+        let parsed = parse_airport_primary_record(record).unwrap();
+        assert_eq!(&parsed.to_bytes(), record);
+    }
+}
@@ -0,0 +1,333 @@
+pub mod brute_force;
+
+use crate::aco::{Aco, AcoInit, AcoMode, AcoProgress, AcoResult, AcoVariant};
+use crate::config::AcoConfig;
+use crate::distance::DistancesIdx;
+use crate::graph::GraphIdx;
+use crate::kahan::KahanAdder;
+use crate::util::cycling;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// Algorithm finding short closed tour visiting every node once
+pub trait TspSolver {
+    /// Returns tour and its length, empty tour with `NaN` length if no tour is found
+    fn solve(&mut self, distances: &DistancesIdx) -> Result<(Vec<u32>, f64), TspError>;
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TspError {
+    /// Instance has more nodes than the solver handles
+    TooLarge,
+}
+
+impl Display for TspError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TspError::TooLarge => write!(f, "too many nodes for the solver"),
+        }
+    }
+}
+
+impl Error for TspError {}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TourError {
+    /// Tour has no nodes while the graph has some
+    Empty,
+    /// Tour has fewer nodes than the graph
+    TooShort,
+    DuplicateNode(u32),
+    /// Node of the graph is not visited
+    MissingNode(u32),
+    /// Consecutive nodes have no feasible leg between them
+    DisconnectedEdge(u32, u32),
+}
+
+impl Display for TourError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TourError::Empty => write!(f, "tour is empty"),
+            TourError::TooShort => write!(f, "tour has fewer nodes than the graph"),
+            TourError::DuplicateNode(node) => write!(f, "node {node} is visited more than once"),
+            TourError::MissingNode(node) => write!(f, "node {node} is not visited"),
+            TourError::DisconnectedEdge(node1, node2) => {
+                write!(f, "no feasible leg between {node1} and {node2}")
+            }
+        }
+    }
+}
+
+impl Error for TourError {}
+
+/// Checks that `tour` is a Hamiltonian cycle of feasible legs and returns its length
+pub fn validate_tour(tour: &[u32], distances: &DistancesIdx) -> Result<f64, TourError> {
+    validate(tour, distances, true)
+}
+
+/// Same as [`validate_tour`] without the closing leg
+pub fn validate_path(path: &[u32], distances: &DistancesIdx) -> Result<f64, TourError> {
+    validate(path, distances, false)
+}
+
+fn validate(tour: &[u32], distances: &DistancesIdx, closed: bool) -> Result<f64, TourError> {
+    let size = distances.graph.size();
+    if tour.is_empty() && size > 0 {
+        return Err(TourError::Empty);
+    }
+    let mut visited = HashSet::with_capacity(tour.len());
+    if let Some(&duplicate) = tour.iter().find(|&&node| !visited.insert(node)) {
+        return Err(TourError::DuplicateNode(duplicate));
+    }
+    if tour.len() < size as usize {
+        return Err(TourError::TooShort);
+    }
+    if let Some(missing) = (0..size).find(|node| !visited.contains(node)) {
+        return Err(TourError::MissingNode(missing));
+    }
+    let legs = match (closed, tour.len()) {
+        (_, 0 | 1) => 0,
+        (true, len) => len,
+        (false, len) => len - 1,
+    };
+    cycling(tour)
+        .take(legs)
+        .map(|(&node1, &node2)| {
+            distances
+                .between(node1, node2)
+                .ok_or(TourError::DisconnectedEdge(node1, node2))
+        })
+        .try_fold(KahanAdder::default(), |acc, dist| {
+            dist.map(|dist| acc.push(dist))
+        })
+        .map(KahanAdder::result)
+}
+
+/// Ant colony optimization run with parameters of `config` and the other options for each
+/// instance
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AcoSolver {
+    pub config: AcoConfig,
+    pub opt_dist: Option<f64>,
+    pub variant: AcoVariant,
+    pub init: AcoInit,
+    pub mode: AcoMode,
+    /// Keep the best-so-far cycle of every iteration in [`AcoResult::frames`]
+    pub capture_frames: bool,
+}
+
+impl AcoSolver {
+    /// Colony over `distances` with every option of the solver
+    pub fn aco<'a>(&self, distances: &'a DistancesIdx<'a>) -> Aco<'a> {
+        let mut aco = Aco::new_with_variant(distances, None, None, self.opt_dist, self.variant)
+            .with_init(self.init)
+            .with_mode(self.mode);
+        if let Some(seed) = self.config.seed {
+            aco = aco.with_seed(seed);
+        }
+        if let Some(k) = self.config.candidates {
+            aco = aco.with_candidates(k);
+        }
+        if self.capture_frames {
+            aco = aco.with_capture_frames();
+        }
+        aco
+    }
+
+    /// Runs the colony reporting to `progress`, returns pheromones after the last iteration
+    /// along with the result
+    pub fn solve_with_intensities<'a>(
+        &self,
+        distances: &'a DistancesIdx<'a>,
+        progress: Option<&dyn AcoProgress>,
+    ) -> (AcoResult, GraphIdx<'a, Option<f64>>) {
+        let config = &self.config;
+        self.aco(distances).aco_with_intensities(
+            config.iterations,
+            config.ants,
+            1.0 - config.evaporation,
+            config.alpha,
+            config.beta,
+            config.elite_ants,
+            config.stagnation_limit,
+            None,
+            progress,
+        )
+    }
+}
+
+impl TspSolver for AcoSolver {
+    fn solve(&mut self, distances: &DistancesIdx) -> Result<(Vec<u32>, f64), TspError> {
+        let config = &self.config;
+        let result = self.aco(distances).aco(
+            config.iterations,
+            config.ants,
+            1.0 - config.evaporation,
+            config.alpha,
+            config.beta,
+            config.elite_ants,
+            config.stagnation_limit,
+            None,
+            None,
+        );
+        Ok((result.tour, result.dist))
+    }
+}
+
+/// Greedy tour going from `start` to the nearest not visited node
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct NearestNeighborSolver {
+    pub start: u32,
+}
+
+impl TspSolver for NearestNeighborSolver {
+    fn solve(&mut self, distances: &DistancesIdx) -> Result<(Vec<u32>, f64), TspError> {
+        if distances.graph.size() == 0 {
+            return Ok((vec![], 0.0));
+        }
+        Ok(distances
+            .nearest_neighbor_tour(self.start)
+            .unwrap_or((vec![], f64::NAN)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::random_points;
+    use rand::Rng;
+    use rand_pcg::Pcg64Mcg;
+
+    fn assert_hamiltonian_cycle(distances: &DistancesIdx, tour: &[u32], dist: f64) {
+        let mut sorted = tour.to_vec();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..distances.graph.size()).collect::<Vec<_>>());
+        assert_eq!(distances.tour_distance(tour), Some(dist));
+    }
+
+    #[test]
+    fn test_nearest_neighbor_solver() {
+        let mut rng = Pcg64Mcg::new(42);
+        for n in 1..30 {
+            let distances = random_points(n, rng.gen());
+            let start = rng.gen_range(0..n as u32);
+            let (tour, dist) = NearestNeighborSolver { start }.solve(&distances).unwrap();
+            assert_eq!(tour[0], start);
+            assert_hamiltonian_cycle(&distances, &tour, dist);
+        }
+
+        assert_eq!(
+            NearestNeighborSolver::default().solve(&DistancesIdx::default()),
+            Ok((vec![], 0.0))
+        );
+        let stuck = DistancesIdx::from_custom_fn(3, |apt1, _| Some(1.0).filter(|_| apt1 == 1));
+        let (tour, dist) = NearestNeighborSolver::default().solve(&stuck).unwrap();
+        assert!(tour.is_empty());
+        assert!(dist.is_nan());
+    }
+
+    #[test]
+    fn test_validate_tour() {
+        let square = DistancesIdx::from_custom_fn(4, |apt1, apt2| {
+            Some(1.0).filter(|_| apt1.abs_diff(apt2) != 2)
+        });
+        assert_eq!(validate_tour(&[0, 1, 2, 3], &square), Ok(4.0));
+        assert_eq!(validate_path(&[0, 1, 2, 3], &square), Ok(3.0));
+        assert_eq!(validate_tour(&[], &square), Err(TourError::Empty));
+        assert_eq!(validate_tour(&[0, 1, 2], &square), Err(TourError::TooShort));
+        assert_eq!(
+            validate_tour(&[0, 1, 2, 1], &square),
+            Err(TourError::DuplicateNode(1))
+        );
+        assert_eq!(
+            validate_tour(&[0, 1, 2, 4], &square),
+            Err(TourError::MissingNode(3))
+        );
+        assert_eq!(
+            validate_tour(&[0, 2, 1, 3], &square),
+            Err(TourError::DisconnectedEdge(0, 2))
+        );
+        assert_eq!(
+            validate_path(&[1, 0, 3, 2], &square),
+            Ok(3.0),
+            "the closing leg is not checked"
+        );
+        assert_eq!(validate_tour(&[], &DistancesIdx::default()), Ok(0.0));
+        assert_eq!(
+            validate_tour(&[0], &DistancesIdx::from_custom_fn(1, |_, _| None)),
+            Ok(0.0)
+        );
+    }
+
+    #[test]
+    fn test_aco_tours_are_valid() {
+        let mut rng = Pcg64Mcg::new(42);
+        for n in 2..20 {
+            let distances = random_points(n, rng.gen());
+            let mut solver = AcoSolver {
+                config: AcoConfig {
+                    ants: 5,
+                    iterations: 5,
+                    seed: Some(rng.gen()),
+                    ..AcoConfig::default()
+                },
+                ..AcoSolver::default()
+            };
+            let (tour, dist) = solver.solve(&distances).unwrap();
+            assert_eq!(validate_tour(&tour, &distances), Ok(dist));
+        }
+    }
+
+    #[test]
+    fn test_aco_solver() {
+        let distances = random_points(10, 42);
+        let mut solvers: [Box<dyn TspSolver>; 2] = [
+            Box::new(AcoSolver {
+                config: AcoConfig {
+                    ants: 10,
+                    iterations: 20,
+                    seed: Some(42),
+                    ..AcoConfig::default()
+                },
+                ..AcoSolver::default()
+            }),
+            Box::new(NearestNeighborSolver::default()),
+        ];
+        for solver in &mut solvers {
+            let (tour, dist) = solver.solve(&distances).unwrap();
+            assert_hamiltonian_cycle(&distances, &tour, dist);
+        }
+    }
+
+    #[test]
+    fn test_aco_solver_options() {
+        let distances = random_points(10, 42);
+        let solver = AcoSolver {
+            config: AcoConfig {
+                ants: 10,
+                iterations: 20,
+                seed: Some(42),
+                ..AcoConfig::default()
+            },
+            variant: AcoVariant::Mmas { tau_max_init: 1.0 },
+            init: AcoInit::NearestNeighbor,
+            mode: AcoMode::Open {
+                start: Some(3),
+                end: Some(7),
+            },
+            capture_frames: true,
+            ..AcoSolver::default()
+        };
+        let (result, intensities) = solver.solve_with_intensities(&distances, None);
+        assert_eq!(result.tour.len(), 10);
+        assert_eq!((result.tour[0], result.tour[9]), (3, 7));
+        assert_eq!(validate_path(&result.tour, &distances), Ok(result.dist));
+        assert_eq!(result.frames.len(), result.converged_at as usize);
+        assert_eq!(intensities.size, 10);
+        assert_eq!(
+            solver.clone().solve(&distances).unwrap(),
+            (result.tour, result.dist)
+        );
+    }
+}
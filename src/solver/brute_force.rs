@@ -0,0 +1,159 @@
+use crate::distance::DistancesIdx;
+use crate::solver::{TspError, TspSolver};
+
+pub const DEFAULT_MAX_NODES: u32 = 12;
+
+/// Exact solver trying all `(n - 1)!` tours starting at node 0, for ground truth on small
+/// instances. Partial tours not shorter than the best one found are not extended.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BruteForceSolver {
+    pub max_nodes: u32,
+}
+
+impl Default for BruteForceSolver {
+    fn default() -> Self {
+        Self {
+            max_nodes: DEFAULT_MAX_NODES,
+        }
+    }
+}
+
+impl TspSolver for BruteForceSolver {
+    fn solve(&mut self, distances: &DistancesIdx) -> Result<(Vec<u32>, f64), TspError> {
+        let size = distances.graph.size();
+        if size > self.max_nodes {
+            return Err(TspError::TooLarge);
+        }
+        match size {
+            0 => return Ok((vec![], 0.0)),
+            1 => return Ok((vec![0], 0.0)),
+            _ => {}
+        }
+
+        let mut tour = vec![0];
+        let mut visited = vec![false; size as usize];
+        visited[0] = true;
+        let mut best = None;
+        search(distances, &mut tour, &mut visited, 0.0, &mut best);
+        Ok(best
+            .and_then(|(best, _)| distances.tour_distance(&best).map(|dist| (best, dist)))
+            .unwrap_or((vec![], f64::NAN)))
+    }
+}
+
+fn search(
+    distances: &DistancesIdx,
+    tour: &mut Vec<u32>,
+    visited: &mut [bool],
+    length: f64,
+    best: &mut Option<(Vec<u32>, f64)>,
+) {
+    let best_length = |best: &Option<(Vec<u32>, f64)>| {
+        best.as_ref()
+            .map_or(f64::INFINITY, |&(_, best_length)| best_length)
+    };
+    let current = *tour
+        .last()
+        .unwrap_or_else(|| unreachable!("Tour starts at node 0"));
+    if tour.len() == visited.len() {
+        if let Some(closing) = distances.between(current, 0) {
+            if length + closing < best_length(best) {
+                *best = Some((tour.clone(), length + closing));
+            }
+        }
+        return;
+    }
+    for next in 0..visited.len() as u32 {
+        if visited[next as usize] {
+            continue;
+        }
+        let Some(leg) = distances.between(current, next) else {
+            continue;
+        };
+        if length + leg >= best_length(best) {
+            continue;
+        }
+        visited[next as usize] = true;
+        tour.push(next);
+        search(distances, tour, visited, length + leg, best);
+        tour.pop();
+        visited[next as usize] = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aco::Aco;
+    use crate::config::AcoConfig;
+    use crate::solver::{AcoSolver, NearestNeighborSolver};
+    use std::f64::consts::PI;
+
+    fn hexagon() -> DistancesIdx<'static> {
+        // vertices of the unit regular hexagon in shuffled order
+        let angles = [0, 3, 1, 5, 2, 4].map(|k| k as f64 * PI / 3.0);
+        DistancesIdx::from_custom_fn(6, move |apt1, apt2| {
+            let (a1, a2) = (angles[apt1 as usize], angles[apt2 as usize]);
+            Some(f64::hypot(a1.cos() - a2.cos(), a1.sin() - a2.sin()))
+        })
+    }
+
+    #[test]
+    fn test_brute_force_hexagon() {
+        let distances = hexagon();
+        let (tour, dist) = BruteForceSolver::default().solve(&distances).unwrap();
+        assert_eq!(tour[0], 0);
+        assert_eq!(tour.len(), 6);
+        // the perimeter of six unit sides
+        assert!((dist - 6.0).abs() < 1e-12);
+
+        let (aco_tour, aco_dist) = AcoSolver {
+            config: AcoConfig {
+                ants: 10,
+                iterations: 30,
+                seed: Some(42),
+                ..AcoConfig::default()
+            },
+            ..AcoSolver::default()
+        }
+        .solve(&distances)
+        .unwrap();
+        assert_eq!(aco_tour.len(), 6);
+        assert!((aco_dist - dist).abs() < 1e-12);
+
+        let (nn_tour, _) = NearestNeighborSolver::default().solve(&distances).unwrap();
+        let (_, opt_dist) = Aco::new(&distances, None, None, None).three_opt(&nn_tour);
+        assert!((opt_dist - dist).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_brute_force_sparse() {
+        // the only feasible cycle is 0 2 4 1 3
+        let ring = [0, 2, 4, 1, 3];
+        let distances = DistancesIdx::from_custom_fn(5, |apt1, apt2| {
+            let pos = |apt| ring.iter().position(|&node| node == apt).unwrap();
+            Some(1.0).filter(|_| matches!((pos(apt1) + 5 - pos(apt2)) % 5, 1 | 4))
+        });
+        let (tour, dist) = BruteForceSolver::default().solve(&distances).unwrap();
+        assert!(tour == ring || tour == [0, 3, 1, 4, 2]);
+        assert_eq!(dist, 5.0);
+
+        let infeasible = DistancesIdx::from_custom_fn(3, |apt1, _| Some(1.0).filter(|_| apt1 == 1));
+        let (tour, dist) = BruteForceSolver::default().solve(&infeasible).unwrap();
+        assert!(tour.is_empty());
+        assert!(dist.is_nan());
+    }
+
+    #[test]
+    fn test_brute_force_too_large() {
+        let distances = DistancesIdx::from_custom_fn(5, |_, _| Some(1.0));
+        assert_eq!(
+            BruteForceSolver { max_nodes: 4 }.solve(&distances),
+            Err(TspError::TooLarge)
+        );
+        assert_eq!(
+            BruteForceSolver::default().solve(&DistancesIdx::default()),
+            Ok((vec![], 0.0))
+        );
+    }
+}
@@ -0,0 +1,469 @@
+use crate::algorithms::union_find::UnionFind;
+use crate::graph::GraphIdx;
+use crate::kahan::kahan_sum;
+use crate::model::{Airport, AirportIdx};
+use crate::par::prelude::*;
+use std::marker::PhantomData;
+
+/// Alternative to [`GraphIdx`] for graphs with mostly absent edges. Row `apt1` of the adjacency
+/// list holds `(apt2, value)` for present edges with `apt1 > apt2`, sorted by `apt2`
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SparseGraphIdx<'a, T: Copy> {
+    pub(crate) size: u32,
+    pub(crate) adjacency: Vec<Vec<(u32, T)>>,
+    pub(crate) _pd: PhantomData<AirportIdx<'a>>,
+}
+
+impl<'a, T: Copy> SparseGraphIdx<'a, T> {
+    pub fn between(&self, apt1: u32, apt2: u32) -> Option<T> {
+        let (row, pos) = self.find(apt1, apt2)?;
+        pos.ok().map(|pos| self.adjacency[row][pos].1)
+    }
+
+    pub fn between_mut(&mut self, apt1: u32, apt2: u32) -> Option<&mut T> {
+        let (row, pos) = self.find(apt1, apt2)?;
+        pos.ok().map(|pos| &mut self.adjacency[row][pos].1)
+    }
+
+    // row of the edge and position in it, or position to insert the edge at
+    fn find(&self, apt1: u32, apt2: u32) -> Option<(usize, Result<usize, usize>)> {
+        if apt1 >= self.size || apt2 >= self.size || apt1 == apt2 {
+            return None;
+        }
+        let (apt1, apt2) = if apt1 > apt2 {
+            (apt1, apt2)
+        } else {
+            (apt2, apt1)
+        };
+        let row = apt1 as usize;
+        Some((
+            row,
+            self.adjacency[row].binary_search_by_key(&apt2, |&(apt, _)| apt),
+        ))
+    }
+
+    pub fn set(&mut self, apt1: u32, apt2: u32, val: T) -> Option<()> {
+        let (row, pos) = self.find(apt1, apt2)?;
+        match pos {
+            Ok(pos) => self.adjacency[row][pos].1 = val,
+            Err(pos) => self.adjacency[row].insert(pos, (apt1.min(apt2), val)),
+        }
+        Some(())
+    }
+
+    /// Keeps only edges for which `f` returns a value
+    pub fn new(
+        AirportIdx { aps, .. }: &'a AirportIdx,
+        f: impl Fn(&Airport, &Airport) -> Option<T>,
+    ) -> Self {
+        let size = aps.len() as u32;
+        let adjacency = aps
+            .iter()
+            .enumerate()
+            .map(|(apt1_i, apt1)| {
+                aps[..apt1_i]
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(apt2_i, apt2)| f(apt1, apt2).map(|val| (apt2_i as u32, val)))
+                    .collect()
+            })
+            .collect();
+        Self {
+            size,
+            adjacency,
+            _pd: PhantomData,
+        }
+    }
+
+    /// Number of present edges
+    pub fn len(&self) -> usize {
+        self.adjacency.iter().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adjacency.iter().all(Vec::is_empty)
+    }
+
+    pub fn transform<B: Copy>(&self, f: impl Fn(T) -> B) -> SparseGraphIdx<'a, B> {
+        SparseGraphIdx {
+            size: self.size,
+            adjacency: self
+                .adjacency
+                .iter()
+                .map(|row| row.iter().map(|&(apt, a)| (apt, f(a))).collect())
+                .collect(),
+            _pd: PhantomData,
+        }
+    }
+
+    pub fn transform_inplace_par(&mut self, f: impl Fn(&mut T) + Sync + Send)
+    where
+        T: Send,
+    {
+        self.adjacency
+            .par_iter_mut()
+            .flat_map(|row| row.par_iter_mut())
+            .for_each(|(_, edge)| f(edge));
+    }
+
+    /// Edges present in any of graphs are passed to `f`, the result keeps those it returns
+    pub fn merge<B: Copy, C: Copy>(
+        &self,
+        other: &SparseGraphIdx<'a, B>,
+        f: impl Fn(Option<T>, Option<B>) -> Option<C>,
+    ) -> Option<SparseGraphIdx<'a, C>> {
+        if self.size != other.size {
+            return None;
+        }
+        let adjacency = self
+            .adjacency
+            .iter()
+            .zip(&other.adjacency)
+            .map(|(row, other_row)| {
+                let mut merged = vec![];
+                merge_rows(row, other_row, &f, &mut merged);
+                merged
+            })
+            .collect();
+        Some(SparseGraphIdx {
+            size: self.size,
+            adjacency,
+            _pd: PhantomData,
+        })
+    }
+
+    /// Merges with the dense graph into the dense target, absent edges are passed as `None`
+    pub fn merge_parallel_into<B, C>(
+        &self,
+        other: &GraphIdx<'a, B>,
+        target: &mut GraphIdx<'a, C>,
+        f: impl (Fn(Option<T>, B) -> C) + Sync,
+    ) -> Option<()>
+    where
+        T: Send + Sync,
+        B: Send + Sync + Copy,
+        C: Send + Sync + Copy,
+    {
+        if self.size != other.size {
+            return None;
+        }
+        target.size = self.size;
+        target.edges.clear();
+        target.edges.par_extend(
+            self.adjacency
+                .par_iter()
+                .enumerate()
+                .flat_map_iter(|(apt1, row)| {
+                    let mut row = row.iter().peekable();
+                    (0..apt1 as u32).map(move |apt2| {
+                        (
+                            apt1 as u32,
+                            apt2,
+                            row.next_if(|&&(apt, _)| apt == apt2).map(|&(_, a)| a),
+                        )
+                    })
+                })
+                .map(|(apt1, apt2, a)| f(a, other.edges[GraphIdx::<B>::pos(apt1, apt2)])),
+        );
+        Some(())
+    }
+
+    /// Same as [`SparseGraphIdx::merge`], but reuses rows of `target` and merges them in parallel
+    pub fn merge_parallel_into_sparse<B, C>(
+        &self,
+        other: &SparseGraphIdx<'a, B>,
+        target: &mut SparseGraphIdx<'a, C>,
+        f: impl (Fn(Option<T>, Option<B>) -> Option<C>) + Sync,
+    ) -> Option<()>
+    where
+        T: Send + Sync,
+        B: Send + Sync + Copy,
+        C: Send + Sync + Copy,
+    {
+        if self.size != other.size {
+            return None;
+        }
+        target.size = self.size;
+        target.adjacency.resize_with(self.adjacency.len(), Vec::new);
+        target
+            .adjacency
+            .par_iter_mut()
+            .zip(&self.adjacency)
+            .zip(&other.adjacency)
+            .for_each(|((merged, row), other_row)| {
+                merged.clear();
+                merge_rows(row, other_row, &f, merged);
+            });
+        Some(())
+    }
+
+    /// Present edges as `(apt1, apt2, value)` with `apt1 > apt2`, in the storage order
+    pub fn iter_edges(&self) -> impl Iterator<Item = (u32, u32, T)> + '_ {
+        self.adjacency
+            .iter()
+            .enumerate()
+            .flat_map(|(apt1, row)| row.iter().map(move |&(apt2, a)| (apt1 as u32, apt2, a)))
+    }
+
+    /// Fraction of present edges, `1.0` for graphs without edges
+    pub fn density(&self) -> f64 {
+        let pairs = self.size as usize * self.size.saturating_sub(1) as usize / 2;
+        if pairs == 0 {
+            return 1.0;
+        }
+        self.len() as f64 / pairs as f64
+    }
+
+    /// Builds the graph over `nodes` only, in their order. Returns `None` if any node is out of
+    /// range or repeated.
+    pub fn subgraph(&self, nodes: &[u32]) -> Option<Self> {
+        let mut positions = vec![None; self.size as usize];
+        for (pos, &node) in nodes.iter().enumerate() {
+            if node >= self.size || positions[node as usize].replace(pos as u32).is_some() {
+                return None;
+            }
+        }
+        let mut adjacency = vec![vec![]; nodes.len()];
+        for (apt1, apt2, edge) in self.iter_edges() {
+            if let (Some(pos1), Some(pos2)) = (positions[apt1 as usize], positions[apt2 as usize]) {
+                adjacency[pos1.max(pos2) as usize].push((pos1.min(pos2), edge));
+            }
+        }
+        for row in &mut adjacency {
+            row.sort_unstable_by_key(|&(apt, _)| apt);
+        }
+        Some(Self {
+            size: nodes.len() as u32,
+            adjacency,
+            _pd: PhantomData,
+        })
+    }
+
+    /// Appends the row of the new last node, `distances_to_existing[i]` is the edge to node `i`
+    pub fn extend_by_one_node(&self, distances_to_existing: Vec<Option<T>>) -> Self {
+        let mut graph = self.clone();
+        graph.extend_by_one_node_inplace(distances_to_existing);
+        graph
+    }
+
+    /// Same as [`SparseGraphIdx::extend_by_one_node`] without copying existing edges
+    pub fn extend_by_one_node_inplace(&mut self, distances_to_existing: Vec<Option<T>>) {
+        assert_eq!(
+            distances_to_existing.len(),
+            self.size as usize,
+            "New node should have edges to all existing nodes"
+        );
+        self.adjacency.push(
+            distances_to_existing
+                .into_iter()
+                .enumerate()
+                .filter_map(|(apt, edge)| edge.map(|edge| (apt as u32, edge)))
+                .collect(),
+        );
+        self.size += 1;
+    }
+}
+
+impl<'a> SparseGraphIdx<'a, f64> {
+    pub fn triangle_sum(&self) -> f64 {
+        kahan_sum(self.iter_edges().map(|(_, _, edge)| edge))
+    }
+
+    /// For each node, up to `k` nodes reachable by present edges, nearest first
+    pub fn k_nearest(&self, k: u32) -> Vec<Vec<u32>> {
+        let mut nearest = vec![vec![]; self.size as usize];
+        for (apt1, apt2, dist) in self.iter_edges() {
+            nearest[apt1 as usize].push((apt2, dist));
+            nearest[apt2 as usize].push((apt1, dist));
+        }
+        nearest
+            .into_iter()
+            .map(|mut nearest| {
+                nearest.sort_unstable_by(|(_, dist1), (_, dist2)| dist1.total_cmp(dist2));
+                nearest.truncate(k as usize);
+                nearest.into_iter().map(|(apt2, _)| apt2).collect()
+            })
+            .collect()
+    }
+
+    /// Whether every node is reachable from any other by present edges
+    pub fn is_connected(&self) -> bool {
+        let mut components = UnionFind::new(self.size);
+        let mut count = self.size;
+        for (apt1, apt2, _) in self.iter_edges() {
+            if components.union(apt1, apt2) {
+                count -= 1;
+            }
+        }
+        count <= 1
+    }
+}
+
+// merges rows sorted by node, keeping values `f` returns for nodes present in any of them
+fn merge_rows<T: Copy, B: Copy, C>(
+    row: &[(u32, T)],
+    other_row: &[(u32, B)],
+    f: impl Fn(Option<T>, Option<B>) -> Option<C>,
+    merged: &mut Vec<(u32, C)>,
+) {
+    let (mut row, mut other_row) = (row.iter().peekable(), other_row.iter().peekable());
+    loop {
+        let (apt, a, b) = match (row.peek(), other_row.peek()) {
+            (None, None) => break,
+            (Some(&&(apt, a)), Some(&&(other_apt, _))) if apt < other_apt => {
+                row.next();
+                (apt, Some(a), None)
+            }
+            (Some(&&(apt, a)), Some(&&(other_apt, b))) if apt == other_apt => {
+                row.next();
+                other_row.next();
+                (apt, Some(a), Some(b))
+            }
+            (_, Some(&&(apt, b))) => {
+                other_row.next();
+                (apt, None, Some(b))
+            }
+            (Some(&&(apt, a)), None) => {
+                row.next();
+                (apt, Some(a), None)
+            }
+        };
+        if let Some(c) = f(a, b) {
+            merged.push((apt, c));
+        }
+    }
+}
+
+impl<'a, T: Copy> From<&GraphIdx<'a, Option<T>>> for SparseGraphIdx<'a, T> {
+    fn from(graph: &GraphIdx<'a, Option<T>>) -> Self {
+        let mut adjacency = vec![vec![]; graph.size as usize];
+        for (apt1, apt2, edge) in graph.iter_edges() {
+            if let Some(edge) = edge {
+                adjacency[apt1 as usize].push((apt2, edge));
+            }
+        }
+        Self {
+            size: graph.size,
+            adjacency,
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: Copy> From<&SparseGraphIdx<'a, T>> for GraphIdx<'a, Option<T>> {
+    fn from(graph: &SparseGraphIdx<'a, T>) -> Self {
+        let mut dense = GraphIdx {
+            size: graph.size,
+            edges: vec![None; (graph.size as usize * graph.size.saturating_sub(1) as usize) / 2],
+            _pd: PhantomData,
+        };
+        for (apt1, row) in graph.adjacency.iter().enumerate() {
+            for &(apt2, edge) in row {
+                dense.set(apt1 as u32, apt2, Some(edge));
+            }
+        }
+        dense
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::field::coord::Coord;
+
+    // 0 - 1 - 2 - 3 chain with a long shortcut between 0 and 2, node 4 is isolated
+    fn chain_graph() -> GraphIdx<'static, Option<f64>> {
+        let mut graph = GraphIdx {
+            size: 5,
+            edges: vec![None; 10],
+            _pd: PhantomData,
+        };
+        graph.set(0, 1, Some(1.0)).unwrap();
+        graph.set(1, 2, Some(1.0)).unwrap();
+        graph.set(2, 3, Some(1.0)).unwrap();
+        graph.set(0, 2, Some(5.0)).unwrap();
+        graph
+    }
+
+    #[test]
+    fn test_sparse_graph_idx() {
+        let graph = chain_graph();
+        assert_eq!(graph.density(), 0.4);
+
+        let mut sparse = SparseGraphIdx::from(&graph);
+        assert_eq!(sparse.len(), 4);
+        assert_eq!(sparse.between(2, 0), Some(5.0));
+        assert_eq!(sparse.between(0, 2), Some(5.0));
+        assert_eq!(sparse.between(0, 3), None);
+        *sparse.between_mut(1, 2).unwrap() = 2.0;
+        sparse.set(3, 4, 7.0).unwrap();
+        assert_eq!(sparse.set(4, 5, 1.0), None);
+        sparse.transform_inplace_par(|edge| *edge *= 2.0);
+        let sparse = sparse.transform(|edge| edge + 1.0);
+        assert_eq!(sparse.between(4, 3), Some(15.0));
+        assert_eq!(
+            GraphIdx::from(&sparse).between(None, 2, 0),
+            Some(Some(11.0))
+        );
+
+        let mut target = GraphIdx::default();
+        sparse
+            .merge_parallel_into(&graph, &mut target, |a, b| a.zip(b).map(|(a, b)| a - b))
+            .unwrap();
+        assert_eq!(target.size, 5);
+        assert_eq!(target.between(None, 0, 1), Some(Some(2.0)));
+        assert_eq!(target.between(None, 1, 2), Some(Some(4.0)));
+        assert_eq!(target.between(None, 2, 0), Some(Some(6.0)));
+        assert_eq!(target.between(None, 3, 4), Some(None));
+        assert_eq!(target.between(None, 0, 3), Some(None));
+        assert_eq!(
+            sparse.merge_parallel_into(&graph.subgraph(&[0]).unwrap(), &mut target, |a, _| a),
+            None
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let chain = SparseGraphIdx::from(&chain_graph());
+        let mut other = SparseGraphIdx::from(&chain_graph().transform_const(None::<u32>));
+        other.set(2, 0, 1).unwrap();
+        other.set(4, 3, 2).unwrap();
+
+        let merged = chain
+            .merge(&other, |a, b| match (a, b) {
+                (Some(a), Some(b)) => Some(a + b as f64),
+                (None, Some(b)) => Some(b as f64),
+                (Some(_), None) => None,
+                (None, None) => unreachable!(),
+            })
+            .unwrap();
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged.between(0, 2), Some(6.0));
+        assert_eq!(merged.between(3, 4), Some(2.0));
+        assert_eq!(merged.between(0, 1), None);
+
+        let mut smaller = other.clone();
+        smaller.size = 4;
+        assert_eq!(chain.merge(&smaller, |a, _| a), None);
+    }
+
+    #[test]
+    fn test_new() {
+        let airports: Vec<_> = [(0.0, 0.0), (0.0, 0.1), (0.0, 1.0)]
+            .into_iter()
+            .map(|(lat, lon)| Airport {
+                icao: format!("{lat}:{lon}"),
+                name: String::new(),
+                coord: Coord { lat, lon },
+            })
+            .collect();
+        let apt_idx = AirportIdx::new(&airports).unwrap();
+        let near = SparseGraphIdx::new(&apt_idx, |apt1, apt2| {
+            Some(apt1.distance_to(apt2)).filter(|&dist| dist < 1000.0)
+        });
+        assert_eq!(near.len(), 1);
+        assert!(near.between(1, 0).is_some());
+        assert!(!near.is_empty());
+    }
+}
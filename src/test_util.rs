@@ -0,0 +1,45 @@
+//! Fixtures shared by unit tests
+
+use crate::distance::DistancesIdx;
+use rand::Rng;
+use rand_pcg::Pcg64Mcg;
+
+/// Airport primary record of Los Angeles Intl
+pub(crate) const KLAX: &str = "SUSAP KLAXK2ALAX     0     \
+    129YHN33563299W118242898E012000128         1800018000C    \
+    MNAR    LOS ANGELES INTL              310231906";
+
+/// Airport primary record of Seattle-Tacoma Intl
+pub(crate) const KSEA: &str = "SUSAP KSEAK1ASEA     0     \
+    119YHN47265960W122184240E016000432         1800018000C    \
+    MNAR    SEATTLE-TACOMA INTL           065001807";
+
+/// Airport primary record of Denver Intl
+pub(crate) const KDEN: &str = "SUSAP KDENK2ADEN     0     \
+    160YHN39514200W104402340E008005434         1800018000C    \
+    MNAR    DENVER INTL                   630481208";
+
+/// Airport primary record of John F Kennedy Intl
+pub(crate) const KJFK: &str = "SUSAP KJFKK6AJFK     0     \
+    145YHN40382374W073464329W013000013         1800018000C    \
+    MNAR    JOHN F KENNEDY INTL           257211912";
+
+/// Airport primary record of Tampa Intl
+pub(crate) const KTPA: &str = "SUSAP KTPAK7ATPA     0     \
+    110YHN27583170W082315970W005000026         1800018000C    \
+    MNAR    TAMPA INTL                    267161101";
+
+/// Euclidean distances between `coords`
+pub(crate) fn points(coords: &[(f64, f64)]) -> DistancesIdx<'static> {
+    DistancesIdx::from_custom_fn(coords.len() as u32, |apt1, apt2| {
+        let ((x1, y1), (x2, y2)) = (coords[apt1 as usize], coords[apt2 as usize]);
+        Some(f64::hypot(x1 - x2, y1 - y2))
+    })
+}
+
+/// Euclidean distances between `n` random points of the unit square
+pub(crate) fn random_points(n: usize, seed: u64) -> DistancesIdx<'static> {
+    let mut rng = Pcg64Mcg::new(seed as u128);
+    let coords: Vec<(f64, f64)> = (0..n).map(|_| (rng.gen(), rng.gen())).collect();
+    points(&coords)
+}
@@ -1,6 +1,10 @@
-use std::f64::consts::PI;
+use std::error::Error;
+use std::f64::consts::{FRAC_PI_2, PI};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Longitude {
     pub hemisphere: LongitudeHemisphere,
     pub degrees: u8,
@@ -10,12 +14,14 @@ pub struct Longitude {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LongitudeHemisphere {
     East,
     West,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Latitude {
     pub hemisphere: LatitudeHemisphere,
     pub degrees: u8,
@@ -25,6 +31,7 @@ pub struct Latitude {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LatitudeHemisphere {
     North,
     South,
@@ -60,12 +67,240 @@ impl From<&Latitude> for f64 {
     }
 }
 
+/// Degrees, minutes and seconds, e.g. `33°56′32.99″N`
+impl Display for Latitude {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let hemisphere = match self.hemisphere {
+            LatitudeHemisphere::North => 'N',
+            LatitudeHemisphere::South => 'S',
+        };
+        write_dms(
+            f,
+            self.degrees,
+            self.minutes,
+            self.seconds,
+            self.fractional_seconds,
+            hemisphere,
+        )
+    }
+}
+
+/// Degrees, minutes and seconds, e.g. `118°24′28.98″W`
+impl Display for Longitude {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let hemisphere = match self.hemisphere {
+            LongitudeHemisphere::East => 'E',
+            LongitudeHemisphere::West => 'W',
+        };
+        write_dms(
+            f,
+            self.degrees,
+            self.minutes,
+            self.seconds,
+            self.fractional_seconds,
+            hemisphere,
+        )
+    }
+}
+
+/// Parses the format of [`Display`]
+impl FromStr for Latitude {
+    type Err = ParseCoordError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (degrees, minutes, seconds, fractional_seconds, hemisphere) = parse_dms(s)?;
+        let hemisphere = match hemisphere {
+            'N' => LatitudeHemisphere::North,
+            'S' => LatitudeHemisphere::South,
+            _ => return Err(ParseCoordError::Format),
+        };
+        if degrees > 90 || degrees == 90 && (minutes, seconds, fractional_seconds) != (0, 0, 0) {
+            return Err(ParseCoordError::OutOfRange);
+        }
+        Ok(Latitude {
+            hemisphere,
+            degrees,
+            minutes,
+            seconds,
+            fractional_seconds,
+        })
+    }
+}
+
+/// Parses the format of [`Display`]
+impl FromStr for Longitude {
+    type Err = ParseCoordError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (degrees, minutes, seconds, fractional_seconds, hemisphere) = parse_dms(s)?;
+        let hemisphere = match hemisphere {
+            'E' => LongitudeHemisphere::East,
+            'W' => LongitudeHemisphere::West,
+            _ => return Err(ParseCoordError::Format),
+        };
+        if degrees > 180 || degrees == 180 && (minutes, seconds, fractional_seconds) != (0, 0, 0) {
+            return Err(ParseCoordError::OutOfRange);
+        }
+        Ok(Longitude {
+            hemisphere,
+            degrees,
+            minutes,
+            seconds,
+            fractional_seconds,
+        })
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Coord {
     pub lat: f64,
     pub lon: f64,
 }
 
+impl Coord {
+    pub fn is_valid(&self) -> bool {
+        self.lat.is_finite()
+            && self.lon.is_finite()
+            && self.lat.abs() <= FRAC_PI_2
+            && self.lon.abs() <= PI
+    }
+
+    pub fn assert_valid(&self) {
+        assert!(
+            self.is_valid(),
+            "Invalid coordinate: lat {} not in [-π/2, π/2] or lon {} not in [-π, π]",
+            self.lat,
+            self.lon
+        );
+    }
+
+    /// Latitude and longitude in decimal degrees
+    pub fn to_degrees(&self) -> (f64, f64) {
+        (self.lat / RADIANS_PER_DEGREE, self.lon / RADIANS_PER_DEGREE)
+    }
+
+    /// Top left and bottom right corners of the smallest box containing `coords`, not crossing
+    /// the date line. Returns `None` if there are no coordinates.
+    pub fn bounding_box(coords: impl IntoIterator<Item = Coord>) -> Option<(Coord, Coord)> {
+        coords.into_iter().map(|coord| (coord, coord)).reduce(
+            |(top_left, bottom_right), (coord, _)| {
+                (
+                    Coord {
+                        lat: top_left.lat.max(coord.lat),
+                        lon: top_left.lon.min(coord.lon),
+                    },
+                    Coord {
+                        lat: bottom_right.lat.min(coord.lat),
+                        lon: bottom_right.lon.max(coord.lon),
+                    },
+                )
+            },
+        )
+    }
+
+    /// Box widened on each side by `margin_frac` of its size in radians, e.g. `0.05` adds 5%
+    /// margins
+    pub fn with_margin(top_left: Coord, bottom_right: Coord, margin_frac: f64) -> (Coord, Coord) {
+        let margin = Coord {
+            lat: (top_left.lat - bottom_right.lat) * margin_frac,
+            lon: (bottom_right.lon - top_left.lon) * margin_frac,
+        };
+        (
+            Coord {
+                lat: top_left.lat + margin.lat,
+                lon: top_left.lon - margin.lon,
+            },
+            Coord {
+                lat: bottom_right.lat - margin.lat,
+                lon: bottom_right.lon + margin.lon,
+            },
+        )
+    }
+
+    /// Box crosses the date line if `top_left.lon > bottom_right.lon`
+    pub fn in_bounding_box(&self, top_left: Coord, bottom_right: Coord) -> bool {
+        let lat_within = (bottom_right.lat..=top_left.lat).contains(&self.lat);
+        let lon_within = if top_left.lon <= bottom_right.lon {
+            (top_left.lon..=bottom_right.lon).contains(&self.lon)
+        } else {
+            self.lon >= top_left.lon || self.lon <= bottom_right.lon
+        };
+        lat_within && lon_within
+    }
+}
+
+/// Decimal degrees, e.g. `33.943°N 118.408°W`
+impl Display for Coord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (lat, lon) = self.to_degrees();
+        let lat_hemisphere = if lat < 0.0 { 'S' } else { 'N' };
+        let lon_hemisphere = if lon < 0.0 { 'W' } else { 'E' };
+        write!(
+            f,
+            "{:.3}°{lat_hemisphere} {:.3}°{lon_hemisphere}",
+            lat.abs(),
+            lon.abs()
+        )
+    }
+}
+
+/// Parses `lat,lon` in signed decimal degrees, e.g. `33.9425,-118.408`
+impl FromStr for Coord {
+    type Err = ParseCoordError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (lat, lon) = s.split_once(',').ok_or(ParseCoordError::Format)?;
+        let parse = |v: &str| v.trim().parse::<f64>().map_err(|_| ParseCoordError::Format);
+        Ok(Coord::try_from((parse(lat)?, parse(lon)?))?)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CoordOutOfRange;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ParseCoordError {
+    /// Input does not follow the expected format
+    Format,
+    /// Value is beyond the valid range
+    OutOfRange,
+}
+
+impl Display for ParseCoordError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseCoordError::Format => write!(f, "invalid coordinate format"),
+            ParseCoordError::OutOfRange => write!(f, "coordinate out of range"),
+        }
+    }
+}
+
+impl Error for ParseCoordError {}
+
+impl From<CoordOutOfRange> for ParseCoordError {
+    fn from(_: CoordOutOfRange) -> Self {
+        ParseCoordError::OutOfRange
+    }
+}
+
+/// From latitude and longitude in decimal degrees
+impl TryFrom<(f64, f64)> for Coord {
+    type Error = CoordOutOfRange;
+
+    fn try_from((lat, lon): (f64, f64)) -> Result<Self, Self::Error> {
+        let coord = Coord {
+            lat: lat * RADIANS_PER_DEGREE,
+            lon: lon * RADIANS_PER_DEGREE,
+        };
+        if coord.is_valid() {
+            Ok(coord)
+        } else {
+            Err(CoordOutOfRange)
+        }
+    }
+}
+
 impl From<(&Latitude, &Longitude)> for Coord {
     fn from((lat, lon): (&Latitude, &Longitude)) -> Self {
         Coord {
@@ -101,3 +336,259 @@ fn coord_to_radians(
         result
     }
 }
+
+fn write_dms(
+    f: &mut Formatter<'_>,
+    degrees: u8,
+    minutes: u8,
+    seconds: u8,
+    fractional_seconds: u8,
+    hemisphere: char,
+) -> std::fmt::Result {
+    write!(
+        f,
+        "{degrees}°{minutes:02}′{seconds:02}.{fractional_seconds:02}″{hemisphere}"
+    )
+}
+
+fn parse_dms(s: &str) -> Result<(u8, u8, u8, u8, char), ParseCoordError> {
+    fn parse_digits(v: &str, max_len: usize) -> Result<u8, ParseCoordError> {
+        if v.is_empty() || v.len() > max_len || !v.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseCoordError::Format);
+        }
+        v.parse().map_err(|_| ParseCoordError::OutOfRange)
+    }
+
+    fn split(s: &str, delimiter: char) -> Result<(&str, &str), ParseCoordError> {
+        s.split_once(delimiter).ok_or(ParseCoordError::Format)
+    }
+
+    let (degrees, rest) = split(s, '°')?;
+    let (minutes, rest) = split(rest, '′')?;
+    let (seconds, rest) = split(rest, '.')?;
+    let (fractional_seconds, hemisphere) = split(rest, '″')?;
+    let mut hemisphere = hemisphere.chars();
+    let (Some(hemisphere), None) = (hemisphere.next(), hemisphere.next()) else {
+        return Err(ParseCoordError::Format);
+    };
+    if fractional_seconds.len() != 2 {
+        return Err(ParseCoordError::Format);
+    }
+    let (degrees, minutes, seconds, fractional_seconds) = (
+        parse_digits(degrees, 3)?,
+        parse_digits(minutes, 2)?,
+        parse_digits(seconds, 2)?,
+        parse_digits(fractional_seconds, 2)?,
+    );
+    if minutes >= 60 || seconds >= 60 {
+        return Err(ParseCoordError::OutOfRange);
+    }
+    Ok((degrees, minutes, seconds, fractional_seconds, hemisphere))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid() {
+        assert!(Coord { lat: 0.0, lon: 0.0 }.is_valid());
+        assert!(Coord {
+            lat: -FRAC_PI_2,
+            lon: PI
+        }
+        .is_valid());
+        assert!(!Coord {
+            lat: FRAC_PI_2 + 0.1,
+            lon: 0.0
+        }
+        .is_valid());
+        assert!(!Coord {
+            lat: 0.0,
+            lon: -PI - 0.1
+        }
+        .is_valid());
+        assert!(!Coord {
+            lat: f64::NAN,
+            lon: 0.0
+        }
+        .is_valid());
+        assert!(!Coord {
+            lat: 0.0,
+            lon: f64::INFINITY
+        }
+        .is_valid());
+    }
+
+    #[test]
+    fn test_in_bounding_box() {
+        let coord = |lat: f64, lon: f64| Coord::try_from((lat, lon)).unwrap();
+        let (top_left, bottom_right) = (coord(60.0, -10.0), coord(35.0, 30.0));
+        assert!(coord(50.0, 0.0).in_bounding_box(top_left, bottom_right));
+        assert!(coord(60.0, 30.0).in_bounding_box(top_left, bottom_right));
+        assert!(!coord(61.0, 0.0).in_bounding_box(top_left, bottom_right));
+        assert!(!coord(50.0, 31.0).in_bounding_box(top_left, bottom_right));
+
+        let (top_left, bottom_right) = (coord(20.0, 170.0), coord(-20.0, -170.0));
+        assert!(coord(0.0, 175.0).in_bounding_box(top_left, bottom_right));
+        assert!(coord(0.0, -175.0).in_bounding_box(top_left, bottom_right));
+        assert!(!coord(0.0, 0.0).in_bounding_box(top_left, bottom_right));
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let coord = |lat: f64, lon: f64| Coord::try_from((lat, lon)).unwrap();
+        let coords = [
+            coord(47.45, -122.31),
+            coord(33.94, -118.41),
+            coord(39.86, -104.67),
+        ];
+        assert_eq!(
+            Coord::bounding_box(coords),
+            Some((coord(47.45, -122.31), coord(33.94, -104.67)))
+        );
+        assert_eq!(
+            Coord::bounding_box([coords[1]]),
+            Some((coords[1], coords[1]))
+        );
+        assert_eq!(Coord::bounding_box([]), None);
+    }
+
+    #[test]
+    fn test_with_margin() {
+        let (top_left, bottom_right) = (
+            Coord {
+                lat: 0.2,
+                lon: -0.4,
+            },
+            Coord {
+                lat: -0.2,
+                lon: 0.6,
+            },
+        );
+        let (wide_top_left, wide_bottom_right) = Coord::with_margin(top_left, bottom_right, 0.5);
+        assert!((wide_top_left.lat - 0.4).abs() < 1e-12);
+        assert!((wide_top_left.lon + 0.9).abs() < 1e-12);
+        assert!((wide_bottom_right.lat + 0.4).abs() < 1e-12);
+        assert!((wide_bottom_right.lon - 1.1).abs() < 1e-12);
+
+        let aspect = |top_left: Coord, bottom_right: Coord| {
+            (bottom_right.lon - top_left.lon) / (top_left.lat - bottom_right.lat)
+        };
+        assert!(
+            (aspect(wide_top_left, wide_bottom_right) - aspect(top_left, bottom_right)).abs()
+                < 1e-12
+        );
+        assert_eq!(
+            Coord::with_margin(top_left, bottom_right, 0.0),
+            (top_left, bottom_right)
+        );
+    }
+
+    #[test]
+    fn test_to_degrees() {
+        let (lat, lon) = Coord {
+            lat: -FRAC_PI_2,
+            lon: PI,
+        }
+        .to_degrees();
+        assert_eq!((lat, lon), (-90.0, 180.0));
+        let (lat, lon) = Coord::try_from((33.9425, -118.408)).unwrap().to_degrees();
+        assert!((lat - 33.9425).abs() < 1e-12);
+        assert!((lon + 118.408).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_try_from_degrees() {
+        assert_eq!(
+            Coord::try_from((-90.0, 180.0)),
+            Ok(Coord {
+                lat: -FRAC_PI_2,
+                lon: PI
+            })
+        );
+        assert_eq!(Coord::try_from((90.5, 0.0)), Err(CoordOutOfRange));
+        assert_eq!(Coord::try_from((0.0, f64::NAN)), Err(CoordOutOfRange));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid coordinate")]
+    fn test_assert_valid() {
+        Coord {
+            lat: f64::NAN,
+            lon: 0.0,
+        }
+        .assert_valid();
+    }
+
+    #[test]
+    fn test_display_from_str_dms() {
+        let lat = Latitude {
+            hemisphere: LatitudeHemisphere::North,
+            degrees: 33,
+            minutes: 56,
+            seconds: 32,
+            fractional_seconds: 99,
+        };
+        let lon = Longitude {
+            hemisphere: LongitudeHemisphere::West,
+            degrees: 118,
+            minutes: 24,
+            seconds: 28,
+            fractional_seconds: 98,
+        };
+        assert_eq!(lat.to_string(), "33°56′32.99″N");
+        assert_eq!(lon.to_string(), "118°24′28.98″W");
+        assert_eq!(lat.to_string().parse(), Ok(lat));
+        assert_eq!(lon.to_string().parse(), Ok(lon));
+
+        let lat = Latitude {
+            hemisphere: LatitudeHemisphere::South,
+            degrees: 0,
+            minutes: 5,
+            seconds: 0,
+            fractional_seconds: 7,
+        };
+        assert_eq!(lat.to_string(), "0°05′00.07″S");
+        assert_eq!(lat.to_string().parse(), Ok(lat));
+
+        assert_eq!(
+            "33°56′32.99″E".parse::<Latitude>(),
+            Err(ParseCoordError::Format)
+        );
+        assert_eq!(
+            "33°56′32.9″N".parse::<Latitude>(),
+            Err(ParseCoordError::Format)
+        );
+        assert_eq!(
+            "33°56′32.99″".parse::<Latitude>(),
+            Err(ParseCoordError::Format)
+        );
+        assert_eq!(
+            "91°00′00.00″N".parse::<Latitude>(),
+            Err(ParseCoordError::OutOfRange)
+        );
+        assert_eq!(
+            "33°60′00.00″N".parse::<Latitude>(),
+            Err(ParseCoordError::OutOfRange)
+        );
+        assert_eq!(
+            "180°00′00.01″W".parse::<Longitude>(),
+            Err(ParseCoordError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_display_from_str_coord() {
+        let coord: Coord = "33.9425, -118.408".parse().unwrap();
+        assert_eq!(coord, Coord::try_from((33.9425, -118.408)).unwrap());
+        assert_eq!(coord.to_string(), "33.943°N 118.408°W");
+        assert_eq!(
+            Coord::try_from((-33.9461, 151.1772)).unwrap().to_string(),
+            "33.946°S 151.177°E"
+        );
+        assert_eq!("33.9425".parse::<Coord>(), Err(ParseCoordError::Format));
+        assert_eq!("north,0".parse::<Coord>(), Err(ParseCoordError::Format));
+        assert_eq!("91,0".parse::<Coord>(), Err(ParseCoordError::OutOfRange));
+    }
+}
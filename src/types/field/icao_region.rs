@@ -0,0 +1,83 @@
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IcaoRegion {
+    WesternSouthPacific,
+    NorthAtlantic,
+    Canada,
+    WestAfricaMaghreb,
+    Europe,
+    CentralSouthernAfrica,
+    WesternAfrica,
+    EasternAfrica,
+    NorthAmerica,
+    SouthernEurope,
+    CentralAmerica,
+    SouthPacific,
+    MiddleEast,
+    NorthPacific,
+    WesternPacific,
+    SouthAmerica,
+    Caribbean,
+    FormerSoviet,
+    SouthAsia,
+    MaritimeSoutheastAsia,
+    SouthwestPacific,
+    EastAsia,
+}
+
+impl IcaoRegion {
+    pub fn from_prefix(c: char) -> Option<IcaoRegion> {
+        Some(match c.to_ascii_uppercase() {
+            'A' => IcaoRegion::WesternSouthPacific,
+            'B' => IcaoRegion::NorthAtlantic,
+            'C' => IcaoRegion::Canada,
+            'D' => IcaoRegion::WestAfricaMaghreb,
+            'E' => IcaoRegion::Europe,
+            'F' => IcaoRegion::CentralSouthernAfrica,
+            'G' => IcaoRegion::WesternAfrica,
+            'H' => IcaoRegion::EasternAfrica,
+            'K' => IcaoRegion::NorthAmerica,
+            'L' => IcaoRegion::SouthernEurope,
+            'M' => IcaoRegion::CentralAmerica,
+            'N' => IcaoRegion::SouthPacific,
+            'O' => IcaoRegion::MiddleEast,
+            'P' => IcaoRegion::NorthPacific,
+            'R' => IcaoRegion::WesternPacific,
+            'S' => IcaoRegion::SouthAmerica,
+            'T' => IcaoRegion::Caribbean,
+            'U' => IcaoRegion::FormerSoviet,
+            'V' => IcaoRegion::SouthAsia,
+            'W' => IcaoRegion::MaritimeSoutheastAsia,
+            'Y' => IcaoRegion::SouthwestPacific,
+            'Z' => IcaoRegion::EastAsia,
+            _ => None?,
+        })
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            IcaoRegion::WesternSouthPacific => "Western South Pacific",
+            IcaoRegion::NorthAtlantic => "Greenland, Iceland and Kosovo",
+            IcaoRegion::Canada => "Canada",
+            IcaoRegion::WestAfricaMaghreb => "Eastern West Africa and Maghreb",
+            IcaoRegion::Europe => "Northern Europe",
+            IcaoRegion::CentralSouthernAfrica => "Central and Southern Africa, Indian Ocean",
+            IcaoRegion::WesternAfrica => "Western West Africa and Maghreb",
+            IcaoRegion::EasternAfrica => "East Africa and Northeast Africa",
+            IcaoRegion::NorthAmerica => "Contiguous United States",
+            IcaoRegion::SouthernEurope => "Southern Europe, Israel and Turkey",
+            IcaoRegion::CentralAmerica => "Central America, Mexico and northern Caribbean",
+            IcaoRegion::SouthPacific => "South Pacific and New Zealand",
+            IcaoRegion::MiddleEast => "Middle East, Afghanistan and Pakistan",
+            IcaoRegion::NorthPacific => "North Pacific, Alaska and Hawaii",
+            IcaoRegion::WesternPacific => "Western Pacific, Japan, Korea, Philippines and Taiwan",
+            IcaoRegion::SouthAmerica => "South America",
+            IcaoRegion::Caribbean => "Caribbean",
+            IcaoRegion::FormerSoviet => "Russia and post-Soviet states",
+            IcaoRegion::SouthAsia => "South Asia and mainland Southeast Asia",
+            IcaoRegion::MaritimeSoutheastAsia => "Maritime Southeast Asia",
+            IcaoRegion::SouthwestPacific => "Australia",
+            IcaoRegion::EastAsia => "China, Mongolia and North Korea",
+        }
+    }
+}
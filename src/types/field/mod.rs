@@ -1,27 +1,87 @@
 use rust_decimal::Decimal;
+use std::fmt::{Display, Formatter};
 
 pub mod coord;
+pub mod icao_region;
 pub mod section_code;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CycleDate {
     pub year: u8,
     pub cycle: u8,
 }
 
+/// AIRAC cycle, e.g. `AIRAC 1906`
+impl Display for CycleDate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AIRAC {:02}{:02}", self.year, self.cycle)
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MagneticTrueIndicator {
     Magnetic,
     True,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TimeZone {
     pub hour: i8,
     pub minute: u8,
 }
 
+impl TimeZone {
+    /// Offset from UTC in seconds, `minute` has the sign of `hour`
+    pub fn offset_seconds(&self) -> i32 {
+        let magnitude = self.hour.unsigned_abs() as i32 * 3600 + self.minute as i32 * 60;
+        if self.hour < 0 {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+
+    /// Inverse of [`TimeZone::offset_seconds`]. Returns `None` for offsets not in whole minutes,
+    /// beyond ±12:59 or between -1:00 and 0:00 exclusive, which have no ARINC 424 code.
+    pub fn from_offset_seconds(offset: i32) -> Option<Self> {
+        if offset % 60 != 0 || offset.abs() >= 13 * 3600 || (-3599..0).contains(&offset) {
+            return None;
+        }
+        let minutes = offset.abs() / 60;
+        let hour = (minutes / 60) as i8;
+        Some(TimeZone {
+            hour: if offset < 0 { -hour } else { hour },
+            minute: (minutes % 60) as u8,
+        })
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<TimeZone> for chrono::FixedOffset {
+    fn from(value: TimeZone) -> Self {
+        chrono::FixedOffset::east_opt(value.offset_seconds())
+            .unwrap_or_else(|| unreachable!("Time zone {value:?} is within a day"))
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TimeZoneOutOfRange;
+
+/// See [`TimeZone::from_offset_seconds`]
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::FixedOffset> for TimeZone {
+    type Error = TimeZoneOutOfRange;
+
+    fn try_from(value: chrono::FixedOffset) -> Result<Self, Self::Error> {
+        Self::from_offset_seconds(value.local_minus_utc()).ok_or(TimeZoneOutOfRange)
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PublicMilitaryIndicator {
     Civil,
     Military,
@@ -29,6 +89,7 @@ pub enum PublicMilitaryIndicator {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MagneticVariation {
     East(Decimal),
     West(Decimal),
@@ -36,6 +97,7 @@ pub enum MagneticVariation {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RunwaySurfaceCode {
     HardSurface,
     SoftSurface,
@@ -44,13 +106,27 @@ pub enum RunwaySurfaceCode {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Altitude {
     Fl(u16),
     Msl(u32),
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RecordType {
     Standard,
     Tailored,
 }
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VhfNavaidType {
+    Vor,
+    VorDme,
+    Vortac,
+    Dme,
+    Tacan,
+    IlsDme,
+    MlsDme,
+}
@@ -1,4 +1,5 @@
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SectionCode {
     Mora,
     Navaid,
@@ -11,6 +12,7 @@ pub enum SectionCode {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EnrichedSectionCode {
     Mora(MoraSubsectionCode),
     Navaid(NavaidSubsectionCode),
@@ -23,17 +25,20 @@ pub enum EnrichedSectionCode {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MoraSubsectionCode {
     GridMora,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NavaidSubsectionCode {
     VhfNavaid,
     NdbNavaid,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EnrouteSubsectionCode {
     Waypoints,
     AirwayMarkers,
@@ -45,6 +50,7 @@ pub enum EnrouteSubsectionCode {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HeliportSubsectionCode {
     Pads,
     TerminalWaypoints,
@@ -57,6 +63,7 @@ pub enum HeliportSubsectionCode {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AirportSubsectionCode {
     ReferencePoints,
     Gates,
@@ -78,18 +85,21 @@ pub enum AirportSubsectionCode {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CompanyRoutesSubsectionCode {
     CompanyRoutes,
     AlternateRecords,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TablesSubsectionCode {
     CruisingTables,
     GeographicalReference,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AirspaceSubsectionCode {
     ControlledAirspace,
     FirUir,
@@ -1,11 +1,18 @@
+pub mod navaid;
+pub mod runway;
+pub mod waypoint;
+
 use crate::types::field::coord::{Latitude, Longitude};
+use crate::types::field::icao_region::IcaoRegion;
 use crate::types::field::section_code::EnrichedSectionCode;
 use crate::types::field::{
     Altitude, CycleDate, MagneticTrueIndicator, MagneticVariation, PublicMilitaryIndicator,
     RecordType, RunwaySurfaceCode, TimeZone,
 };
+use std::fmt::{Display, Formatter};
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AirportPrimaryRecord<'a> {
     pub record_type: RecordType,
     pub customer_area_code: &'a str,
@@ -23,6 +30,7 @@ pub struct AirportPrimaryRecord<'a> {
     pub magnetic_variation: MagneticVariation,
     pub airport_elevation: i32,
     pub speed_limit: Option<u16>,
+    #[cfg_attr(feature = "serde", serde(borrow))]
     pub recommended_navaid: Option<&'a str>,
     pub transition_altitude: Option<u32>,
     pub transition_level: Option<u32>,
@@ -35,3 +43,146 @@ pub struct AirportPrimaryRecord<'a> {
     pub file_record_number: u32,
     pub cycle_date: CycleDate,
 }
+
+impl<'a> AirportPrimaryRecord<'a> {
+    pub fn country_code(&self) -> Option<IcaoRegion> {
+        self.icao_identifier
+            .chars()
+            .next()
+            .and_then(IcaoRegion::from_prefix)
+    }
+
+    /// `utc` in the time zone of the airport, `None` if it is unknown
+    #[cfg(feature = "chrono")]
+    pub fn local_time(
+        &self,
+        utc: chrono::DateTime<chrono::Utc>,
+    ) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+        self.time_zone
+            .map(|time_zone| utc.with_timezone(&chrono::FixedOffset::from(time_zone)))
+    }
+
+    pub fn full_icao_code(&self) -> String {
+        if self.icao_code.is_empty() {
+            self.icao_identifier.to_string()
+        } else {
+            format!("{}/{}", self.icao_identifier, self.icao_code)
+        }
+    }
+}
+
+/// One-line summary, e.g. `KLAX (LOS ANGELES INTL) @ 33°56′32.99″N 118°24′28.98″W elev=128ft`
+impl Display for AirportPrimaryRecord<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({}) @ {} {} elev={}ft",
+            self.icao_identifier,
+            self.airport_name,
+            self.airport_reference_point_latitude,
+            self.airport_reference_point_longitude,
+            self.airport_elevation
+        )
+    }
+}
+
+/// [`AirportPrimaryRecord`] which does not borrow the input
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedAirportPrimaryRecord {
+    pub record_type: RecordType,
+    pub customer_area_code: String,
+    pub icao_identifier: String,
+    pub icao_code: String,
+    pub enriched_section_code: EnrichedSectionCode,
+    pub ata_designator: String,
+    pub continuation_record_number: u8,
+    pub speed_limit_altitude: Option<Altitude>,
+    pub longest_runway: u16,
+    pub ifr_capability: bool,
+    pub longest_runway_surface_code: RunwaySurfaceCode,
+    pub airport_reference_point_latitude: Latitude,
+    pub airport_reference_point_longitude: Longitude,
+    pub magnetic_variation: MagneticVariation,
+    pub airport_elevation: i32,
+    pub speed_limit: Option<u16>,
+    pub recommended_navaid: Option<String>,
+    pub transition_altitude: Option<u32>,
+    pub transition_level: Option<u32>,
+    pub public_military_indicator: PublicMilitaryIndicator,
+    pub time_zone: Option<TimeZone>,
+    pub daylight_indicator: Option<bool>,
+    pub magnetic_true_indicator: Option<MagneticTrueIndicator>,
+    pub datum_code: String,
+    pub airport_name: String,
+    pub file_record_number: u32,
+    pub cycle_date: CycleDate,
+}
+
+impl OwnedAirportPrimaryRecord {
+    pub fn as_record(&self) -> AirportPrimaryRecord<'_> {
+        AirportPrimaryRecord {
+            record_type: self.record_type,
+            customer_area_code: &self.customer_area_code,
+            icao_identifier: &self.icao_identifier,
+            icao_code: &self.icao_code,
+            enriched_section_code: self.enriched_section_code,
+            ata_designator: &self.ata_designator,
+            continuation_record_number: self.continuation_record_number,
+            speed_limit_altitude: self.speed_limit_altitude,
+            longest_runway: self.longest_runway,
+            ifr_capability: self.ifr_capability,
+            longest_runway_surface_code: self.longest_runway_surface_code,
+            airport_reference_point_latitude: self.airport_reference_point_latitude,
+            airport_reference_point_longitude: self.airport_reference_point_longitude,
+            magnetic_variation: self.magnetic_variation,
+            airport_elevation: self.airport_elevation,
+            speed_limit: self.speed_limit,
+            recommended_navaid: self.recommended_navaid.as_deref(),
+            transition_altitude: self.transition_altitude,
+            transition_level: self.transition_level,
+            public_military_indicator: self.public_military_indicator,
+            time_zone: self.time_zone,
+            daylight_indicator: self.daylight_indicator,
+            magnetic_true_indicator: self.magnetic_true_indicator,
+            datum_code: &self.datum_code,
+            airport_name: &self.airport_name,
+            file_record_number: self.file_record_number,
+            cycle_date: self.cycle_date,
+        }
+    }
+}
+
+impl From<&AirportPrimaryRecord<'_>> for OwnedAirportPrimaryRecord {
+    fn from(value: &AirportPrimaryRecord<'_>) -> Self {
+        Self {
+            record_type: value.record_type,
+            customer_area_code: value.customer_area_code.to_string(),
+            icao_identifier: value.icao_identifier.to_string(),
+            icao_code: value.icao_code.to_string(),
+            enriched_section_code: value.enriched_section_code,
+            ata_designator: value.ata_designator.to_string(),
+            continuation_record_number: value.continuation_record_number,
+            speed_limit_altitude: value.speed_limit_altitude,
+            longest_runway: value.longest_runway,
+            ifr_capability: value.ifr_capability,
+            longest_runway_surface_code: value.longest_runway_surface_code,
+            airport_reference_point_latitude: value.airport_reference_point_latitude,
+            airport_reference_point_longitude: value.airport_reference_point_longitude,
+            magnetic_variation: value.magnetic_variation,
+            airport_elevation: value.airport_elevation,
+            speed_limit: value.speed_limit,
+            recommended_navaid: value.recommended_navaid.map(str::to_string),
+            transition_altitude: value.transition_altitude,
+            transition_level: value.transition_level,
+            public_military_indicator: value.public_military_indicator,
+            time_zone: value.time_zone,
+            daylight_indicator: value.daylight_indicator,
+            magnetic_true_indicator: value.magnetic_true_indicator,
+            datum_code: value.datum_code.to_string(),
+            airport_name: value.airport_name.to_string(),
+            file_record_number: value.file_record_number,
+            cycle_date: value.cycle_date,
+        }
+    }
+}
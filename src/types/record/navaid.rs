@@ -0,0 +1,57 @@
+use crate::types::field::coord::{Latitude, Longitude};
+use crate::types::field::section_code::EnrichedSectionCode;
+use crate::types::field::{CycleDate, MagneticVariation, RecordType, VhfNavaidType};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VhfNavaidRecord<'a> {
+    pub record_type: RecordType,
+    pub customer_area_code: &'a str,
+    pub enriched_section_code: EnrichedSectionCode,
+    /// Airport of the terminal navaid, empty for enroute ones
+    pub icao_identifier: &'a str,
+    pub navaid_identifier: &'a str,
+    pub icao_code: &'a str,
+    pub continuation_record_number: u8,
+    /// In tenths of kHz
+    pub frequency: u32,
+    pub navaid_type: VhfNavaidType,
+    pub class: &'a str,
+    pub vor_latitude: Option<Latitude>,
+    pub vor_longitude: Option<Longitude>,
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub dme_identifier: Option<&'a str>,
+    pub dme_latitude: Option<Latitude>,
+    pub dme_longitude: Option<Longitude>,
+    /// Station declination
+    pub magnetic_variation: Option<MagneticVariation>,
+    /// DME elevation
+    pub elevation: Option<i32>,
+    pub datum_code: &'a str,
+    pub navaid_name: &'a str,
+    pub file_record_number: u32,
+    pub cycle_date: CycleDate,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NdbNavaidRecord<'a> {
+    pub record_type: RecordType,
+    pub customer_area_code: &'a str,
+    pub enriched_section_code: EnrichedSectionCode,
+    /// Airport of the terminal NDB, empty for enroute ones
+    pub icao_identifier: &'a str,
+    pub ndb_identifier: &'a str,
+    pub icao_code: &'a str,
+    pub continuation_record_number: u8,
+    /// In kHz, tenths are dropped
+    pub ndb_frequency: u16,
+    pub ndb_class: &'a str,
+    pub latitude: Latitude,
+    pub longitude: Longitude,
+    pub magnetic_variation: MagneticVariation,
+    pub datum_code: &'a str,
+    pub navaid_name: &'a str,
+    pub file_record_number: u32,
+    pub cycle_date: CycleDate,
+}
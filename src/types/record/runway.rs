@@ -0,0 +1,32 @@
+use crate::types::field::coord::{Latitude, Longitude};
+use crate::types::field::section_code::EnrichedSectionCode;
+use crate::types::field::{CycleDate, RecordType};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RunwayRecord<'a> {
+    pub record_type: RecordType,
+    pub customer_area_code: &'a str,
+    pub icao_identifier: &'a str,
+    pub icao_code: &'a str,
+    pub enriched_section_code: EnrichedSectionCode,
+    pub runway_identifier: &'a str,
+    pub continuation_record_number: u8,
+    /// In feet
+    pub runway_length: u16,
+    /// In thousandths of percent, positive uphill from the threshold
+    pub runway_gradient: Option<i32>,
+    /// In feet
+    pub runway_width: u16,
+    pub threshold_latitude: Latitude,
+    pub threshold_longitude: Longitude,
+    /// Ellipsoidal height of the landing threshold point, in tenths of meters
+    pub threshold_elevation: Option<i32>,
+    /// In feet
+    pub landing_threshold_elevation: i32,
+    /// In feet
+    pub displaced_threshold_distance: u16,
+    pub runway_description: &'a str,
+    pub file_record_number: u32,
+    pub cycle_date: CycleDate,
+}
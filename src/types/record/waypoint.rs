@@ -0,0 +1,25 @@
+use crate::types::field::coord::{Latitude, Longitude};
+use crate::types::field::section_code::EnrichedSectionCode;
+use crate::types::field::{CycleDate, MagneticVariation, RecordType};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TerminalWaypointRecord<'a> {
+    pub record_type: RecordType,
+    pub customer_area_code: &'a str,
+    /// Airport the waypoint belongs to
+    pub icao_identifier: &'a str,
+    pub enriched_section_code: EnrichedSectionCode,
+    pub waypoint_identifier: &'a str,
+    pub icao_code: &'a str,
+    pub continuation_record_number: u8,
+    pub waypoint_type: &'a str,
+    pub waypoint_usage: &'a str,
+    pub latitude: Latitude,
+    pub longitude: Longitude,
+    pub magnetic_variation: MagneticVariation,
+    pub datum_code: &'a str,
+    pub waypoint_name: &'a str,
+    pub file_record_number: u32,
+    pub cycle_date: CycleDate,
+}
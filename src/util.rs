@@ -119,9 +119,183 @@ pub fn parse_blank_arr(blank: &[u8], allowed_len: impl RangeBounds<usize>) -> Op
     }
 }
 
+// 5.1 All alpha and alpha/numeric fields will be left justified
+pub fn write_alpha(s: &str, buf: &mut [u8]) {
+    let bytes = s.as_bytes();
+    let len = bytes.len().min(buf.len());
+    buf[..len].copy_from_slice(&bytes[..len]);
+    write_blank(&mut buf[len..]);
+}
+
+pub fn write_num(mut num: u32, buf: &mut [u8]) {
+    for c in buf.iter_mut().rev() {
+        *c = b'0' + (num % 10) as u8;
+        num /= 10;
+    }
+}
+
+pub fn write_blank(buf: &mut [u8]) {
+    buf.fill(b' ');
+}
+
 pub fn cycling<T>(it: &[T]) -> impl Iterator<Item = (&T, &T)> {
     it.iter().zip(it.iter().skip(1)).chain(
         it.first()
             .and_then(|first| it.last().map(|last| (last, first))),
     )
 }
+
+/// Same as [`cycling`] with positions of the pair in `it`, the last one is `(len - 1, 0)`
+pub fn cycling_with_index<T>(it: &[T]) -> impl Iterator<Item = (usize, usize, &T, &T)> {
+    (0..it.len()).map(|i| {
+        let j = (i + 1) % it.len();
+        (i, j, &it[i], &it[j])
+    })
+}
+
+pub fn cycling_rev<T>(it: &[T]) -> impl Iterator<Item = (&T, &T)> {
+    it.iter().rev().zip(it.iter().rev().skip(1)).chain(
+        it.first()
+            .and_then(|first| it.last().map(|last| (first, last))),
+    )
+}
+
+/// Escapes text for XML content and attribute values
+pub fn xml_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Quotes CSV field doubling quotes inside it, only if it has a separator, a quote or a line
+/// break unless quoting is `always`
+pub fn csv_quote(field: &str, always: bool) -> String {
+    if always || field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Escapes text for JSON string literals
+pub fn json_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Exponential moving average with smoothing factor `2 / (window + 1)`
+pub fn smooth_history(history: &[f64], window: usize) -> Vec<f64> {
+    let alpha = 2.0 / (window as f64 + 1.0);
+    history
+        .iter()
+        .scan(None, |ema: &mut Option<f64>, &v| {
+            let next = ema.map_or(v, |ema| alpha * v + (1.0 - alpha) * ema);
+            *ema = Some(next);
+            Some(next)
+        })
+        .collect()
+}
+
+/// Average change of the value per iteration, negative when improving
+pub fn convergence_rate(history: &[f64]) -> f64 {
+    match history {
+        [first, .., last] => (last - first) / (history.len() - 1) as f64,
+        _ => 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cycling_with_index() {
+        let items = [1, 2, 3];
+        assert_eq!(
+            cycling_with_index(&items).collect::<Vec<_>>(),
+            vec![(0, 1, &1, &2), (1, 2, &2, &3), (2, 0, &3, &1)]
+        );
+        assert!(cycling_with_index(&items)
+            .map(|(_, _, a, b)| (a, b))
+            .eq(cycling(&items)));
+        assert_eq!(cycling_with_index::<u32>(&[]).count(), 0);
+        assert_eq!(
+            cycling_with_index(&[1]).collect::<Vec<_>>(),
+            vec![(0, 0, &1, &1)]
+        );
+    }
+
+    #[test]
+    fn test_cycling_rev() {
+        let items = [1, 2, 3];
+        assert_eq!(
+            cycling_rev(&items).collect::<Vec<_>>(),
+            vec![(&3, &2), (&2, &1), (&1, &3)]
+        );
+        let mut forward: Vec<_> = cycling(&items).map(|(a, b)| (b, a)).collect();
+        let mut backward: Vec<_> = cycling_rev(&items).collect();
+        forward.sort_unstable();
+        backward.sort_unstable();
+        assert_eq!(backward, forward);
+        assert_eq!(cycling_rev::<u32>(&[]).count(), 0);
+        assert_eq!(cycling_rev(&[1]).collect::<Vec<_>>(), vec![(&1, &1)]);
+    }
+
+    #[test]
+    fn test_xml_escape() {
+        assert_eq!(xml_escape("LOS ANGELES INTL"), "LOS ANGELES INTL");
+        assert_eq!(
+            xml_escape(r#"<A & 'B' "C">"#),
+            "&lt;A &amp; &apos;B&apos; &quot;C&quot;&gt;"
+        );
+    }
+
+    #[test]
+    fn test_csv_quote() {
+        assert_eq!(csv_quote("KLAX", false), "KLAX");
+        assert_eq!(csv_quote("KLAX", true), r#""KLAX""#);
+        assert_eq!(csv_quote("LOS ANGELES, CA", false), r#""LOS ANGELES, CA""#);
+        assert_eq!(csv_quote(r#"THE "INTL""#, false), r#""THE ""INTL""""#);
+        assert_eq!(csv_quote("", true), r#""""#);
+    }
+
+    #[test]
+    fn test_json_escape() {
+        assert_eq!(json_escape("LOS ANGELES INTL"), "LOS ANGELES INTL");
+        assert_eq!(json_escape("\"A\"\\B\n\u{1}"), r#"\"A\"\\B\n\u0001"#);
+    }
+
+    #[test]
+    fn test_smooth_history() {
+        assert_eq!(smooth_history(&[], 3), Vec::<f64>::new());
+        assert_eq!(smooth_history(&[4.0, 2.0, 2.0], 1), vec![4.0, 2.0, 2.0]);
+        assert_eq!(smooth_history(&[4.0, 2.0, 2.0], 3), vec![4.0, 3.0, 2.5]);
+    }
+
+    #[test]
+    fn test_convergence_rate() {
+        assert_eq!(convergence_rate(&[]), 0.0);
+        assert_eq!(convergence_rate(&[1.0]), 0.0);
+        assert_eq!(convergence_rate(&[10.0, 8.0, 7.0, 4.0]), -2.0);
+    }
+}
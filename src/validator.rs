@@ -0,0 +1,318 @@
+use crate::types::record::navaid::VhfNavaidRecord;
+use crate::types::record::AirportPrimaryRecord;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+
+const AIRPORT_ELEVATION_RANGE: RangeInclusive<i32> = -1500..=15000;
+const CYCLE_RANGE: RangeInclusive<u8> = 1..=13;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ValidationWarning {
+    SpeedLimitWithoutAltitude,
+    TransitionLevelBelowAltitude {
+        transition_altitude: u32,
+        transition_level: u32,
+    },
+    IcaoCodeMismatch,
+    ContinuationRecordNumber(u8),
+    IcaoIdentifierLength(usize),
+    IfrWithoutRunway,
+    ElevationOutOfRange(i32),
+    CycleOutOfRange(u8),
+    /// Recommended navaid of the airport at `airport` index is not in the navaid database
+    UnknownRecommendedNavaid {
+        airport: usize,
+    },
+}
+
+impl Display for ValidationWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationWarning::SpeedLimitWithoutAltitude => {
+                write!(f, "speed limit is set without speed limit altitude")
+            }
+            ValidationWarning::TransitionLevelBelowAltitude {
+                transition_altitude,
+                transition_level,
+            } => write!(
+                f,
+                "transition level {transition_level} is below transition altitude {transition_altitude}"
+            ),
+            ValidationWarning::IcaoCodeMismatch => {
+                write!(f, "ICAO code does not match ICAO identifier prefix")
+            }
+            ValidationWarning::ContinuationRecordNumber(n) => {
+                write!(f, "continuation record number {n} of primary record is not 0")
+            }
+            ValidationWarning::IcaoIdentifierLength(len) => {
+                write!(f, "ICAO identifier has {len} characters instead of 4")
+            }
+            ValidationWarning::IfrWithoutRunway => {
+                write!(f, "IFR capable airport has no runway length")
+            }
+            ValidationWarning::ElevationOutOfRange(elevation) => write!(
+                f,
+                "airport elevation {elevation} is out of range {AIRPORT_ELEVATION_RANGE:?}"
+            ),
+            ValidationWarning::CycleOutOfRange(cycle) => {
+                write!(f, "cycle {cycle} is out of range {CYCLE_RANGE:?}")
+            }
+            ValidationWarning::UnknownRecommendedNavaid { airport } => {
+                write!(f, "recommended navaid of airport #{airport} is unknown")
+            }
+        }
+    }
+}
+
+pub fn validate_airport_primary_record(rec: &AirportPrimaryRecord) -> Vec<ValidationWarning> {
+    let mut warnings = vec![];
+    if rec.continuation_record_number != 0 {
+        warnings.push(ValidationWarning::ContinuationRecordNumber(
+            rec.continuation_record_number,
+        ));
+    }
+    if rec.ifr_capability && rec.longest_runway == 0 {
+        warnings.push(ValidationWarning::IfrWithoutRunway);
+    }
+    if !AIRPORT_ELEVATION_RANGE.contains(&rec.airport_elevation) {
+        warnings.push(ValidationWarning::ElevationOutOfRange(
+            rec.airport_elevation,
+        ));
+    }
+    if !CYCLE_RANGE.contains(&rec.cycle_date.cycle) {
+        warnings.push(ValidationWarning::CycleOutOfRange(rec.cycle_date.cycle));
+    }
+    if rec.speed_limit.is_some() && rec.speed_limit_altitude.is_none() {
+        warnings.push(ValidationWarning::SpeedLimitWithoutAltitude);
+    }
+    if let Some((transition_altitude, transition_level)) =
+        rec.transition_altitude.zip(rec.transition_level)
+    {
+        if transition_level < transition_altitude {
+            warnings.push(ValidationWarning::TransitionLevelBelowAltitude {
+                transition_altitude,
+                transition_level,
+            });
+        }
+    }
+    // identifiers starting with a digit are local ones, not bound to ICAO region
+    if let (Some(icao_identifier), Some(icao_code)) = (
+        rec.icao_identifier.bytes().next(),
+        rec.icao_code.bytes().next(),
+    ) {
+        if icao_identifier.is_ascii_alphabetic() && icao_identifier != icao_code {
+            warnings.push(ValidationWarning::IcaoCodeMismatch);
+        }
+    }
+    if rec
+        .icao_identifier
+        .bytes()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic())
+        && rec.icao_identifier.len() != 4
+    {
+        warnings.push(ValidationWarning::IcaoIdentifierLength(
+            rec.icao_identifier.len(),
+        ));
+    }
+    warnings
+}
+
+pub fn validate_recommended_navaids(
+    airports: &[AirportPrimaryRecord],
+    navaids: &[VhfNavaidRecord],
+) -> Vec<ValidationWarning> {
+    let navaid_identifiers: HashSet<_> = navaids.iter().map(|rec| rec.navaid_identifier).collect();
+    airports
+        .iter()
+        .enumerate()
+        .filter(|(_, rec)| {
+            rec.recommended_navaid
+                .is_some_and(|navaid| !navaid_identifiers.contains(navaid))
+        })
+        .map(|(airport, _)| ValidationWarning::UnknownRecommendedNavaid { airport })
+        .collect()
+}
+
+impl<'a> AirportPrimaryRecord<'a> {
+    pub fn validate(&self) -> Vec<ValidationWarning> {
+        validate_airport_primary_record(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::record::navaid::parse_vhf_navaid_record;
+    use crate::parser::record::parse_airport_primary_record;
+    use crate::test_util::KLAX;
+    use crate::types::field::{Altitude, CycleDate};
+
+    fn klax() -> AirportPrimaryRecord<'static> {
+        let record = KLAX.as_bytes();
+        parse_airport_primary_record(record).unwrap()
+    }
+
+    #[test]
+    fn test_valid() {
+        assert_eq!(klax().validate(), vec![]);
+    }
+
+    #[test]
+    fn test_speed_limit_without_altitude() {
+        let rec = AirportPrimaryRecord {
+            speed_limit: Some(250),
+            ..klax()
+        };
+        assert_eq!(
+            rec.validate(),
+            vec![ValidationWarning::SpeedLimitWithoutAltitude]
+        );
+        let rec = AirportPrimaryRecord {
+            speed_limit_altitude: Some(Altitude::Msl(10000)),
+            ..rec
+        };
+        assert_eq!(rec.validate(), vec![]);
+    }
+
+    #[test]
+    fn test_transition_level_below_altitude() {
+        let rec = AirportPrimaryRecord {
+            transition_level: Some(5000),
+            ..klax()
+        };
+        assert_eq!(
+            rec.validate(),
+            vec![ValidationWarning::TransitionLevelBelowAltitude {
+                transition_altitude: 18000,
+                transition_level: 5000
+            }]
+        );
+    }
+
+    #[test]
+    fn test_icao_code_mismatch() {
+        let rec = AirportPrimaryRecord {
+            icao_code: "CY",
+            ..klax()
+        };
+        assert_eq!(rec.validate(), vec![ValidationWarning::IcaoCodeMismatch]);
+        let rec = AirportPrimaryRecord {
+            icao_identifier: "00AK",
+            icao_code: "PA",
+            ..klax()
+        };
+        assert_eq!(rec.validate(), vec![]);
+    }
+
+    #[test]
+    fn test_continuation_record_number() {
+        let rec = AirportPrimaryRecord {
+            continuation_record_number: 1,
+            ..klax()
+        };
+        assert_eq!(
+            rec.validate(),
+            vec![ValidationWarning::ContinuationRecordNumber(1)]
+        );
+    }
+
+    #[test]
+    fn test_icao_identifier_length() {
+        let rec = AirportPrimaryRecord {
+            icao_identifier: "KLA",
+            ..klax()
+        };
+        assert_eq!(
+            rec.validate(),
+            vec![ValidationWarning::IcaoIdentifierLength(3)]
+        );
+        let rec = AirportPrimaryRecord {
+            icao_identifier: "1L2",
+            ..klax()
+        };
+        assert_eq!(rec.validate(), vec![]);
+    }
+
+    #[test]
+    fn test_ifr_without_runway() {
+        let rec = AirportPrimaryRecord {
+            longest_runway: 0,
+            ..klax()
+        };
+        assert_eq!(rec.validate(), vec![ValidationWarning::IfrWithoutRunway]);
+        let rec = AirportPrimaryRecord {
+            ifr_capability: false,
+            ..rec
+        };
+        assert_eq!(rec.validate(), vec![]);
+    }
+
+    #[test]
+    fn test_elevation_out_of_range() {
+        for airport_elevation in [-1500, 15000] {
+            let rec = AirportPrimaryRecord {
+                airport_elevation,
+                ..klax()
+            };
+            assert_eq!(rec.validate(), vec![]);
+        }
+        for airport_elevation in [-1501, 15001] {
+            let rec = AirportPrimaryRecord {
+                airport_elevation,
+                ..klax()
+            };
+            assert_eq!(
+                rec.validate(),
+                vec![ValidationWarning::ElevationOutOfRange(airport_elevation)]
+            );
+        }
+    }
+
+    #[test]
+    fn test_cycle_out_of_range() {
+        for cycle in [0, 14] {
+            let rec = AirportPrimaryRecord {
+                cycle_date: CycleDate { year: 19, cycle },
+                ..klax()
+            };
+            assert_eq!(
+                rec.validate(),
+                vec![ValidationWarning::CycleOutOfRange(cycle)]
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_recommended_navaids() {
+        let airports = [
+            klax(),
+            AirportPrimaryRecord {
+                recommended_navaid: Some("LAX"),
+                ..klax()
+            },
+            AirportPrimaryRecord {
+                recommended_navaid: Some("SEA"),
+                ..klax()
+            },
+        ];
+        let lax = parse_vhf_navaid_record(
+            b"SUSAD        LAX   K2011360\
+            VTHW N33560033W118255598LAX N33560033W118255598E0150001811     \
+            NARLOS ANGELES                   123451906",
+        )
+        .unwrap();
+        let navaids = [
+            lax,
+            VhfNavaidRecord {
+                navaid_identifier: "DEN",
+                ..lax
+            },
+        ];
+        assert_eq!(
+            validate_recommended_navaids(&airports, &navaids),
+            vec![ValidationWarning::UnknownRecommendedNavaid { airport: 2 }]
+        );
+    }
+}